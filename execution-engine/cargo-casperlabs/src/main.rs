@@ -7,13 +7,14 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use clap::{crate_version, App, Arg};
+use clap::{crate_version, App, AppSettings, Arg, SubCommand};
 use lazy_static::lazy_static;
 
 pub mod common;
 mod contract_package;
 pub mod dependency;
 mod tests_package;
+mod validate;
 
 const APP_NAME: &str = "cargo-casperlabs";
 const ABOUT: &str =
@@ -34,11 +35,41 @@ const USE_SYSTEM_CONTRACTS_ARG_HELP: &str =
 const WORKSPACE_PATH_ARG_NAME: &str = "workspace-path";
 const WORKSPACE_PATH_ARG_LONG: &str = "workspace-path";
 
+const VALIDATE_SUBCOMMAND_NAME: &str = "validate";
+const VALIDATE_SUBCOMMAND_ABOUT: &str =
+    "Checks a compiled Wasm contract against the rules enforced by the execution engine";
+
+const WASM_PATH_ARG_NAME: &str = "wasm";
+const WASM_PATH_ARG_VALUE_NAME: &str = "wasm";
+const WASM_PATH_ARG_HELP: &str = "Path to the compiled Wasm contract";
+
+const MAX_STACK_HEIGHT_ARG_NAME: &str = "max-stack-height";
+const MAX_STACK_HEIGHT_ARG_LONG: &str = "max-stack-height";
+const MAX_STACK_HEIGHT_ARG_DEFAULT: &str = "65536";
+const MAX_STACK_HEIGHT_ARG_VALUE_NAME: &str = "NUM";
+const MAX_STACK_HEIGHT_ARG_HELP: &str =
+    "Max stack height of the network's Wasm costs, as given in its chainspec";
+
+const MAX_MEMORY_PAGES_ARG_NAME: &str = "max-memory-pages";
+const MAX_MEMORY_PAGES_ARG_LONG: &str = "max-memory-pages";
+const MAX_MEMORY_PAGES_ARG_DEFAULT: &str = "64";
+const MAX_MEMORY_PAGES_ARG_VALUE_NAME: &str = "NUM";
+const MAX_MEMORY_PAGES_ARG_HELP: &str =
+    "Max number of 64 KiB memory pages of the network's Wasm limits, as given in its chainspec";
+
+const MAX_MODULE_SIZE_ARG_NAME: &str = "max-module-size";
+const MAX_MODULE_SIZE_ARG_LONG: &str = "max-module-size";
+const MAX_MODULE_SIZE_ARG_DEFAULT: &str = "2097152";
+const MAX_MODULE_SIZE_ARG_VALUE_NAME: &str = "BYTES";
+const MAX_MODULE_SIZE_ARG_HELP: &str =
+    "Max module size in bytes of the network's Wasm limits, as given in its chainspec";
+
 const FAILURE_EXIT_CODE: i32 = 101;
 
 lazy_static! {
     static ref USAGE: String = format!(
         r#"cargo casperlabs [FLAGS] <path>
+    cargo casperlabs validate [OPTIONS] <wasm>
     rustup install {0}
     rustup target add --toolchain {0} wasm32-unknown-unknown
     cd <path>/tests
@@ -48,11 +79,20 @@ lazy_static! {
     static ref ARGS: Args = Args::new();
 }
 
+#[derive(Debug)]
+struct ValidateArgs {
+    wasm_path: PathBuf,
+    max_stack_height: u32,
+    max_memory_pages: u32,
+    max_module_size: u32,
+}
+
 #[derive(Debug)]
 struct Args {
-    root_path: PathBuf,
+    root_path: Option<PathBuf>,
     use_system_contracts: bool,
     workspace_path: Option<PathBuf>,
+    validate: Option<ValidateArgs>,
 }
 
 impl Args {
@@ -89,19 +129,51 @@ impl Args {
             .takes_value(true)
             .hidden(true);
 
+        let validate_subcommand = SubCommand::with_name(VALIDATE_SUBCOMMAND_NAME)
+            .about(VALIDATE_SUBCOMMAND_ABOUT)
+            .arg(
+                Arg::with_name(WASM_PATH_ARG_NAME)
+                    .required(true)
+                    .value_name(WASM_PATH_ARG_VALUE_NAME)
+                    .help(WASM_PATH_ARG_HELP),
+            )
+            .arg(
+                Arg::with_name(MAX_STACK_HEIGHT_ARG_NAME)
+                    .long(MAX_STACK_HEIGHT_ARG_LONG)
+                    .takes_value(true)
+                    .default_value(MAX_STACK_HEIGHT_ARG_DEFAULT)
+                    .value_name(MAX_STACK_HEIGHT_ARG_VALUE_NAME)
+                    .help(MAX_STACK_HEIGHT_ARG_HELP),
+            )
+            .arg(
+                Arg::with_name(MAX_MEMORY_PAGES_ARG_NAME)
+                    .long(MAX_MEMORY_PAGES_ARG_LONG)
+                    .takes_value(true)
+                    .default_value(MAX_MEMORY_PAGES_ARG_DEFAULT)
+                    .value_name(MAX_MEMORY_PAGES_ARG_VALUE_NAME)
+                    .help(MAX_MEMORY_PAGES_ARG_HELP),
+            )
+            .arg(
+                Arg::with_name(MAX_MODULE_SIZE_ARG_NAME)
+                    .long(MAX_MODULE_SIZE_ARG_LONG)
+                    .takes_value(true)
+                    .default_value(MAX_MODULE_SIZE_ARG_DEFAULT)
+                    .value_name(MAX_MODULE_SIZE_ARG_VALUE_NAME)
+                    .help(MAX_MODULE_SIZE_ARG_HELP),
+            );
+
         let arg_matches = App::new(APP_NAME)
             .version(crate_version!())
             .about(ABOUT)
             .usage(USAGE.as_str())
+            .setting(AppSettings::SubcommandsNegateReqs)
             .arg(root_path_arg)
             .arg(use_system_contracts_arg)
             .arg(workspace_path_arg)
+            .subcommand(validate_subcommand)
             .get_matches_from(filtered_args_iter);
 
-        let root_path = arg_matches
-            .value_of(ROOT_PATH_ARG_NAME)
-            .expect("expected path")
-            .into();
+        let root_path = arg_matches.value_of(ROOT_PATH_ARG_NAME).map(PathBuf::from);
 
         let use_system_contracts = arg_matches.is_present(USE_SYSTEM_CONTRACTS_ARG_NAME);
 
@@ -109,15 +181,52 @@ impl Args {
             .value_of(WORKSPACE_PATH_ARG_NAME)
             .map(PathBuf::from);
 
+        let validate = arg_matches
+            .subcommand_matches(VALIDATE_SUBCOMMAND_NAME)
+            .map(|validate_matches| {
+                let wasm_path = validate_matches
+                    .value_of(WASM_PATH_ARG_NAME)
+                    .expect("expected wasm path")
+                    .into();
+                let max_stack_height = validate_matches
+                    .value_of(MAX_STACK_HEIGHT_ARG_NAME)
+                    .map(str::parse)
+                    .expect("expected max stack height")
+                    .unwrap_or_else(|_| {
+                        common::print_error_and_exit(": invalid value for --max-stack-height")
+                    });
+                let max_memory_pages = validate_matches
+                    .value_of(MAX_MEMORY_PAGES_ARG_NAME)
+                    .map(str::parse)
+                    .expect("expected max memory pages")
+                    .unwrap_or_else(|_| {
+                        common::print_error_and_exit(": invalid value for --max-memory-pages")
+                    });
+                let max_module_size = validate_matches
+                    .value_of(MAX_MODULE_SIZE_ARG_NAME)
+                    .map(str::parse)
+                    .expect("expected max module size")
+                    .unwrap_or_else(|_| {
+                        common::print_error_and_exit(": invalid value for --max-module-size")
+                    });
+                ValidateArgs {
+                    wasm_path,
+                    max_stack_height,
+                    max_memory_pages,
+                    max_module_size,
+                }
+            });
+
         Args {
             root_path,
             use_system_contracts,
             workspace_path,
+            validate,
         }
     }
 
     pub fn root_path(&self) -> &Path {
-        &self.root_path
+        self.root_path.as_deref().expect("expected path")
     }
 
     pub fn use_system_contracts(&self) -> bool {
@@ -127,9 +236,23 @@ impl Args {
     pub fn workspace_path(&self) -> Option<&Path> {
         self.workspace_path.as_deref()
     }
+
+    fn validate(&self) -> Option<&ValidateArgs> {
+        self.validate.as_ref()
+    }
 }
 
 fn main() {
+    if let Some(validate_args) = ARGS.validate() {
+        validate::run(
+            &validate_args.wasm_path,
+            validate_args.max_stack_height,
+            validate_args.max_memory_pages,
+            validate_args.max_module_size,
+        );
+        return;
+    }
+
     if ARGS.root_path().exists() {
         common::print_error_and_exit(&format!(
             ": destination '{}' already exists",
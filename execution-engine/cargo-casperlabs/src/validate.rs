@@ -0,0 +1,49 @@
+//! Checks a compiled Wasm contract against the rules enforced by the execution engine.
+
+use std::{fs, path::Path, process};
+
+use colour::{e_red, green};
+
+use engine_wasm_prep::{wasm_costs::WasmCosts, wasm_limits::WasmLimits, Preprocessor};
+
+use crate::{common, FAILURE_EXIT_CODE};
+
+pub fn run(wasm_path: &Path, max_stack_height: u32, max_memory_pages: u32, max_module_size: u32) {
+    let module_bytes = match fs::read(wasm_path) {
+        Ok(module_bytes) => module_bytes,
+        Err(error) => common::print_error_and_exit(&format!(
+            ": failed to read '{}': {}",
+            wasm_path.display(),
+            error
+        )),
+    };
+
+    // Only the max stack height of the Wasm costs affects validation.
+    let wasm_costs = WasmCosts {
+        max_stack_height,
+        ..Default::default()
+    };
+    let wasm_limits = WasmLimits {
+        max_memory_pages,
+        max_module_size,
+        ..Default::default()
+    };
+    let report = Preprocessor::new(wasm_costs, wasm_limits).validate(&module_bytes);
+
+    if report.is_valid() {
+        green!("valid");
+        println!(": '{}'", wasm_path.display());
+        return;
+    }
+
+    for error in report.errors() {
+        e_red!("error");
+        eprintln!(": {}", error);
+    }
+    eprintln!(
+        "'{}' breaks {} rule(s) of the execution engine",
+        wasm_path.display(),
+        report.errors().len()
+    );
+    process::exit(FAILURE_EXIT_CODE)
+}
@@ -8,6 +8,7 @@ const FAILURE_EXIT_CODE: i32 = 101;
 const SUCCESS_EXIT_CODE: i32 = 0;
 const USE_SYSTEM_CONTRACTS: &str = "--use-system-contracts";
 const TURBO: &str = "turbo";
+const VALIDATE: &str = "validate";
 
 lazy_static! {
     static ref WORKSPACE_PATH_ARG: String =
@@ -82,3 +83,30 @@ fn should_succeed_without_using_system_contracts() {
 fn should_succeed_using_system_contracts() {
     run_tool_and_resulting_tests(false);
 }
+
+#[test]
+fn should_validate_compiled_contract() {
+    let wasm_path = format!("{}/wasm/standard_payment.wasm", env!("CARGO_MANIFEST_DIR"));
+    let mut command = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    command.args(&[VALIDATE, &wasm_path]);
+    let output = output_from_command(command);
+    assert_eq!(SUCCESS_EXIT_CODE, output.status.code().unwrap());
+}
+
+#[test]
+fn should_fail_to_validate_invalid_module() {
+    let wasm_path = TEST_DIR.path().join("invalid.wasm");
+    std::fs::write(&wasm_path, b"not wasm").unwrap();
+
+    let output_error = Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .arg(VALIDATE)
+        .arg(&wasm_path)
+        .unwrap_err();
+
+    let exit_code = output_error.as_output().unwrap().status.code().unwrap();
+    assert_eq!(FAILURE_EXIT_CODE, exit_code);
+
+    let stderr: String = String::from_utf8_lossy(&output_error.as_output().unwrap().stderr).into();
+    assert!(stderr.contains("Deserialization error"));
+}
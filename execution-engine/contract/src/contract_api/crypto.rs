@@ -0,0 +1,45 @@
+//! Functions for hashing and verifying signatures.
+
+use casperlabs_types::{
+    account::{PublicKey, ED25519_SIGNATURE_LENGTH},
+    api_error, BLAKE2B_DIGEST_LENGTH,
+};
+
+use super::to_ptr;
+use crate::{ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+
+/// Returns the blake2b-256 hash of the given bytes.
+pub fn blake2b<T: AsRef<[u8]>>(input: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let input = input.as_ref();
+    let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
+    let result = unsafe {
+        ext_ffi::blake2b(
+            input.as_ptr(),
+            input.len(),
+            ret.as_mut_ptr(),
+            BLAKE2B_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
+/// Returns `true` if `signature` is a valid Ed25519 signature of `message` by `public_key`.
+pub fn verify_signature(
+    message: &[u8],
+    signature: &[u8; ED25519_SIGNATURE_LENGTH],
+    public_key: PublicKey,
+) -> bool {
+    let (public_key_ptr, public_key_size, _bytes) = to_ptr(public_key);
+    let result = unsafe {
+        ext_ffi::verify_signature(
+            message.as_ptr(),
+            message.len(),
+            signature.as_ptr(),
+            signature.len(),
+            public_key_ptr,
+            public_key_size,
+        )
+    };
+    result != 0
+}
@@ -11,7 +11,7 @@ use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes},
     ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef,
-    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH, RANDOM_BYTES_COUNT,
 };
 
 use crate::{args_parser::ArgsParser, contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -66,6 +66,88 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
         unsafe { bytes_written.assume_init() }
     };
 
+    deserialize_contract_result(bytes_written)
+}
+
+/// Calls the function exported as `entry_point` by the given stored contract, passing the given
+/// arguments to it.
+///
+/// The contract must have been stored with that entry point, e.g. via
+/// [`storage::store_entry_points`](crate::contract_api::storage::store_entry_points), otherwise
+/// execution stops.  Return values are handled as per [`call_contract`].
+pub fn call_entry_point<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+) -> T {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = contract_api::to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_entry_point(
+                key_ptr,
+                key_size,
+                entry_point_ptr,
+                entry_point_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { bytes_written.assume_init() }
+    };
+
+    deserialize_contract_result(bytes_written)
+}
+
+/// Calls the function exported as `entry_point` by the given `version` of a contract package,
+/// passing the given arguments to it.
+///
+/// Calling a contract package via [`call_contract`] or [`call_entry_point`] instead calls its
+/// latest enabled version.  If `version` doesn't exist or has been disabled, execution stops.
+/// Return values are handled as per [`call_contract`].
+pub fn call_versioned_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    package: ContractRef,
+    version: u32,
+    entry_point: &str,
+    args: A,
+) -> T {
+    let package_key: Key = package.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(package_key);
+    let (entry_point_ptr, entry_point_size, _bytes2) = contract_api::to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_versioned_contract(
+                key_ptr,
+                key_size,
+                version,
+                entry_point_ptr,
+                entry_point_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { bytes_written.assume_init() }
+    };
+
+    deserialize_contract_result(bytes_written)
+}
+
+fn deserialize_contract_result<T: CLTyped + FromBytes>(bytes_written: usize) -> T {
     let serialized_result = if bytes_written == 0 {
         // If no bytes were written, the host buffer hasn't been set and hence shouldn't be read.
         vec![]
@@ -166,6 +248,19 @@ pub fn get_phase() -> Phase {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the next [`RANDOM_BYTES_COUNT`] bytes of a random stream seeded by the current deploy's
+/// hash and [`Phase`].
+///
+/// Successive calls within a deploy, including those made by called contracts, return different
+/// bytes, while re-executing the deploy always yields the same sequence.  Anyone who knows the
+/// deploy hash can compute the bytes, so the deployer can predict them.
+pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
+    let mut ret = [0u8; RANDOM_BYTES_COUNT];
+    let result = unsafe { ext_ffi::random_bytes(ret.as_mut_ptr(), RANDOM_BYTES_COUNT) };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
 /// Returns the requested named [`Key`] from the current context.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
@@ -222,6 +317,20 @@ pub fn remove_key(name: &str) {
     unsafe { ext_ffi::remove_key(name_ptr, name_size) }
 }
 
+/// Emits an event with the given `name` and `value`, recorded against the current context.
+///
+/// Events are returned to the host alongside the deploy's results, so that clients can observe what
+/// a contract did without inspecting its effects on global state.  If the deploy fails, its events
+/// are discarded.
+///
+/// The current context is either the caller's account or a stored contract depending on whether the
+/// currently-executing module is a direct call or a sub-call respectively.
+pub fn emit_event(name: &str, value: CLValue) {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let (value_ptr, value_size, _bytes2) = contract_api::to_ptr(value);
+    unsafe { ext_ffi::emit_event(name_ptr, name_size, value_ptr, value_size) };
+}
+
 /// Returns the named keys of the current context.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
@@ -138,6 +138,130 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Stores the serialized bytes of several exported, non-mangled `extern "C"` functions as a new
+/// contract under a [`URef`] generated by the host.
+///
+/// Each function keeps its name and can be invoked via
+/// [`runtime::call_entry_point`](crate::contract_api::runtime::call_entry_point).
+pub fn store_entry_points(entry_points: &[&str], named_keys: BTreeMap<String, Key>) -> ContractRef {
+    let entry_points: Vec<String> = entry_points
+        .iter()
+        .map(|name| String::from(*name))
+        .collect();
+    let (names_ptr, names_size, _bytes1) = contract_api::to_ptr(entry_points);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_entry_points(
+            names_ptr,
+            names_size,
+            keys_ptr,
+            keys_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::URef(URef::new(addr, AccessRights::READ_ADD_WRITE))
+}
+
+/// Stores the serialized bytes of several exported, non-mangled `extern "C"` functions as a new
+/// contract at an immutable address generated by the host.
+///
+/// Each function keeps its name and can be invoked via
+/// [`runtime::call_entry_point`](crate::contract_api::runtime::call_entry_point).
+pub fn store_entry_points_at_hash(
+    entry_points: &[&str],
+    named_keys: BTreeMap<String, Key>,
+) -> ContractRef {
+    let entry_points: Vec<String> = entry_points
+        .iter()
+        .map(|name| String::from(*name))
+        .collect();
+    let (names_ptr, names_size, _bytes1) = contract_api::to_ptr(entry_points);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_entry_points_at_hash(
+            names_ptr,
+            names_size,
+            keys_ptr,
+            keys_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::Hash(addr)
+}
+
+/// Creates an empty contract package under a new [`URef`].
+///
+/// Calling the package as a stored contract calls its latest enabled version.  Adding, disabling
+/// and enabling versions requires the write access granted by the returned `URef`.
+pub fn create_contract_package_at_uref() -> URef {
+    let key_ptr = contract_api::alloc_bytes(Key::max_serialized_length());
+    let bytes = unsafe {
+        ext_ffi::create_contract_package_at_uref(key_ptr); // URef has `READ_ADD_WRITE`
+        Vec::from_raw_parts(
+            key_ptr,
+            KEY_UREF_SERIALIZED_LENGTH,
+            KEY_UREF_SERIALIZED_LENGTH,
+        )
+    };
+    let key: Key = bytesrepr::deserialize(bytes).unwrap_or_revert();
+    if let Key::URef(uref) = key {
+        uref
+    } else {
+        runtime::revert(ApiError::UnexpectedKeyVariant);
+    }
+}
+
+/// Adds the stored `contract` as the newest version of the contract package under `package`, and
+/// returns the new version number.  Versions are numbered from 1.
+pub fn add_contract_version(package: URef, contract: ContractRef) -> u32 {
+    let package_key: Key = package.into();
+    let (package_key_ptr, package_key_size, _bytes1) = contract_api::to_ptr(package_key);
+    let contract_key: Key = contract.into();
+    let (contract_key_ptr, contract_key_size, _bytes2) = contract_api::to_ptr(contract_key);
+    let mut version = MaybeUninit::uninit();
+    unsafe {
+        ext_ffi::add_contract_version(
+            package_key_ptr,
+            package_key_size,
+            contract_key_ptr,
+            contract_key_size,
+            version.as_mut_ptr(),
+        );
+        version.assume_init()
+    }
+}
+
+/// Disables the given `version` of the contract package under `package`, so that it can no longer
+/// be called.  Callers of the package's latest version fall back to the newest enabled version.
+pub fn disable_contract_version(package: URef, version: u32) -> Result<(), ApiError> {
+    set_contract_version_enabled(package, version, false)
+}
+
+/// Re-enables the given `version` of the contract package under `package`.
+pub fn enable_contract_version(package: URef, version: u32) -> Result<(), ApiError> {
+    set_contract_version_enabled(package, version, true)
+}
+
+fn set_contract_version_enabled(
+    package: URef,
+    version: u32,
+    is_enabled: bool,
+) -> Result<(), ApiError> {
+    let package_key: Key = package.into();
+    let (package_key_ptr, package_key_size, _bytes) = contract_api::to_ptr(package_key);
+    let ret = unsafe {
+        ext_ffi::set_contract_version_enabled(
+            package_key_ptr,
+            package_key_size,
+            version,
+            u32::from(is_enabled),
+        )
+    };
+    api_error::result_from(ret)
+}
+
 /// Returns a new unforgeable pointer, where the value is initialized to `init`.
 pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
     let key_ptr = contract_api::alloc_bytes(Key::max_serialized_length());
@@ -104,4 +104,67 @@ extern "C" {
     ) -> i32;
     pub fn get_main_purse(dest_ptr: *mut u8);
     pub fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
+    pub fn emit_event(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn blake2b(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8, out_size: usize) -> i32;
+    pub fn random_bytes(out_ptr: *mut u8, out_size: usize) -> i32;
+    pub fn verify_signature(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
+    pub fn call_entry_point(
+        key_ptr: *const u8,
+        key_size: usize,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
+    pub fn store_entry_points(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        uref_addr_ptr: *const u8,
+    );
+    pub fn store_entry_points_at_hash(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn create_contract_package_at_uref(key_ptr: *mut u8);
+    pub fn add_contract_version(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        contract_key_ptr: *const u8,
+        contract_key_size: usize,
+        version_ptr: *mut u32,
+    );
+    pub fn set_contract_version_enabled(
+        package_key_ptr: *const u8,
+        package_key_size: usize,
+        version: u32,
+        is_enabled: u32,
+    ) -> i32;
+    pub fn call_versioned_contract(
+        key_ptr: *const u8,
+        key_size: usize,
+        version: u32,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
 }
@@ -13,6 +13,8 @@ pub const INIT_FLAG_KEY: [u8; 32] = [1u8; 32];
 pub const TOTAL_SUPPLY_KEY: [u8; 32] = [255u8; 32];
 pub const BALANCE_BYTE: u8 = 1;
 pub const PURSE_NAME: &str = "erc20_main_purse";
+pub const TRANSFER_EVENT: &str = "transfer";
+pub const APPROVAL_EVENT: &str = "approval";
 
 struct ERC20Token;
 
@@ -60,8 +62,9 @@ fn entry_point() {
     let mut token = ERC20Token;
     match Api::from_args() {
         Api::Transfer(recipient, amount) => {
-            match token.transfer(&runtime::get_caller(), &recipient, amount) {
-                Ok(()) => {}
+            let sender = runtime::get_caller();
+            match token.transfer(&sender, &recipient, amount) {
+                Ok(()) => emit_transfer(sender, recipient, amount),
                 Err(ERC20TransferError::NotEnoughBalance) => {
                     runtime::revert(Error::TransferFailureNotEnoughBalance)
                 }
@@ -69,7 +72,7 @@ fn entry_point() {
         }
         Api::TransferFrom(owner, recipient, amount) => {
             match token.transfer_from(&runtime::get_caller(), &owner, &recipient, amount) {
-                Ok(()) => {}
+                Ok(()) => emit_transfer(owner, recipient, amount),
                 Err(ERC20TransferFromError::TransferError(
                     ERC20TransferError::NotEnoughBalance,
                 )) => runtime::revert(Error::TransferFromFailureNotEnoughBalance),
@@ -78,7 +81,11 @@ fn entry_point() {
                 }
             };
         }
-        Api::Approve(spender, amount) => token.approve(&runtime::get_caller(), &spender, amount),
+        Api::Approve(spender, amount) => {
+            let owner = runtime::get_caller();
+            token.approve(&owner, &spender, amount);
+            emit_approval(owner, spender, amount);
+        }
         Api::BalanceOf(address) => {
             runtime::ret(CLValue::from_t(token.balance_of(&address)).unwrap_or_revert())
         }
@@ -102,6 +109,16 @@ fn entry_point() {
     }
 }
 
+fn emit_transfer(sender: PublicKey, recipient: PublicKey, amount: U512) {
+    let value = CLValue::from_t((sender, recipient, amount)).unwrap_or_revert();
+    runtime::emit_event(TRANSFER_EVENT, value);
+}
+
+fn emit_approval(owner: PublicKey, spender: PublicKey, amount: U512) {
+    let value = CLValue::from_t((owner, spender, amount)).unwrap_or_revert();
+    runtime::emit_event(APPROVAL_EVENT, value);
+}
+
 fn is_not_initialized() -> bool {
     let flag: Option<i32> = storage::read_local(&INIT_FLAG_KEY).unwrap_or_revert();
     flag.is_none()
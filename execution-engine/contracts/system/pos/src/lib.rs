@@ -13,10 +13,12 @@ use contract::{
 };
 use proof_of_stake::{
     MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    BONDING_KEY, ERA_HEIGHT_KEY, UNBONDING_KEY,
 };
 use types::{
-    account::PublicKey, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::PublicKey,
+    system_contract_errors::{mint, pos::Error},
+    ApiError, BlockTime, CLValue, Key, Phase, TransferResult, URef, U512,
 };
 
 const METHOD_BOND: &str = "bond";
@@ -25,9 +27,9 @@ const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
-
-const BONDING_KEY: u8 = 1;
-const UNBONDING_KEY: u8 = 2;
+const METHOD_SLASH: &str = "slash";
+const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+const METHOD_UNBOND_PAYOUT: &str = "unbond_payout";
 
 pub struct ProofOfStakeContract;
 
@@ -53,6 +55,12 @@ impl MintProvider for ProofOfStakeContract {
     fn balance(&mut self, purse: URef) -> Option<U512> {
         system::get_balance(purse)
     }
+
+    fn mint(&mut self, amount: U512) -> Result<URef, ()> {
+        let result: Result<URef, mint::Error> =
+            runtime::call_contract(system::get_mint(), ("mint", amount));
+        result.map_err(|_| ())
+    }
 }
 
 impl QueueProvider for ProofOfStakeContract {
@@ -79,6 +87,16 @@ impl QueueProvider for ProofOfStakeContract {
     fn write_unbonding(&mut self, queue: Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Reads the era height of the latest unbonding payout from the local state of the contract.
+    fn read_era_height(&mut self) -> Option<u64> {
+        storage::read_local(&ERA_HEIGHT_KEY).unwrap_or_default()
+    }
+
+    /// Writes the era height of the latest unbonding payout to the local state of the contract.
+    fn write_era_height(&mut self, era_height: u64) {
+        storage::write_local(ERA_HEIGHT_KEY, era_height);
+    }
 }
 
 impl RuntimeProvider for ProofOfStakeContract {
@@ -232,6 +250,27 @@ pub fn delegate() {
                 .finalize_payment(amount_spent, account)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn slash(slashes: BTreeMap<PublicKey, U512>)`
+        METHOD_SLASH => {
+            let slashes: BTreeMap<PublicKey, U512> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract.slash(slashes).unwrap_or_revert();
+        }
+        // Type of this method: `fn distribute_rewards(rewards: BTreeMap<PublicKey, U512>)`
+        METHOD_DISTRIBUTE_REWARDS => {
+            let rewards: BTreeMap<PublicKey, U512> = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract.distribute_rewards(rewards).unwrap_or_revert();
+        }
+        // Type of this method: `fn unbond_payout(era_height: u64)`
+        METHOD_UNBOND_PAYOUT => {
+            let era_height: u64 = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract.unbond_payout(era_height).unwrap_or_revert();
+        }
         _ => {}
     }
 }
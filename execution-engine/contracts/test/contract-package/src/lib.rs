@@ -0,0 +1,108 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, CLValue, ContractRef, Key, URef};
+
+const PACKAGE_NAME: &str = "contract_package";
+const VERSION_ONE_FUNCTION: &str = "version_one";
+const VERSION_TWO_FUNCTION: &str = "version_two";
+const CALL_ENTRY_POINT: &str = "call";
+const CREATE: &str = "create";
+const CALL_LATEST: &str = "call-latest";
+const CALL_VERSION: &str = "call-version";
+const DISABLE: &str = "disable";
+const ENABLE: &str = "enable";
+const DISABLE_READ_ONLY: &str = "disable-read-only";
+
+#[repr(u16)]
+enum Error {
+    UnexpectedVersionNumber = 1,
+    UnexpectedResult = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn version_one() {
+    runtime::ret(CLValue::from_t(1u32).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn version_two() {
+    runtime::ret(CLValue::from_t(2u32).unwrap_or_revert())
+}
+
+fn get_package() -> URef {
+    match runtime::get_key(PACKAGE_NAME).unwrap_or_revert_with(ApiError::GetKey) {
+        Key::URef(uref) => uref,
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+fn get_arg<T: CLTyped + FromBytes>(i: u32) -> T {
+    runtime::get_arg(i)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn check_result(result: u32, expected_arg_index: u32) {
+    let expected: u32 = get_arg(expected_arg_index);
+    if result != expected {
+        runtime::revert(ApiError::User(Error::UnexpectedResult as u16));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = get_arg(0);
+
+    match method_name.as_str() {
+        CREATE => {
+            let package = storage::create_contract_package_at_uref();
+            for (expected_version, function) in [VERSION_ONE_FUNCTION, VERSION_TWO_FUNCTION]
+                .iter()
+                .enumerate()
+            {
+                let contract_ref = storage::store_function_at_hash(function, BTreeMap::new());
+                let version = storage::add_contract_version(package, contract_ref);
+                if version != expected_version as u32 + 1 {
+                    runtime::revert(ApiError::User(Error::UnexpectedVersionNumber as u16));
+                }
+            }
+            runtime::put_key(PACKAGE_NAME, package.into());
+        }
+        CALL_LATEST => {
+            let result: u32 = runtime::call_contract(ContractRef::URef(get_package()), ());
+            check_result(result, 1);
+        }
+        CALL_VERSION => {
+            let version: u32 = get_arg(1);
+            let result: u32 = runtime::call_versioned_contract(
+                ContractRef::URef(get_package()),
+                version,
+                CALL_ENTRY_POINT,
+                (),
+            );
+            check_result(result, 2);
+        }
+        DISABLE => {
+            let version: u32 = get_arg(1);
+            storage::disable_contract_version(get_package(), version).unwrap_or_revert();
+        }
+        ENABLE => {
+            let version: u32 = get_arg(1);
+            storage::enable_contract_version(get_package(), version).unwrap_or_revert();
+        }
+        DISABLE_READ_ONLY => {
+            let version: u32 = get_arg(1);
+            storage::disable_contract_version(get_package().into_read(), version)
+                .unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,98 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use contract::{
+    contract_api::{crypto, runtime},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{PublicKey, ED25519_SIGNATURE_LENGTH},
+    api_error,
+    bytesrepr::FromBytes,
+    ApiError, CLTyped, BLAKE2B_DIGEST_LENGTH,
+};
+
+const BLAKE2B: &str = "blake2b";
+const BLAKE2B_OVERSIZED: &str = "blake2b-oversized";
+const BLAKE2B_UNDERSIZED: &str = "blake2b-undersized";
+const VERIFY_SIGNATURE: &str = "verify-signature";
+
+#[repr(u16)]
+enum Error {
+    HashMismatch = 1,
+    VerificationMismatch = 2,
+}
+
+#[repr(u32)]
+enum Args {
+    MethodName = 0,
+    Arg1 = 1,
+    Arg2 = 2,
+    Arg3 = 3,
+    Arg4 = 4,
+}
+
+fn get_arg<T: CLTyped + FromBytes>(index: Args) -> T {
+    runtime::get_arg(index as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = get_arg(Args::MethodName);
+
+    match method_name.as_str() {
+        BLAKE2B => {
+            let input: Vec<u8> = get_arg(Args::Arg1);
+            let expected_hash: [u8; BLAKE2B_DIGEST_LENGTH] = get_arg(Args::Arg2);
+            if crypto::blake2b(input) != expected_hash {
+                runtime::revert(ApiError::User(Error::HashMismatch as u16));
+            }
+        }
+        BLAKE2B_OVERSIZED => {
+            let input: Vec<u8> = get_arg(Args::Arg1);
+            let expected_hash: [u8; BLAKE2B_DIGEST_LENGTH] = get_arg(Args::Arg2);
+            let mut buffer = [0u8; BLAKE2B_DIGEST_LENGTH + 1];
+            let result = unsafe {
+                ext_ffi::blake2b(
+                    input.as_ptr(),
+                    input.len(),
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                )
+            };
+            api_error::result_from(result).unwrap_or_revert();
+            if buffer[..BLAKE2B_DIGEST_LENGTH] != expected_hash {
+                runtime::revert(ApiError::User(Error::HashMismatch as u16));
+            }
+        }
+        BLAKE2B_UNDERSIZED => {
+            let input: Vec<u8> = get_arg(Args::Arg1);
+            let mut buffer = [0u8; BLAKE2B_DIGEST_LENGTH - 1];
+            let result = unsafe {
+                ext_ffi::blake2b(
+                    input.as_ptr(),
+                    input.len(),
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                )
+            };
+            api_error::result_from(result).unwrap_or_revert();
+        }
+        VERIFY_SIGNATURE => {
+            let message: Vec<u8> = get_arg(Args::Arg1);
+            let signature: [u8; ED25519_SIGNATURE_LENGTH] = get_arg(Args::Arg2);
+            let public_key: PublicKey = get_arg(Args::Arg3);
+            let expected_validity: bool = get_arg(Args::Arg4);
+            if crypto::verify_signature(&message, &signature, public_key) != expected_validity {
+                runtime::revert(ApiError::User(Error::VerificationMismatch as u16));
+            }
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
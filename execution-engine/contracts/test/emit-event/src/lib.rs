@@ -0,0 +1,57 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue};
+
+const SUBCALL_NAME: &str = "emit_from_subcall";
+const EMIT_FROM_SESSION: &str = "emit-from-session";
+const EMIT_VIA_SUBCALL: &str = "emit-via-subcall";
+const EMIT_AND_REVERT: &str = "emit-and-revert";
+const SESSION_EVENT_NAME: &str = "session_event";
+const SESSION_EVENT_VALUE: u64 = 1;
+const SUBCALL_EVENT_NAME: &str = "subcall_event";
+const SUBCALL_EVENT_VALUE: &str = "Hello, world!";
+const REVERT_ERROR: u16 = 100;
+
+enum Args {
+    MethodName = 0,
+}
+
+fn emit_session_event() {
+    let value = CLValue::from_t(SESSION_EVENT_VALUE).unwrap_or_revert();
+    runtime::emit_event(SESSION_EVENT_NAME, value);
+}
+
+#[no_mangle]
+pub extern "C" fn emit_from_subcall() {
+    let value = CLValue::from_t(String::from(SUBCALL_EVENT_VALUE)).unwrap_or_revert();
+    runtime::emit_event(SUBCALL_EVENT_NAME, value);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method_name.as_str() {
+        EMIT_FROM_SESSION => emit_session_event(),
+        EMIT_VIA_SUBCALL => {
+            let reference = storage::store_function_at_hash(SUBCALL_NAME, BTreeMap::new());
+            runtime::call_contract::<_, ()>(reference, ());
+            emit_session_event();
+        }
+        EMIT_AND_REVERT => {
+            emit_session_event();
+            runtime::revert(ApiError::User(REVERT_ERROR));
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,73 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, ContractRef, Key};
+
+const CONTRACT_NAME: &str = "entry_points";
+const GREET_ENTRY_POINT: &str = "greet";
+const SET_GREETING_ENTRY_POINT: &str = "set_greeting";
+const MISSING_ENTRY_POINT: &str = "missing";
+const GREETING_KEY: &str = "greeting";
+const GREETING: &str = "Hello, world!";
+const STORE: &str = "store";
+const CALL_GREET: &str = "call-greet";
+const CALL_MISSING: &str = "call-missing";
+
+#[repr(u16)]
+enum Error {
+    UnexpectedGreeting = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn greet() {
+    let value = CLValue::from_t(String::from(GREETING)).unwrap_or_revert();
+    runtime::ret(value)
+}
+
+#[no_mangle]
+pub extern "C" fn set_greeting() {
+    let greeting = storage::new_uref(String::from(GREETING));
+    runtime::put_key(GREETING_KEY, greeting.into());
+}
+
+fn get_contract_ref() -> ContractRef {
+    match runtime::get_key(CONTRACT_NAME).unwrap_or_revert_with(ApiError::GetKey) {
+        Key::Hash(hash) => ContractRef::Hash(hash),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method_name.as_str() {
+        STORE => {
+            let contract_ref = storage::store_entry_points_at_hash(
+                &[GREET_ENTRY_POINT, SET_GREETING_ENTRY_POINT],
+                BTreeMap::new(),
+            );
+            runtime::put_key(CONTRACT_NAME, contract_ref.into());
+        }
+        CALL_GREET => {
+            let greeting: String =
+                runtime::call_entry_point(get_contract_ref(), GREET_ENTRY_POINT, ());
+            if greeting != GREETING {
+                runtime::revert(ApiError::User(Error::UnexpectedGreeting as u16));
+            }
+        }
+        CALL_MISSING => {
+            runtime::call_entry_point::<_, ()>(get_contract_ref(), MISSING_ENTRY_POINT, ());
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
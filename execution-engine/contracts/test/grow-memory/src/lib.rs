@@ -0,0 +1,25 @@
+#![no_std]
+
+use core::arch::wasm32;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+const MEMORY_INDEX: u32 = 0;
+const GROW_FAILED: usize = usize::max_value();
+
+#[repr(u16)]
+enum Error {
+    GrowMemory = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let pages: u32 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if wasm32::memory_grow(MEMORY_INDEX, pages as usize) == GROW_FAILED {
+        runtime::revert(ApiError::User(Error::GrowMemory as u16));
+    }
+}
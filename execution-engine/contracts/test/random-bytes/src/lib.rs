@@ -0,0 +1,77 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{api_error, ApiError, CLValue, RANDOM_BYTES_COUNT};
+
+const DRAW: &str = "draw";
+const DRAW_VIA_SUBCALL: &str = "draw-via-subcall";
+const NEW_UREF: &str = "new-uref";
+const DRAW_AND_NEW_UREF: &str = "draw-and-new-uref";
+const DRAW_OVERSIZED: &str = "draw-oversized";
+const DRAW_UNDERSIZED: &str = "draw-undersized";
+const FIRST_DRAW_KEY: &str = "first_draw";
+const SECOND_DRAW_KEY: &str = "second_draw";
+const UREF_KEY: &str = "uref";
+
+fn store_draws(first: [u8; RANDOM_BYTES_COUNT], second: [u8; RANDOM_BYTES_COUNT]) {
+    runtime::put_key(FIRST_DRAW_KEY, storage::new_uref(first).into());
+    runtime::put_key(SECOND_DRAW_KEY, storage::new_uref(second).into());
+}
+
+#[no_mangle]
+pub extern "C" fn draw_random_bytes() {
+    let random_bytes = runtime::random_bytes();
+    runtime::ret(CLValue::from_t(random_bytes).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method_name.as_str() {
+        DRAW => {
+            let first = runtime::random_bytes();
+            let second = runtime::random_bytes();
+            store_draws(first, second);
+        }
+        DRAW_VIA_SUBCALL => {
+            let contract_ref =
+                storage::store_function_at_hash("draw_random_bytes", BTreeMap::new());
+            let first = runtime::random_bytes();
+            let second = runtime::call_contract(contract_ref, ());
+            store_draws(first, second);
+        }
+        NEW_UREF => {
+            runtime::put_key(UREF_KEY, storage::new_uref(()).into());
+        }
+        DRAW_AND_NEW_UREF => {
+            runtime::random_bytes();
+            runtime::put_key(UREF_KEY, storage::new_uref(()).into());
+        }
+        DRAW_OVERSIZED => {
+            let mut buffer = [0u8; RANDOM_BYTES_COUNT + 1];
+            let result = unsafe { ext_ffi::random_bytes(buffer.as_mut_ptr(), buffer.len()) };
+            api_error::result_from(result).unwrap_or_revert();
+            let mut first = [0u8; RANDOM_BYTES_COUNT];
+            first.copy_from_slice(&buffer[..RANDOM_BYTES_COUNT]);
+            let second = runtime::random_bytes();
+            store_draws(first, second);
+        }
+        DRAW_UNDERSIZED => {
+            let mut buffer = [0u8; RANDOM_BYTES_COUNT - 1];
+            let result = unsafe { ext_ffi::random_bytes(buffer.as_mut_ptr(), buffer.len()) };
+            api_error::result_from(result).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use proof_of_stake::QueueEntry;
+use types::{account::PublicKey, BlockTime, ProtocolVersion, U512};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidStateRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+}
+
+impl BidStateRequest {
+    pub fn new(pre_state_hash: Blake2bHash, protocol_version: ProtocolVersion) -> Self {
+        BidStateRequest {
+            pre_state_hash,
+            protocol_version,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
+
+/// A bonding or unbonding request which is not yet applied to the stakes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueuedChange {
+    validator: PublicKey,
+    amount: U512,
+    timestamp: BlockTime,
+    era_height: u64,
+}
+
+impl QueuedChange {
+    pub fn new(validator: PublicKey, amount: U512, timestamp: BlockTime, era_height: u64) -> Self {
+        QueuedChange {
+            validator,
+            amount,
+            timestamp,
+            era_height,
+        }
+    }
+
+    pub fn validator(&self) -> PublicKey {
+        self.validator
+    }
+
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+
+    pub fn timestamp(&self) -> BlockTime {
+        self.timestamp
+    }
+
+    pub fn era_height(&self) -> u64 {
+        self.era_height
+    }
+}
+
+impl From<QueueEntry> for QueuedChange {
+    fn from(entry: QueueEntry) -> Self {
+        QueuedChange::new(
+            entry.validator,
+            entry.amount,
+            entry.timestamp,
+            entry.era_height,
+        )
+    }
+}
+
+/// The bids held by the Proof of Stake contract: the current stakes of the bonded validators, and
+/// the bonding and unbonding requests which are not yet applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BidState {
+    bids: BTreeMap<PublicKey, U512>,
+    bonding_queue: Vec<QueuedChange>,
+    unbonding_queue: Vec<QueuedChange>,
+}
+
+impl BidState {
+    pub fn new(
+        bids: BTreeMap<PublicKey, U512>,
+        bonding_queue: Vec<QueuedChange>,
+        unbonding_queue: Vec<QueuedChange>,
+    ) -> Self {
+        BidState {
+            bids,
+            bonding_queue,
+            unbonding_queue,
+        }
+    }
+
+    pub fn bids(&self) -> &BTreeMap<PublicKey, U512> {
+        &self.bids
+    }
+
+    pub fn bonding_queue(&self) -> &[QueuedChange] {
+        &self.bonding_queue
+    }
+
+    pub fn unbonding_queue(&self) -> &[QueuedChange] {
+        &self.unbonding_queue
+    }
+}
+
+pub enum BidStateResult {
+    RootNotFound,
+    Success(BidState),
+}
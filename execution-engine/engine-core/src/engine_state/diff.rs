@@ -0,0 +1,40 @@
+use engine_shared::newtypes::Blake2bHash;
+use engine_storage::global_state::{self, TrieDiff};
+use types::Key;
+
+pub enum DiffResult {
+    RootNotFound(Blake2bHash),
+    Success(TrieDiff<Key>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRequest {
+    left_state_hash: Blake2bHash,
+    right_state_hash: Blake2bHash,
+}
+
+impl DiffRequest {
+    pub fn new(left_state_hash: Blake2bHash, right_state_hash: Blake2bHash) -> Self {
+        DiffRequest {
+            left_state_hash,
+            right_state_hash,
+        }
+    }
+
+    pub fn left_state_hash(&self) -> Blake2bHash {
+        self.left_state_hash
+    }
+
+    pub fn right_state_hash(&self) -> Blake2bHash {
+        self.right_state_hash
+    }
+}
+
+impl From<global_state::DiffResult<Key>> for DiffResult {
+    fn from(diff_result: global_state::DiffResult<Key>) -> Self {
+        match diff_result {
+            global_state::DiffResult::Found(trie_diff) => DiffResult::Success(trie_diff),
+            global_state::DiffResult::RootNotFound(root) => DiffResult::RootNotFound(root),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+use crate::engine_state::{system_call::SystemCallResult, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistributeRewardsRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    rewards: Vec<(PublicKey, U512)>,
+}
+
+impl DistributeRewardsRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        rewards: Vec<(PublicKey, U512)>,
+    ) -> Self {
+        DistributeRewardsRequest {
+            pre_state_hash,
+            protocol_version,
+            rewards,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn rewards(&self) -> &[(PublicKey, U512)] {
+        &self.rewards
+    }
+}
+
+#[derive(Debug)]
+pub enum DistributeRewardsError {
+    /// The rewarded validator is not bonded.
+    UnknownValidator(PublicKey),
+    /// Executing the Proof of Stake contract failed.
+    Execution(Error),
+}
+
+impl fmt::Display for DistributeRewardsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            DistributeRewardsError::UnknownValidator(validator) => {
+                write!(f, "Unknown validator: {}", validator)
+            }
+            DistributeRewardsError::Execution(error) => write!(f, "Execution error: {}", error),
+        }
+    }
+}
+
+pub type DistributeRewardsResult = SystemCallResult<DistributeRewardsError>;
@@ -1,9 +1,22 @@
+use super::module_cache::DEFAULT_MODULE_CACHE_CAPACITY;
+
 /// The runtime configuration of the execution engine
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     highway: bool,
+    module_cache_capacity: usize,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            use_system_contracts: false,
+            highway: false,
+            module_cache_capacity: DEFAULT_MODULE_CACHE_CAPACITY,
+        }
+    }
 }
 
 impl EngineConfig {
@@ -29,4 +42,14 @@ impl EngineConfig {
         self.highway = highway;
         self
     }
+
+    /// The maximum number of deserialized stored contracts held in the module cache.
+    pub fn module_cache_capacity(self) -> usize {
+        self.module_cache_capacity
+    }
+
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> EngineConfig {
+        self.module_cache_capacity = module_cache_capacity;
+        self
+    }
 }
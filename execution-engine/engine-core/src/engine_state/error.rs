@@ -1,7 +1,8 @@
 use failure::Fail;
 
 use engine_shared::newtypes::Blake2bHash;
-use types::{bytesrepr, system_contract_errors::mint};
+use engine_wasm_prep::wasm_limits::WasmLimits;
+use types::{bytesrepr, system_contract_errors::mint, Key};
 
 use crate::execution;
 use types::ProtocolVersion;
@@ -16,6 +17,10 @@ pub enum Error {
     InvalidProtocolVersion(ProtocolVersion),
     #[fail(display = "Invalid upgrade config")]
     InvalidUpgradeConfig,
+    #[fail(display = "Invalid Wasm limits: {:?}", _0)]
+    InvalidWasmLimits(WasmLimits),
+    #[fail(display = "Deploy has already been executed")]
+    DeployReplayed,
     #[fail(display = "Wasm preprocessing error: {}", _0)]
     WasmPreprocessing(engine_wasm_prep::PreprocessingError),
     #[fail(display = "Wasm serialization error: {:?}", _0)]
@@ -38,6 +43,10 @@ pub enum Error {
     Serialization(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     Mint(mint::Error),
+    #[fail(display = "Expected a stored contract")]
+    ExpectedStoredContract,
+    #[fail(display = "View call attempted to write to {}", _0)]
+    ViewWrite(Key),
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -0,0 +1,56 @@
+use engine_shared::newtypes::Blake2bHash;
+use types::ProtocolVersion;
+
+use crate::engine_state::{
+    deploy_item::DeployItem, execution_effect::ExecutionEffect, execution_result::ExecutionResult,
+    Error,
+};
+
+pub struct EstimateRequest {
+    pub state_hash: Blake2bHash,
+    pub block_time: u64,
+    pub deploy_item: DeployItem,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl EstimateRequest {
+    pub fn new(
+        state_hash: Blake2bHash,
+        block_time: u64,
+        deploy_item: DeployItem,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        EstimateRequest {
+            state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        }
+    }
+}
+
+pub enum EstimateResult {
+    RootNotFound,
+    /// The results of executing the payment and session code of the deploy without a gas limit.
+    ///
+    /// `payment_result` is a precondition failure if the deploy could not be executed at all, and
+    /// the session code is only executed if the payment code succeeded.  `effect` holds the
+    /// combined transforms of the payment code and, if it succeeded, the session code.  It does
+    /// not include the finalization of the payment.
+    Success {
+        payment_result: ExecutionResult,
+        maybe_session_result: Option<ExecutionResult>,
+        effect: ExecutionEffect,
+    },
+}
+
+impl EstimateResult {
+    /// Constructs an [`EstimateResult::Success`] for a deploy which could not be executed at all.
+    pub fn precondition_failure(error: Error) -> Self {
+        EstimateResult::Success {
+            payment_result: ExecutionResult::precondition_failure(error),
+            maybe_session_result: None,
+            effect: ExecutionEffect::default(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use types::{CLValue, Key};
+
+/// An event emitted by a contract via `runtime::emit_event`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    source: Key,
+    name: String,
+    value: CLValue,
+}
+
+impl Event {
+    pub fn new(source: Key, name: String, value: CLValue) -> Self {
+        Event {
+            source,
+            name,
+            value,
+        }
+    }
+
+    /// The key of the account or contract which emitted the event.
+    pub fn source(&self) -> Key {
+        self.source
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &CLValue {
+        &self.value
+    }
+
+    pub fn destructure(self) -> (Key, String, CLValue) {
+        (self.source, self.name, self.value)
+    }
+}
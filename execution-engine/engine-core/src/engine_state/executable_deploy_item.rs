@@ -1,3 +1,6 @@
+/// The name of the entry point invoked when a deploy or call does not name one.
+pub const DEFAULT_ENTRY_POINT_NAME: &str = "call";
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum ExecutableDeployItem {
     ModuleBytes {
@@ -6,19 +9,33 @@ pub enum ExecutableDeployItem {
     },
     StoredContractByHash {
         hash: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
     },
     StoredContractByName {
         name: String,
+        entry_point: String,
         args: Vec<u8>,
     },
     StoredContractByURef {
         uref: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
     },
 }
 
 impl ExecutableDeployItem {
+    /// Returns the name of the exported function to invoke.  Module bytes are always entered via
+    /// [`DEFAULT_ENTRY_POINT_NAME`].
+    pub fn entry_point(&self) -> &str {
+        match self {
+            ExecutableDeployItem::ModuleBytes { .. } => DEFAULT_ENTRY_POINT_NAME,
+            ExecutableDeployItem::StoredContractByHash { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByName { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByURef { entry_point, .. } => entry_point,
+        }
+    }
+
     pub fn take_args(self) -> Vec<u8> {
         match self {
             ExecutableDeployItem::ModuleBytes { args, .. } => args,
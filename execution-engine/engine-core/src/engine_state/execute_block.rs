@@ -0,0 +1,31 @@
+use engine_storage::global_state::CommitResult;
+
+use crate::engine_state::execution_result::ExecutionResult;
+
+/// The results of executing the deploys of a block in order, and of committing their combined
+/// effects.
+pub struct ExecuteBlockResult {
+    execution_results: Vec<ExecutionResult>,
+    commit_result: CommitResult,
+}
+
+impl ExecuteBlockResult {
+    pub fn new(execution_results: Vec<ExecutionResult>, commit_result: CommitResult) -> Self {
+        ExecuteBlockResult {
+            execution_results,
+            commit_result,
+        }
+    }
+
+    pub fn execution_results(&self) -> &[ExecutionResult] {
+        &self.execution_results
+    }
+
+    pub fn commit_result(&self) -> &CommitResult {
+        &self.commit_result
+    }
+
+    pub fn take(self) -> (Vec<ExecutionResult>, CommitResult) {
+        (self.execution_results, self.commit_result)
+    }
+}
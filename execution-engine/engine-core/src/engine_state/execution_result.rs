@@ -1,4 +1,4 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use super::{error, event::Event, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
 use engine_shared::{
     additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
     stored_value::StoredValue, transform::Transform,
@@ -45,9 +45,18 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        /// Net number of bytes added to global state, charged for as part of `cost`
+        storage_bytes: u64,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        /// Net number of bytes added to global state, charged for as part of `cost`
+        storage_bytes: u64,
+        /// Events emitted by the executed contracts, in the order they were emitted
+        events: Vec<Event>,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +75,7 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            storage_bytes: 0,
         }
     }
 
@@ -99,6 +109,22 @@ impl ExecutionResult {
         }
     }
 
+    pub fn storage_bytes(&self) -> u64 {
+        match self {
+            ExecutionResult::Failure { storage_bytes, .. } => *storage_bytes,
+            ExecutionResult::Success { storage_bytes, .. } => *storage_bytes,
+        }
+    }
+
+    /// Returns the events emitted during a successful execution.  The events of a failed execution
+    /// are discarded along with its effects.
+    pub fn events(&self) -> &[Event] {
+        match self {
+            ExecutionResult::Failure { .. } => &[],
+            ExecutionResult::Success { events, .. } => events,
+        }
+    }
+
     pub fn effect(&self) -> &ExecutionEffect {
         match self {
             ExecutionResult::Failure { effect, .. } => effect,
@@ -108,23 +134,101 @@ impl ExecutionResult {
 
     pub fn with_cost(self, cost: Gas) -> Self {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                storage_bytes,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                storage_bytes,
+            },
+            ExecutionResult::Success {
+                effect,
+                storage_bytes,
+                events,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                storage_bytes,
+                events,
+            },
+        }
+    }
+
+    pub fn with_storage_bytes(self, storage_bytes: u64) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                storage_bytes,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                events,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                storage_bytes,
+                events,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                cost,
+                storage_bytes,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                storage_bytes,
+            },
+            ExecutionResult::Success {
+                cost,
+                storage_bytes,
+                events,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                storage_bytes,
+                events,
+            },
+        }
+    }
+
+    /// Sets the events of a successful execution.  A failed execution is returned unchanged, as its
+    /// events are discarded.
+    pub fn with_events(self, events: Vec<Event>) -> Self {
+        match self {
+            failure @ ExecutionResult::Failure { .. } => failure,
+            ExecutionResult::Success {
+                effect,
+                cost,
+                storage_bytes,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                storage_bytes,
+                events,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
@@ -191,6 +295,7 @@ impl ExecutionResult {
             error,
             effect,
             cost,
+            storage_bytes: 0,
         }
     }
 }
@@ -258,18 +363,36 @@ impl ExecutionResultBuilder {
         payment_cost + session_cost
     }
 
+    pub fn total_storage_bytes(&self) -> u64 {
+        let payment_storage_bytes = self
+            .payment_execution_result
+            .as_ref()
+            .map(ExecutionResult::storage_bytes)
+            .unwrap_or_default();
+        let session_storage_bytes = self
+            .session_execution_result
+            .as_ref()
+            .map(ExecutionResult::storage_bytes)
+            .unwrap_or_default();
+        payment_storage_bytes.saturating_add(session_storage_bytes)
+    }
+
     pub fn build<R: StateReader<Key, StoredValue>>(
         self,
         reader: &R,
         correlation_id: CorrelationId,
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let cost = self.total_cost();
+        let storage_bytes = self.total_storage_bytes();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
+        let mut events = Vec::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            storage_bytes,
+            events: Vec::new(),
         };
 
         match self.payment_execution_result {
@@ -278,6 +401,7 @@ impl ExecutionResultBuilder {
                     return Ok(result);
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.extend_from_slice(result.events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -288,9 +412,10 @@ impl ExecutionResultBuilder {
         match self.session_execution_result {
             Some(result) => {
                 if result.is_failure() {
-                    ret = result.with_cost(cost);
+                    ret = result.with_cost(cost).with_storage_bytes(storage_bytes);
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.extend_from_slice(result.events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -305,6 +430,7 @@ impl ExecutionResultBuilder {
                     ));
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.extend_from_slice(result.events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
@@ -313,7 +439,7 @@ impl ExecutionResultBuilder {
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret.with_effect(reduced_effect).with_events(events))
     }
 
     fn add_effects(
@@ -8,7 +8,12 @@ use rand::{
 
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::{HostFunctionCost, HostFunctionCosts},
+    storage_costs::StorageCosts,
+    wasm_costs::WasmCosts,
+    wasm_limits::WasmLimits,
+};
 use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -119,6 +124,10 @@ pub struct GenesisConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    host_function_costs: HostFunctionCosts,
+    storage_costs: StorageCosts,
+    wasm_limits: WasmLimits,
+    deploy_hash_expiry_millis: u64,
 }
 
 impl GenesisConfig {
@@ -132,6 +141,10 @@ impl GenesisConfig {
         standard_payment_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        host_function_costs: HostFunctionCosts,
+        storage_costs: StorageCosts,
+        wasm_limits: WasmLimits,
+        deploy_hash_expiry_millis: u64,
     ) -> Self {
         GenesisConfig {
             name,
@@ -142,6 +155,10 @@ impl GenesisConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
         }
     }
 
@@ -173,6 +190,22 @@ impl GenesisConfig {
         self.wasm_costs
     }
 
+    pub fn host_function_costs(&self) -> HostFunctionCosts {
+        self.host_function_costs
+    }
+
+    pub fn storage_costs(&self) -> StorageCosts {
+        self.storage_costs
+    }
+
+    pub fn wasm_limits(&self) -> WasmLimits {
+        self.wasm_limits
+    }
+
+    pub fn deploy_hash_expiry_millis(&self) -> u64 {
+        self.deploy_hash_expiry_millis
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -235,6 +268,61 @@ impl Distribution<GenesisConfig> for Standard {
             opcodes_div: rng.gen(),
         };
 
+        let host_function_costs = {
+            let mut host_function_cost = || HostFunctionCost::new(rng.gen(), rng.gen());
+            HostFunctionCosts {
+                read: host_function_cost(),
+                read_local: host_function_cost(),
+                load_named_keys: host_function_cost(),
+                write: host_function_cost(),
+                write_local: host_function_cost(),
+                add: host_function_cost(),
+                add_local: host_function_cost(),
+                new_uref: host_function_cost(),
+                get_arg_size: host_function_cost(),
+                get_arg: host_function_cost(),
+                ret: host_function_cost(),
+                call_contract: host_function_cost(),
+                get_key: host_function_cost(),
+                has_key: host_function_cost(),
+                put_key: host_function_cost(),
+                remove_key: host_function_cost(),
+                get_caller: host_function_cost(),
+                get_blocktime: host_function_cost(),
+                store_function: host_function_cost(),
+                store_function_at_hash: host_function_cost(),
+                is_valid_uref: host_function_cost(),
+                revert: host_function_cost(),
+                add_associated_key: host_function_cost(),
+                remove_associated_key: host_function_cost(),
+                update_associated_key: host_function_cost(),
+                set_action_threshold: host_function_cost(),
+                create_purse: host_function_cost(),
+                transfer_to_account: host_function_cost(),
+                transfer_from_purse_to_account: host_function_cost(),
+                transfer_from_purse_to_purse: host_function_cost(),
+                get_balance: host_function_cost(),
+                get_phase: host_function_cost(),
+                upgrade_contract_at_uref: host_function_cost(),
+                get_system_contract: host_function_cost(),
+                get_main_purse: host_function_cost(),
+                read_host_buffer: host_function_cost(),
+            }
+        };
+
+        let storage_costs = StorageCosts {
+            gas_per_byte: rng.gen(),
+        };
+
+        let wasm_limits = WasmLimits {
+            max_memory_pages: rng.gen(),
+            initial_memory_pages: rng.gen(),
+            max_module_size: rng.gen(),
+        };
+
+        // the deploy hash expiry is carried as a `u32` in the chainspec
+        let deploy_hash_expiry_millis = rng.gen::<u32>().into();
+
         GenesisConfig {
             name,
             timestamp,
@@ -244,6 +332,10 @@ impl Distribution<GenesisConfig> for Standard {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
         }
     }
 }
@@ -0,0 +1,50 @@
+use engine_shared::newtypes::Blake2bHash;
+use types::Key;
+
+pub enum ListKeysResult {
+    RootNotFound,
+    Success {
+        keys: Vec<Key>,
+        maybe_next_page_token: Option<Key>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListKeysRequest {
+    state_hash: Blake2bHash,
+    prefix: Vec<u8>,
+    page_size: usize,
+    maybe_page_token: Option<Key>,
+}
+
+impl ListKeysRequest {
+    pub fn new(
+        state_hash: Blake2bHash,
+        prefix: Vec<u8>,
+        page_size: usize,
+        maybe_page_token: Option<Key>,
+    ) -> Self {
+        ListKeysRequest {
+            state_hash,
+            prefix,
+            page_size,
+            maybe_page_token,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn page_token(&self) -> Option<Key> {
+        self.maybe_page_token
+    }
+}
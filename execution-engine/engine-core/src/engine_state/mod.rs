@@ -1,25 +1,40 @@
+pub mod bid_state;
 pub mod deploy_item;
+pub mod diff;
+pub mod distribute_rewards;
 pub mod engine_config;
 mod error;
+pub mod estimate;
+pub mod event;
 pub mod executable_deploy_item;
+pub mod execute_block;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+pub mod list_keys;
+pub mod module_cache;
 pub mod op;
 pub mod query;
+pub mod replay_protection;
+pub mod slash;
+pub mod system_call;
 pub mod system_contract_cache;
+pub mod unbond_payout;
 pub mod upgrade;
 pub mod utils;
+pub mod view;
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    convert::TryInto,
     rc::Rc,
 };
 
 use num_traits::Zero;
 use parity_wasm::elements::Module;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use contract::args_parser::ArgsParser;
 use engine_shared::{
@@ -37,11 +52,17 @@ use engine_storage::{
     global_state::{CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
-use proof_of_stake::Stakes;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts,
+    validation::ValidationReport, wasm_costs::WasmCosts, wasm_limits::WasmLimits, Preprocessor,
+};
+use proof_of_stake::{Queue, Stakes, BONDING_KEY, UNBONDING_KEY};
 use types::{
-    account::PublicKey, bytesrepr::ToBytes, system_contract_errors::mint, AccessRights, BlockTime,
-    Key, Phase, ProtocolVersion, URef, KEY_HASH_LENGTH, U512, UREF_ADDR_LENGTH,
+    account::PublicKey,
+    bytesrepr::ToBytes,
+    system_contract_errors::{mint, pos},
+    AccessRights, ApiError, BlockTime, CLValue, Key, Phase, ProtocolVersion, URef, KEY_HASH_LENGTH,
+    U512, UREF_ADDR_LENGTH,
 };
 
 pub use self::{
@@ -50,22 +71,36 @@ pub use self::{
 };
 use crate::{
     engine_state::{
+        bid_state::{BidState, BidStateRequest, BidStateResult},
         deploy_item::DeployItem,
+        diff::{DiffRequest, DiffResult},
+        distribute_rewards::{
+            DistributeRewardsError, DistributeRewardsRequest, DistributeRewardsResult,
+        },
         error::Error::MissingSystemContract,
+        estimate::{EstimateRequest, EstimateResult},
         executable_deploy_item::ExecutableDeployItem,
+        execute_block::ExecuteBlockResult,
         execute_request::ExecuteRequest,
         execution_result::{ExecutionResult, ForcedTransferResult},
         genesis::{
             GenesisAccount, GenesisConfig, GenesisResult, PLACEHOLDER_KEY, POS_BONDING_PURSE,
             POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
         },
-        query::{QueryRequest, QueryResult},
+        list_keys::{ListKeysRequest, ListKeysResult},
+        module_cache::ModuleCache,
+        op::Op,
+        query::{QueryRequest, QueryResult, QueryWithProofRequest, QueryWithProofResult},
+        slash::{SlashError, SlashRequest, SlashResult},
         system_contract_cache::SystemContractCache,
+        unbond_payout::{UnbondPayoutError, UnbondPayoutRequest, UnbondPayoutResult},
         upgrade::{UpgradeConfig, UpgradeResult},
+        view::{ViewRequest, ViewResult},
     },
     execution::{self, AddressGenerator, Executor, MINT_NAME, POS_NAME},
+    runtime,
     tracking_copy::{TrackingCopy, TrackingCopyExt},
-    KnownKeys,
+    DeployHash, KnownKeys,
 };
 
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
@@ -77,11 +112,19 @@ pub const SYSTEM_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
 const MINT_METHOD_NAME: &str = "mint";
+const POS_METHOD_SLASH: &str = "slash";
+const POS_METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+const POS_METHOD_UNBOND_PAYOUT: &str = "unbond_payout";
+const EXPIRED_DEPLOY_RECORDS_PAGE_SIZE: usize = 1_000;
+/// The maximum number of expired records of executed deploys deleted by a single commit.  The rest
+/// are left to the following commits.
+pub const MAX_EXPIRED_DEPLOY_RECORDS_PER_COMMIT: usize = 1_000;
 
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     state: S,
 }
 
@@ -92,9 +135,11 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let module_cache = ModuleCache::new(config.module_cache_capacity());
         EngineState {
             config,
             system_contract_cache,
+            module_cache,
             state,
         }
     }
@@ -103,6 +148,10 @@ where
         &self.config
     }
 
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -113,6 +162,36 @@ where
         }
     }
 
+    pub fn host_function_costs(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<HostFunctionCosts>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.host_function_costs())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn storage_costs(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<StorageCosts>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.storage_costs())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn wasm_limits(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<WasmLimits>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.wasm_limits())),
+            None => Ok(None),
+        }
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -130,7 +209,7 @@ where
         genesis_config: GenesisConfig,
     ) -> Result<GenesisResult, Error> {
         // Preliminaries
-        let executor = Executor::new(self.config);
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
         let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
         let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
@@ -139,7 +218,13 @@ where
         let initial_root_hash = self.state.empty_root();
         let protocol_version = genesis_config.protocol_version();
         let wasm_costs = genesis_config.wasm_costs();
-        let preprocessor = Preprocessor::new(wasm_costs);
+        let host_function_costs = genesis_config.host_function_costs();
+        let storage_costs = genesis_config.storage_costs();
+        let wasm_limits = genesis_config.wasm_limits();
+        if !wasm_limits.is_valid() {
+            return Err(Error::InvalidWasmLimits(wasm_limits));
+        }
+        let preprocessor = Preprocessor::new(wasm_costs, wasm_limits);
 
         // Spec #3: Create "virtual system account" object.
         let virtual_system_account = {
@@ -242,7 +327,7 @@ where
                     correlation_id,
                     tracking_copy,
                     phase,
-                    ProtocolData::default(),
+                    ProtocolData::partial_with_wasm_limits(wasm_limits),
                     system_contract_cache,
                 )?
             }
@@ -265,7 +350,8 @@ where
 
             // Constructs a partial protocol data with already known uref to pass the validation
             // step
-            let partial_protocol_data = ProtocolData::partial_with_mint(mint_reference);
+            let partial_protocol_data =
+                ProtocolData::partial_with_mint(wasm_limits, mint_reference);
 
             if !self.config.use_system_contracts() {
                 let uref = {
@@ -296,6 +382,7 @@ where
                     phase,
                     partial_protocol_data,
                     system_contract_cache,
+                    false,
                 )?;
 
                 let stakes = Stakes::new(bonded_validators);
@@ -395,6 +482,9 @@ where
         // specification.
         let protocol_data = ProtocolData::partial_without_standard_payment(
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
             mint_reference,
             proof_of_stake_reference,
         );
@@ -448,6 +538,10 @@ where
         // Spec #2: Associate given CostTable with given ProtocolVersion.
         let protocol_data = ProtocolData::new(
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            genesis_config.deploy_hash_expiry_millis(),
             mint_reference,
             proof_of_stake_reference,
             standard_payment_reference,
@@ -562,6 +656,7 @@ where
                         phase,
                         protocol_data,
                         system_contract_cache,
+                        false,
                     )?;
 
                     runtime
@@ -668,9 +763,37 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
+        let new_host_function_costs = match upgrade_config.host_function_costs() {
+            Some(new_host_function_costs) => new_host_function_costs,
+            None => *current_protocol_data.host_function_costs(),
+        };
+
+        let new_storage_costs = match upgrade_config.storage_costs() {
+            Some(new_storage_costs) => new_storage_costs,
+            None => *current_protocol_data.storage_costs(),
+        };
+
+        let new_wasm_limits = match upgrade_config.wasm_limits() {
+            Some(new_wasm_limits) => new_wasm_limits,
+            None => *current_protocol_data.wasm_limits(),
+        };
+
+        if !new_wasm_limits.is_valid_upgrade_of(current_protocol_data.wasm_limits()) {
+            return Err(Error::InvalidWasmLimits(new_wasm_limits));
+        }
+
+        let new_deploy_hash_expiry_millis = match upgrade_config.deploy_hash_expiry_millis() {
+            Some(new_deploy_hash_expiry_millis) => new_deploy_hash_expiry_millis,
+            None => current_protocol_data.deploy_hash_expiry_millis(),
+        };
+
         // 3.1.2.2 persist wasm CostTable
         let new_protocol_data = ProtocolData::new(
             new_wasm_costs,
+            new_host_function_costs,
+            new_storage_costs,
+            new_wasm_limits,
+            new_deploy_hash_expiry_millis,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
@@ -694,7 +817,7 @@ where
 
                 // preprocess installer module
                 let upgrade_installer_module = {
-                    let preprocessor = Preprocessor::new(new_wasm_costs);
+                    let preprocessor = Preprocessor::new(new_wasm_costs, new_wasm_limits);
                     preprocessor.preprocess(bytes)?
                 };
 
@@ -746,7 +869,7 @@ where
                 let state = Rc::clone(&tracking_copy);
                 let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
-                let executor = Executor::new(self.config);
+                let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
 
                 executor.exec_system(
                     upgrade_installer_module,
@@ -813,60 +936,513 @@ where
             .into())
     }
 
+    /// Looks up a key without following any path, and returns a Merkle proof of its value, or of
+    /// its absence, which can be verified against the given state hash.
+    pub fn run_query_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        query_request: QueryWithProofRequest,
+    ) -> Result<QueryWithProofResult, Error> {
+        let key = query_request.key().normalize();
+        let read_with_proof_result = self
+            .state
+            .read_with_proof(correlation_id, query_request.state_hash(), &key)
+            .map_err(Into::into)?;
+        Ok(read_with_proof_result.into())
+    }
+
+    /// Returns the keys whose values were added, removed or changed between two state hashes.
+    pub fn run_diff(
+        &self,
+        correlation_id: CorrelationId,
+        diff_request: DiffRequest,
+    ) -> Result<DiffResult, Error> {
+        let diff_result = self
+            .state
+            .diff(
+                correlation_id,
+                diff_request.left_state_hash(),
+                diff_request.right_state_hash(),
+            )
+            .map_err(Into::into)?;
+        Ok(diff_result.into())
+    }
+
+    /// Lists the keys under the given state hash whose serialized form starts with the requested
+    /// prefix, in order of their serialized bytes.  Keys are returned a page at a time, each page
+    /// starting after the key given as the page token.
+    pub fn run_list_keys(
+        &self,
+        correlation_id: CorrelationId,
+        list_keys_request: ListKeysRequest,
+    ) -> Result<ListKeysResult, Error> {
+        let reader = match self
+            .state
+            .checkout(list_keys_request.state_hash())
+            .map_err(Into::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(ListKeysResult::RootNotFound),
+        };
+
+        let maybe_page_token_bytes = match list_keys_request.page_token() {
+            Some(page_token) => Some(page_token.to_bytes()?),
+            None => None,
+        };
+
+        // One key past the page is fetched to tell whether there is a next page.
+        let page_size = list_keys_request.page_size();
+        let mut keys = reader
+            .keys_with_prefix(
+                correlation_id,
+                list_keys_request.prefix(),
+                maybe_page_token_bytes.as_deref(),
+                page_size.saturating_add(1),
+            )
+            .map_err(Into::into)?;
+
+        let maybe_next_page_token = if keys.len() > page_size {
+            keys.truncate(page_size);
+            keys.last().cloned()
+        } else {
+            None
+        };
+
+        Ok(ListKeysResult::Success {
+            keys,
+            maybe_next_page_token,
+        })
+    }
+
+    /// Executes the deploys of the request against its parent state.
+    ///
+    /// Every deploy is executed against the same parent state with its own tracking copy, so the
+    /// deploys are independent of one another and are executed concurrently.  The results are
+    /// returned in the order of the deploys in the request.  As none of the deploys sees the
+    /// record of another, every copy of a deploy hash repeated within the request after the first
+    /// is rejected with [`Error::DeployReplayed`] up front, unless the protocol version disables
+    /// the rejection.
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+    ) -> Result<Vec<ExecutionResult>, RootNotFound>
+    where
+        S: Sync,
+    {
         // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
+        let protocol_data = self
+            .get_protocol_data(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
-
-        let mut results = Vec::new();
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+        let protocol_version = exec_request.protocol_version;
+        let parent_state_hash = exec_request.parent_state_hash;
+        let blocktime = BlockTime::new(exec_request.block_time);
+
+        let deploy_hash_expiry_millis = protocol_data.deploy_hash_expiry_millis();
+
+        let mut deploys = exec_request.take_deploys();
+        if deploy_hash_expiry_millis != 0 {
+            let mut deploy_hashes = HashSet::new();
+            for deploy in deploys.iter_mut() {
+                let is_repeated = match deploy {
+                    Ok(deploy_item) => !deploy_hashes.insert(deploy_item.deploy_hash),
+                    Err(_) => false,
+                };
+                if is_repeated {
+                    *deploy = Err(ExecutionResult::precondition_failure(Error::DeployReplayed));
+                }
+            }
+        }
 
-        for deploy_item in exec_request.take_deploys() {
-            let result = match deploy_item {
+        deploys
+            .into_par_iter()
+            .map(|deploy_item| match deploy_item {
                 Ok(deploy_item) => self.deploy(
                     correlation_id,
                     &executor,
                     &preprocessor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
+                    protocol_version,
+                    parent_state_hash,
+                    blocktime,
+                    deploy_hash_expiry_millis,
                     deploy_item,
                 ),
-                Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
-                                                      * below */
-            };
-            match result {
-                Ok(result) => results.push(result),
-                Err(error) => {
-                    return Err(error);
+                Err(exec_result) => Ok(exec_result),
+            })
+            .collect()
+    }
+
+    /// Executes the deploys of the request in order, each against the parent state with the
+    /// effects of the preceding deploys applied, then commits the combined effects of all of the
+    /// deploys in a single commit.
+    pub fn run_execute_block(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<ExecuteBlockResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let protocol_version = exec_request.protocol_version;
+        let parent_state_hash = exec_request.parent_state_hash;
+        let blocktime = BlockTime::new(exec_request.block_time);
+
+        let mut block_tracking_copy = match self.tracking_copy(parent_state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => {
+                return Ok(ExecuteBlockResult::new(
+                    Vec::new(),
+                    CommitResult::RootNotFound,
+                ))
+            }
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+
+        let mut execution_results = Vec::new();
+        let mut block_effects: AdditiveMap<Key, Transform> = AdditiveMap::new();
+
+        for deploy_item in exec_request.take_deploys() {
+            let execution_result = match deploy_item {
+                Ok(deploy_item) => {
+                    let tracking_copy = Rc::new(RefCell::new(block_tracking_copy.fork()));
+                    self.deploy_with_tracking_copy(
+                        correlation_id,
+                        &executor,
+                        &preprocessor,
+                        protocol_version,
+                        blocktime,
+                        protocol_data.deploy_hash_expiry_millis(),
+                        deploy_item,
+                        tracking_copy,
+                    )
                 }
+                Err(execution_result) => execution_result,
             };
+
+            // Apply the effects of the deploy so that they are visible to the following deploys.
+            for (key, transform) in execution_result.effect().transforms.iter() {
+                let value = match transform {
+                    Transform::Identity => continue,
+                    Transform::Write(value) => value.to_owned(),
+                    transform => match block_tracking_copy.get(correlation_id, key)? {
+                        Some(current_value) => match transform.to_owned().apply(current_value) {
+                            Ok(value) => value,
+                            Err(error) => {
+                                return Ok(ExecuteBlockResult::new(execution_results, error.into()))
+                            }
+                        },
+                        None => {
+                            return Ok(ExecuteBlockResult::new(
+                                execution_results,
+                                CommitResult::KeyNotFound(*key),
+                            ))
+                        }
+                    },
+                };
+                block_tracking_copy.write(*key, value);
+                block_effects.insert_add(*key, transform.to_owned());
+            }
+
+            execution_results.push(execution_result);
+        }
+
+        let commit_result = self.apply_effect(
+            correlation_id,
+            protocol_version,
+            parent_state_hash,
+            block_effects,
+        )?;
+        Ok(ExecuteBlockResult::new(execution_results, commit_result))
+    }
+
+    /// Executes the payment and session code of a deploy against the given state with an
+    /// effectively unlimited gas limit, to estimate the gas the deploy consumes.  The effects are
+    /// not committed, and the payment is not finalized.
+    pub fn run_estimate(
+        &self,
+        correlation_id: CorrelationId,
+        estimate_request: EstimateRequest,
+    ) -> Result<EstimateResult, Error> {
+        let protocol_version = estimate_request.protocol_version;
+        let blocktime = BlockTime::new(estimate_request.block_time);
+        let DeployItem {
+            address: account_addr,
+            session,
+            payment,
+            authorization_keys,
+            deploy_hash,
+            ..
+        } = estimate_request.deploy_item;
+        let address = Key::Account(account_addr);
+
+        let tracking_copy = match self.tracking_copy(estimate_request.state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(EstimateResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+
+        let account: Account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_addr)
+        {
+            Ok(account) => account,
+            Err(_) => return Ok(EstimateResult::precondition_failure(Error::Authorization)),
+        };
+        if !account.can_authorize(&authorization_keys) {
+            return Ok(EstimateResult::precondition_failure(Error::Authorization));
+        }
+        if !account.can_deploy_with(&authorization_keys) {
+            return Ok(EstimateResult::precondition_failure(
+                execution::Error::DeploymentAuthorizationFailure.into(),
+            ));
+        }
+
+        let session_module = match self.get_module(
+            Rc::clone(&tracking_copy),
+            &session,
+            &account,
+            correlation_id,
+            &preprocessor,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+
+        // Neither phase is limited by the amount the deploy pays.
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+
+        let payment_result = match self.exec_payment(
+            correlation_id,
+            &executor,
+            &preprocessor,
+            protocol_version,
+            protocol_data,
+            blocktime,
+            deploy_hash,
+            address,
+            &account,
+            authorization_keys.clone(),
+            payment,
+            gas_limit,
+            Rc::clone(&tracking_copy),
+        ) {
+            Ok(payment_result) => payment_result,
+            Err(error) => return Ok(EstimateResult::precondition_failure(error)),
+        };
+
+        let mut effect = payment_result.effect().to_owned();
+        if payment_result.is_failure() {
+            return Ok(EstimateResult::Success {
+                payment_result,
+                maybe_session_result: None,
+                effect,
+            });
+        }
+
+        let post_payment_tc = tracking_copy.borrow();
+        let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+        let session_result = executor.exec(
+            session_module,
+            session.take_args(),
+            address,
+            &account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            session_tc,
+            Phase::Session,
+            protocol_data,
+            system_contract_cache,
+        );
+
+        // As in a deploy, the session is charged for the record of the execution of the deploy.
+        let session_result = if protocol_data.deploy_hash_expiry_millis() != 0 {
+            let record_bytes =
+                replay_protection::executed_deploy_record_size(deploy_hash, blocktime);
+            let record_cost = Gas::new(protocol_data.storage_costs().calculate(record_bytes));
+            let cost = session_result.cost() + record_cost;
+            let storage_bytes = session_result.storage_bytes().saturating_add(record_bytes);
+            session_result
+                .with_cost(cost)
+                .with_storage_bytes(storage_bytes)
+        } else {
+            session_result
+        };
+
+        // As in a deploy, the effects of failed session code are discarded.
+        if session_result.is_success() {
+            for (key, op) in session_result.effect().ops.iter() {
+                effect.ops.insert_add(*key, op.to_owned());
+            }
+            for (key, transform) in session_result.effect().transforms.iter() {
+                effect.transforms.insert_add(*key, transform.to_owned());
+            }
         }
 
-        Ok(results)
+        Ok(EstimateResult::Success {
+            payment_result,
+            maybe_session_result: Some(session_result),
+            effect,
+        })
+    }
+
+    /// Checks `module_bytes` against the rules enforced when preprocessing and executing a module
+    /// under the given protocol version, without executing it.
+    pub fn validate(
+        &self,
+        module_bytes: &[u8],
+        protocol_version: ProtocolVersion,
+    ) -> Result<ValidationReport, Error> {
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+        let preprocessor =
+            Preprocessor::new(*protocol_data.wasm_costs(), *protocol_data.wasm_limits());
+        Ok(preprocessor.validate(module_bytes))
+    }
+
+    /// Calls a stored contract in a read-only context and returns the value it passes to
+    /// `runtime::ret`.
+    ///
+    /// The contract is executed with its own named keys, as when called by another contract, on
+    /// behalf of the requesting account.  No payment is taken and nothing is committed; the
+    /// runtime context is read-only, so a contract which writes to global state fails with
+    /// [`Error::ViewWrite`].
+    ///
+    /// As a view is not part of any deploy, it runs with a zeroed deploy hash, from which its
+    /// random bytes and the addresses of any URefs it creates are derived, and with an empty set
+    /// of authorization keys, so it can't act with the authority of the account.
+    pub fn run_view(
+        &self,
+        correlation_id: CorrelationId,
+        view_request: ViewRequest,
+    ) -> Result<ViewResult, Error> {
+        let protocol_version = view_request.protocol_version;
+
+        let tracking_copy = match self.tracking_copy(view_request.state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(ViewResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let account = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, view_request.address)?;
+        let contract_key = Self::get_stored_contract_key(&view_request.contract, &account)?;
+        let mut module = self.get_module_from_key(
+            Rc::clone(&tracking_copy),
+            contract_key,
+            correlation_id,
+            &protocol_version,
+        )?;
+        runtime::select_entry_point(&mut module, view_request.contract.entry_point())?;
+        let mut named_keys = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, contract_key)?
+            .named_keys()
+            .to_owned();
+
+        // A view may consume as much gas as the maximum payment for a deploy.
+        let gas_limit =
+            Gas::from_motes(Motes::new(U512::from(MAX_PAYMENT)), CONV_RATE).unwrap_or_default();
+        let deploy_hash = [0u8; 32];
+        let address_generator = AddressGenerator::new(&deploy_hash, Phase::Session);
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
+
+        let result = executor.exec_with_return_value(
+            module,
+            view_request.contract.take_args(),
+            &mut named_keys,
+            contract_key,
+            &account,
+            BTreeSet::new(),
+            BlockTime::new(view_request.block_time),
+            deploy_hash,
+            gas_limit,
+            Rc::new(RefCell::new(address_generator)),
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::Session,
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+            true,
+        );
+
+        match result {
+            Ok(return_value) => Ok(ViewResult::Success(return_value)),
+            Err(execution::Error::ReadOnlyWrite(key)) => Err(Error::ViewWrite(key)),
+            Err(error) => Err(error.into()),
+        }
     }
 
-    pub fn get_module(
+    pub fn get_module<R>(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
         correlation_id: CorrelationId,
         preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let stored_contract_key = match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
                 let module = preprocessor.preprocess(&module_bytes)?;
                 return Ok(module);
             }
+            _ => Self::get_stored_contract_key(deploy_item, account)?,
+        };
+        let mut module = self.get_module_from_key(
+            tracking_copy,
+            stored_contract_key,
+            correlation_id,
+            protocol_version,
+        )?;
+        runtime::select_entry_point(&mut module, deploy_item.entry_point())?;
+        Ok(module)
+    }
+
+    /// Returns the key of the stored contract which a deploy item refers to.
+    fn get_stored_contract_key(
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+    ) -> Result<Key, error::Error> {
+        let stored_contract_key = match deploy_item {
+            ExecutableDeployItem::ModuleBytes { .. } => {
+                return Err(error::Error::ExpectedStoredContract)
+            }
             ExecutableDeployItem::StoredContractByHash { hash, .. } => {
                 let hash_len = hash.len();
                 if hash_len != KEY_HASH_LENGTH {
@@ -931,21 +1507,20 @@ where
                 }
             }
         };
-        self.get_module_from_key(
-            tracking_copy,
-            stored_contract_key,
-            correlation_id,
-            protocol_version,
-        )
+        Ok(stored_contract_key)
     }
 
-    fn get_module_from_key(
+    fn get_module_from_key<R>(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         stored_contract_key: Key,
         correlation_id: CorrelationId,
         protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let contract = tracking_copy
             .borrow_mut()
             .get_contract(correlation_id, stored_contract_key)?;
@@ -961,8 +1536,12 @@ where
             return Err(error::Error::Exec(exec_error));
         }
 
-        let (ret, _, _) = contract.destructure();
-        let module = engine_wasm_prep::deserialize(&ret)?;
+        let module =
+            self.module_cache
+                .get_or_load(stored_contract_key, *protocol_version, || {
+                    let (ret, _, _) = contract.destructure();
+                    engine_wasm_prep::deserialize(&ret)
+                })?;
         Ok(module)
     }
 
@@ -975,16 +1554,11 @@ where
         protocol_version: ProtocolVersion,
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
+        deploy_hash_expiry_millis: u64,
         deploy_item: DeployItem,
     ) -> Result<ExecutionResult, RootNotFound> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
-        let session = deploy_item.session;
-        let payment = deploy_item.payment;
-        let address = Key::Account(deploy_item.address);
-        let authorization_keys = deploy_item.authorization_keys;
-        let deploy_hash = deploy_item.deploy_hash;
-
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
@@ -993,46 +1567,153 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
-        // Get addr bytes from `address` (which is actually a Key)
-        // validation_spec_3: account validity
-        let account_addr = match address.into_account() {
-            Some(account_addr) => account_addr,
-            None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::Authorization,
-                ))
-            }
-        };
-
-        // Get account from tracking copy
-        // validation_spec_3: account validity
-        let account: Account = match tracking_copy
-            .borrow_mut()
-            .get_account(correlation_id, account_addr)
-        {
-            Ok(account) => account,
-            Err(_) => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::Authorization,
-                ));
-            }
-        };
+        Ok(self.deploy_with_tracking_copy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            blocktime,
+            deploy_hash_expiry_millis,
+            deploy_item,
+            tracking_copy,
+        ))
+    }
 
-        // Authorize using provided authorization keys
+    /// Executes a deploy against the given tracking copy, which may be a fork of the tracking copy
+    /// of another deploy.
+    ///
+    /// A deploy whose hash was already executed within the expiry horizon is rejected with
+    /// [`Error::DeployReplayed`].  Otherwise, unless it fails a precondition, the execution of the
+    /// deploy is recorded along with its effects.  The expiry horizon, `deploy_hash_expiry_millis`,
+    /// is the one set in the protocol data of the protocol version; when it is zero, deploys are
+    /// neither checked nor recorded.
+    #[allow(clippy::too_many_arguments)]
+    fn deploy_with_tracking_copy<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        deploy_hash_expiry_millis: u64,
+        deploy_item: DeployItem,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        if deploy_hash_expiry_millis == 0 {
+            return self.execute_deploy_item(
+                correlation_id,
+                executor,
+                preprocessor,
+                protocol_version,
+                blocktime,
+                deploy_item,
+                tracking_copy,
+            );
+        }
+
+        let executed_deploy_key = replay_protection::executed_deploy_key(deploy_item.deploy_hash);
+
+        let previous_record = match tracking_copy
+            .borrow_mut()
+            .get(correlation_id, &executed_deploy_key)
+        {
+            Ok(previous_record) => previous_record,
+            Err(error) => return ExecutionResult::precondition_failure(Error::Exec(error.into())),
+        };
+        if let Some(executed_at) = previous_record.and_then(replay_protection::executed_at) {
+            if replay_protection::is_replay(executed_at, blocktime, deploy_hash_expiry_millis) {
+                return ExecutionResult::precondition_failure(Error::DeployReplayed);
+            }
+        }
+
+        let execution_result = self.execute_deploy_item(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            blocktime,
+            deploy_item,
+            tracking_copy,
+        );
+        if execution_result.has_precondition_failure() {
+            return execution_result;
+        }
+
+        let mut effect = execution_result.effect().clone();
+        effect.ops.insert(executed_deploy_key, Op::Write);
+        effect.transforms.insert(
+            executed_deploy_key,
+            Transform::Write(replay_protection::executed_deploy_record(blocktime)),
+        );
+        let index_key =
+            replay_protection::executed_deploy_index_key(deploy_item.deploy_hash, blocktime);
+        effect.ops.insert(index_key, Op::Write);
+        effect.transforms.insert(
+            index_key,
+            Transform::Write(replay_protection::executed_deploy_index_value()),
+        );
+        execution_result.with_effect(effect)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_deploy_item<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let session = deploy_item.session;
+        let payment = deploy_item.payment;
+        let address = Key::Account(deploy_item.address);
+        let authorization_keys = deploy_item.authorization_keys;
+        let deploy_hash = deploy_item.deploy_hash;
+
+        // Get addr bytes from `address` (which is actually a Key)
+        // validation_spec_3: account validity
+        let account_addr = match address.into_account() {
+            Some(account_addr) => account_addr,
+            None => return ExecutionResult::precondition_failure(error::Error::Authorization),
+        };
+
+        // Get account from tracking copy
+        // validation_spec_3: account validity
+        let account: Account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_addr)
+        {
+            Ok(account) => account,
+            Err(_) => {
+                return ExecutionResult::precondition_failure(error::Error::Authorization);
+            }
+        };
+
+        // Authorize using provided authorization keys
         // validation_spec_3: account validity
         if !account.can_authorize(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return ExecutionResult::precondition_failure(
                 crate::engine_state::error::Error::Authorization,
-            ));
+            );
         }
 
         // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
         if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return ExecutionResult::precondition_failure(
                 // TODO?:this doesn't happen in execution any longer, should error variant be moved
                 execution::Error::DeploymentAuthorizationFailure.into(),
-            ));
+            );
         }
 
         // Create session code `A` from provided session bytes
@@ -1047,7 +1728,7 @@ where
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
@@ -1056,12 +1737,10 @@ where
             Ok(Some(protocol_data)) => protocol_data,
             Ok(None) => {
                 let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
+                return ExecutionResult::precondition_failure(Error::Exec(error.into()));
             }
         };
 
@@ -1080,13 +1759,13 @@ where
                 .get_contract(correlation_id, Key::URef(mint_reference))
             {
                 Ok(contract) => contract,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             if !self.system_contract_cache.has(&mint_reference) {
                 let module = match engine_wasm_prep::deserialize(mint_contract.bytes()) {
                     Ok(module) => module,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                    Err(error) => return ExecutionResult::precondition_failure(error.into()),
                 };
                 self.system_contract_cache.insert(mint_reference, module);
             }
@@ -1107,7 +1786,7 @@ where
         {
             Ok(contract) => contract,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
@@ -1120,7 +1799,7 @@ where
                 match proof_of_stake_contract.named_keys().get(POS_REWARDS_PURSE) {
                     Some(key) => *key,
                     None => {
-                        return Ok(ExecutionResult::precondition_failure(Error::Deploy));
+                        return ExecutionResult::precondition_failure(Error::Deploy);
                     }
                 };
 
@@ -1131,7 +1810,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1147,7 +1826,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1159,15 +1838,13 @@ where
             .get_purse_balance(correlation_id, account_main_purse_balance_key)
         {
             Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
         };
 
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
         if account_main_purse_balance < max_payment_cost {
-            return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPayment,
-            ));
+            return ExecutionResult::precondition_failure(Error::InsufficientPayment);
         }
 
         // Finalization is executed by system account (currently genesis account)
@@ -1184,119 +1861,26 @@ where
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
 
         // Execute provided payment code
-        let payment_result = {
-            // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
-            // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
-
-            let module_bytes_is_empty = match payment {
-                ExecutableDeployItem::ModuleBytes {
-                    ref module_bytes, ..
-                } => module_bytes.is_empty(),
-                _ => false,
-            };
-
-            // Create payment code module from bytes
-            // validation_spec_1: valid wasm bytes
-            let maybe_payment_module = if module_bytes_is_empty {
-                let standard_payment = match self.state.get_protocol_data(protocol_version) {
-                    Ok(Some(protocol_data)) => {
-                        Key::URef(protocol_data.standard_payment()).normalize()
-                    }
-                    Ok(None) => {
-                        return Ok(ExecutionResult::precondition_failure(
-                            Error::InvalidProtocolVersion(protocol_version),
-                        ))
-                    }
-                    Err(_) => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
-                };
-                // If not in "use-system-contracts" mode, the returned module is the "do_nothing"
-                // Wasm.
-                self.get_module_from_key(
-                    Rc::clone(&tracking_copy),
-                    standard_payment,
-                    correlation_id,
-                    &protocol_version,
-                )
-            } else {
-                self.get_module(
-                    Rc::clone(&tracking_copy),
-                    &payment,
-                    &account,
-                    correlation_id,
-                    preprocessor,
-                    &protocol_version,
-                )
-            };
-
-            let payment_module = match maybe_payment_module {
-                Ok(module) => module,
-                Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error));
-                }
-            };
-            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
-
-            // payment_code_spec_2: execute payment code
-            let phase = Phase::Payment;
-            if !self.config.use_system_contracts() && module_bytes_is_empty {
-                let mut named_keys = account.named_keys().clone();
-                let address_generator = AddressGenerator::new(&deploy_hash, phase);
-
-                let mut runtime = match executor.create_runtime(
-                    payment_module,
-                    payment.take_args(),
-                    &mut named_keys,
-                    address,
-                    &account,
-                    authorization_keys.clone(),
-                    blocktime,
-                    deploy_hash,
-                    pay_gas_limit,
-                    Rc::new(RefCell::new(address_generator)),
-                    protocol_version,
-                    correlation_id,
-                    Rc::clone(&tracking_copy),
-                    phase,
-                    protocol_data,
-                    system_contract_cache,
-                ) {
-                    Ok((_instance, runtime)) => runtime,
-                    Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(Error::Exec(error)))
-                    }
-                };
-
-                let effects_snapshot = tracking_copy.borrow().effect();
-                match runtime.call_host_standard_payment() {
-                    Ok(()) => ExecutionResult::Success {
-                        effect: runtime.context().effect(),
-                        cost: runtime.context().gas_counter(),
-                    },
-                    Err(error) => ExecutionResult::Failure {
-                        error: error.into(),
-                        effect: effects_snapshot,
-                        cost: runtime.context().gas_counter(),
-                    },
-                }
-            } else {
-                executor.exec(
-                    payment_module,
-                    payment.take_args(),
-                    address,
-                    &account,
-                    authorization_keys.clone(),
-                    blocktime,
-                    deploy_hash,
-                    pay_gas_limit,
-                    protocol_version,
-                    correlation_id,
-                    Rc::clone(&tracking_copy),
-                    phase,
-                    protocol_data,
-                    system_contract_cache,
-                )
-            }
+        // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
+        // conv_rate)
+        let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let payment_result = match self.exec_payment(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            protocol_data,
+            blocktime,
+            deploy_hash,
+            address,
+            &account,
+            authorization_keys.clone(),
+            payment,
+            pay_gas_limit,
+            Rc::clone(&tracking_copy),
+        ) {
+            Ok(payment_result) => payment_result,
+            Err(error) => return ExecutionResult::precondition_failure(error),
         };
 
         let payment_result_cost = payment_result.cost();
@@ -1309,7 +1893,7 @@ where
             let payment_purse: Key =
                 match proof_of_stake_contract.named_keys().get(POS_PAYMENT_PURSE) {
                     Some(key) => *key,
-                    None => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
+                    None => return ExecutionResult::precondition_failure(Error::Deploy),
                 };
 
             let purse_balance_key = match tracking_copy.borrow_mut().get_purse_balance_key(
@@ -1319,7 +1903,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             };
 
@@ -1329,7 +1913,7 @@ where
             {
                 Ok(balance) => balance,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -1339,13 +1923,13 @@ where
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPayment,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
             };
-            return Ok(ExecutionResult::new_payment_code_error(
+            return ExecutionResult::new_payment_code_error(
                 error,
                 max_payment_cost,
                 account_main_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
-            ));
+            );
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
@@ -1353,15 +1937,16 @@ where
         let post_payment_tc = tracking_copy.borrow();
         let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
 
+        // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
+        // payment code execution) * conv_rate, yes session
+        // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
+        // - (gas spent during payment execution)
+        let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
+            .unwrap_or_default()
+            - payment_result_cost;
+
         // session_code_spec_2: execute session code
         let session_result = {
-            // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
-            // payment code execution) * conv_rate, yes session
-            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
-            // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
-                .unwrap_or_default()
-                - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
             executor.exec(
@@ -1382,6 +1967,30 @@ where
             )
         };
 
+        // The record of the execution of the deploy, which is written whether or not the session
+        // succeeds, is paid for within the session gas limit like the storage the session adds.
+        let session_result = if protocol_data.deploy_hash_expiry_millis() != 0 {
+            let record_bytes =
+                replay_protection::executed_deploy_record_size(deploy_hash, blocktime);
+            let record_cost = Gas::new(protocol_data.storage_costs().calculate(record_bytes));
+            let cost = session_result.cost() + record_cost;
+            if cost > session_gas_limit {
+                ExecutionResult::Failure {
+                    error: Error::Exec(execution::Error::GasLimit),
+                    effect: Default::default(),
+                    cost: session_gas_limit,
+                    storage_bytes: record_bytes,
+                }
+            } else {
+                let storage_bytes = session_result.storage_bytes().saturating_add(record_bytes);
+                session_result
+                    .with_cost(cost)
+                    .with_storage_bytes(storage_bytes)
+            }
+        } else {
+            session_result
+        };
+
         let post_session_rc = if session_result.is_failure() {
             // If session code fails we do not include its effects,
             // so we start again from the post-payment state.
@@ -1408,7 +2017,7 @@ where
                             match engine_wasm_prep::deserialize(proof_of_stake_contract.bytes()) {
                                 Ok(module) => module,
                                 Err(error) => {
-                                    return Ok(ExecutionResult::precondition_failure(error.into()))
+                                    return ExecutionResult::precondition_failure(error.into())
                                 }
                             };
                         self.system_contract_cache
@@ -1434,7 +2043,7 @@ where
                 .get_contract(correlation_id, Key::URef(proof_of_stake_reference))
             {
                 Ok(info) => info,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
@@ -1472,9 +2081,141 @@ where
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
-        Ok(ret)
+        ret
     }
 
+    /// Executes the payment code of a deploy with the given gas limit.  Returns an error if the
+    /// payment code could not be executed at all.
+    #[allow(clippy::too_many_arguments)]
+    fn exec_payment<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        blocktime: BlockTime,
+        deploy_hash: DeployHash,
+        address: Key,
+        account: &Account,
+        authorization_keys: BTreeSet<PublicKey>,
+        payment: ExecutableDeployItem,
+        gas_limit: Gas,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> Result<ExecutionResult, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let module_bytes_is_empty = match payment {
+            ExecutableDeployItem::ModuleBytes {
+                ref module_bytes, ..
+            } => module_bytes.is_empty(),
+            _ => false,
+        };
+
+        // Create payment code module from bytes
+        // validation_spec_1: valid wasm bytes
+        let payment_module = if module_bytes_is_empty {
+            let standard_payment = Key::URef(protocol_data.standard_payment()).normalize();
+            // If not in "use-system-contracts" mode, the returned module is the "do_nothing"
+            // Wasm.
+            self.get_module_from_key(
+                Rc::clone(&tracking_copy),
+                standard_payment,
+                correlation_id,
+                &protocol_version,
+            )?
+        } else {
+            self.get_module(
+                Rc::clone(&tracking_copy),
+                &payment,
+                account,
+                correlation_id,
+                preprocessor,
+                &protocol_version,
+            )?
+        };
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        // payment_code_spec_2: execute payment code
+        let phase = Phase::Payment;
+        if !self.config.use_system_contracts() && module_bytes_is_empty {
+            let mut named_keys = account.named_keys().clone();
+            let address_generator = AddressGenerator::new(&deploy_hash, phase);
+
+            let mut runtime = executor
+                .create_runtime(
+                    payment_module,
+                    payment.take_args(),
+                    &mut named_keys,
+                    address,
+                    account,
+                    authorization_keys,
+                    blocktime,
+                    deploy_hash,
+                    gas_limit,
+                    Rc::new(RefCell::new(address_generator)),
+                    protocol_version,
+                    correlation_id,
+                    Rc::clone(&tracking_copy),
+                    phase,
+                    protocol_data,
+                    system_contract_cache,
+                    false,
+                )
+                .map(|(_instance, runtime)| runtime)
+                .map_err(Error::Exec)?;
+
+            let effects_snapshot = tracking_copy.borrow().effect();
+            let storage_size_before = tracking_copy
+                .borrow_mut()
+                .storage_size_delta(correlation_id)
+                .map_err(|error| Error::Exec(error.into()))?;
+            let payment_result = match runtime
+                .call_host_standard_payment()
+                .and_then(|()| runtime.charge_storage(storage_size_before))
+            {
+                Ok(storage_bytes) => ExecutionResult::Success {
+                    effect: runtime.context().effect(),
+                    cost: runtime.context().gas_counter(),
+                    storage_bytes,
+                    events: runtime.context().events().to_vec(),
+                },
+                Err(error) => ExecutionResult::Failure {
+                    error: error.into(),
+                    effect: effects_snapshot,
+                    cost: runtime.context().gas_counter(),
+                    storage_bytes: 0,
+                },
+            };
+            Ok(payment_result)
+        } else {
+            Ok(executor.exec(
+                payment_module,
+                payment.take_args(),
+                address,
+                account,
+                authorization_keys,
+                blocktime,
+                deploy_hash,
+                gas_limit,
+                protocol_version,
+                correlation_id,
+                tracking_copy,
+                phase,
+                protocol_data,
+                system_contract_cache,
+            ))
+        }
+    }
+
+    /// Commits `effects` to the state `pre_state_hash`.
+    ///
+    /// The records of executed deploys held by the state which have expired by the latest
+    /// execution recorded in `effects` are deleted along with their index entries, so the records
+    /// don't accumulate in global state.  The number deleted by one commit is bounded, so a commit
+    /// following a long gap or an upgrade lowering the expiry stays cheap.
     pub fn apply_effect(
         &self,
         correlation_id: CorrelationId,
@@ -1485,7 +2226,21 @@ where
     where
         Error: From<S::Error>,
     {
-        match self.state.commit(correlation_id, pre_state_hash, effects)? {
+        let expired_keys = self.expired_deploy_records(
+            correlation_id,
+            protocol_version,
+            pre_state_hash,
+            &effects,
+        )?;
+
+        let commit_result = match self.state.commit(correlation_id, pre_state_hash, effects)? {
+            CommitResult::Success { state_root, .. } if !expired_keys.is_empty() => self
+                .state
+                .delete_keys(correlation_id, state_root, &expired_keys)?,
+            commit_result => commit_result,
+        };
+
+        match commit_result {
             CommitResult::Success { state_root, .. } => {
                 let bonded_validators =
                     self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
@@ -1498,6 +2253,448 @@ where
         }
     }
 
+    /// Returns the keys of the records of executed deploys held by the state `pre_state_hash`, and
+    /// of their index entries, which have expired by the latest execution recorded in `effects`.
+    ///
+    /// The index is walked from its start, i.e. from the earliest execution, up to the first entry
+    /// which hasn't expired or until `MAX_EXPIRED_DEPLOY_RECORDS_PER_COMMIT` expired entries are
+    /// found.  A record which `effects` writes anew is left in place.
+    fn expired_deploy_records(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        effects: &AdditiveMap<Key, Transform>,
+    ) -> Result<Vec<Key>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let mut expired_keys = Vec::new();
+
+        let expiry_millis = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data.deploy_hash_expiry_millis(),
+            None => return Ok(expired_keys),
+        };
+        if expiry_millis == 0 {
+            return Ok(expired_keys);
+        }
+
+        let blocktime = match effects
+            .keys()
+            .filter_map(replay_protection::parse_executed_deploy_index_key)
+            .map(|(_, executed_at)| executed_at)
+            .max()
+        {
+            Some(blocktime) => blocktime,
+            None => return Ok(expired_keys),
+        };
+
+        // A missing root is reported by the commit.
+        let reader = match self.state.checkout(pre_state_hash)? {
+            Some(reader) => reader,
+            None => return Ok(expired_keys),
+        };
+
+        let prefix = replay_protection::executed_deploy_index_prefix();
+        let mut maybe_start_after: Option<Vec<u8>> = None;
+        let mut expired_count = 0;
+        loop {
+            let index_keys = reader.keys_with_prefix(
+                correlation_id,
+                &prefix,
+                maybe_start_after.as_deref(),
+                EXPIRED_DEPLOY_RECORDS_PAGE_SIZE,
+            )?;
+
+            for index_key in &index_keys {
+                let (deploy_hash, executed_at) =
+                    match replay_protection::parse_executed_deploy_index_key(index_key) {
+                        Some(index_entry) => index_entry,
+                        None => continue,
+                    };
+                if expired_count == MAX_EXPIRED_DEPLOY_RECORDS_PER_COMMIT
+                    || replay_protection::is_replay(executed_at, blocktime, expiry_millis)
+                {
+                    return Ok(expired_keys);
+                }
+                expired_count += 1;
+                if effects.get(index_key).is_none() {
+                    expired_keys.push(*index_key);
+                }
+                // The deploy may have been executed again since its record expired, in which
+                // case the record belongs to the later execution and is left in place.
+                let record_key = replay_protection::executed_deploy_key(deploy_hash);
+                let recorded_at = reader
+                    .read(correlation_id, &record_key)?
+                    .and_then(replay_protection::executed_at);
+                if recorded_at == Some(executed_at) && effects.get(&record_key).is_none() {
+                    expired_keys.push(record_key);
+                }
+            }
+
+            match index_keys.last() {
+                Some(last_key) if index_keys.len() == EXPIRED_DEPLOY_RECORDS_PAGE_SIZE => {
+                    maybe_start_after = Some(last_key.to_bytes()?);
+                }
+                _ => return Ok(expired_keys),
+            }
+        }
+    }
+
+    pub fn commit_slash(
+        &self,
+        correlation_id: CorrelationId,
+        slash_request: SlashRequest,
+    ) -> Result<SlashResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let pre_state_hash = slash_request.pre_state_hash();
+        let protocol_version = slash_request.protocol_version();
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(SlashResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        // Multiple slashes of the same validator are summed up.
+        let mut slashes: BTreeMap<PublicKey, U512> = BTreeMap::new();
+        for (validator, amount) in slash_request.slashes() {
+            let total = slashes.entry(*validator).or_insert_with(U512::zero);
+            *total = total.saturating_add(*amount);
+        }
+
+        // Check the slashes against the current stakes up front, so that the caller gets a typed
+        // error instead of a reverted execution.
+        let mut stakes = self.get_stakes(correlation_id, &tracking_copy, &protocol_data)?;
+        for (validator, amount) in slashes.iter() {
+            let stake = stakes.0.get(validator).cloned().unwrap_or_else(U512::zero);
+            if let Err(error) = stakes.slash(validator, *amount) {
+                let slash_error = match error {
+                    pos::Error::NotBonded => SlashError::UnknownValidator(*validator),
+                    pos::Error::SlashTooLarge => SlashError::Overdrawn {
+                        validator: *validator,
+                        stake,
+                        amount: *amount,
+                    },
+                    pos::Error::CannotUnbondLastValidator => SlashError::LastValidator(*validator),
+                    error => SlashError::ProofOfStake(error),
+                };
+                return Ok(SlashResult::Failure(slash_error));
+            }
+        }
+
+        let args = {
+            let args = (POS_METHOD_SLASH, slashes);
+            ArgsParser::parse(args)
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()
+                .expect("args should serialize")
+        };
+
+        let execution_result = self.exec_proof_of_stake(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            BlockTime::default(),
+            args,
+            tracking_copy,
+        )?;
+
+        match execution_result {
+            ExecutionResult::Failure { error, .. } => {
+                Ok(SlashResult::Failure(SlashError::Execution(error)))
+            }
+            ExecutionResult::Success { effect, .. } => {
+                let commit_result = self.apply_effect(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    effect.transforms,
+                )?;
+                Ok(SlashResult::from_commit_result(commit_result))
+            }
+        }
+    }
+
+    pub fn commit_distribute_rewards(
+        &self,
+        correlation_id: CorrelationId,
+        distribute_rewards_request: DistributeRewardsRequest,
+    ) -> Result<DistributeRewardsResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let pre_state_hash = distribute_rewards_request.pre_state_hash();
+        let protocol_version = distribute_rewards_request.protocol_version();
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(DistributeRewardsResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        // Multiple rewards for the same validator are summed up.
+        let mut rewards: BTreeMap<PublicKey, U512> = BTreeMap::new();
+        for (validator, amount) in distribute_rewards_request.rewards() {
+            let total = rewards.entry(*validator).or_insert_with(U512::zero);
+            *total = total.saturating_add(*amount);
+        }
+
+        // Only bonded validators can be rewarded, as the rewards are restaked.
+        let stakes = self.get_stakes(correlation_id, &tracking_copy, &protocol_data)?;
+        if let Some(validator) = rewards.keys().find(|key| !stakes.0.contains_key(key)) {
+            return Ok(DistributeRewardsResult::Failure(
+                DistributeRewardsError::UnknownValidator(*validator),
+            ));
+        }
+
+        let args = {
+            let args = (POS_METHOD_DISTRIBUTE_REWARDS, rewards);
+            ArgsParser::parse(args)
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()
+                .expect("args should serialize")
+        };
+
+        let execution_result = self.exec_proof_of_stake(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            BlockTime::default(),
+            args,
+            tracking_copy,
+        )?;
+
+        match execution_result {
+            ExecutionResult::Failure { error, .. } => Ok(DistributeRewardsResult::Failure(
+                DistributeRewardsError::Execution(error),
+            )),
+            ExecutionResult::Success { effect, .. } => {
+                let commit_result = self.apply_effect(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    effect.transforms,
+                )?;
+                Ok(DistributeRewardsResult::from_commit_result(commit_result))
+            }
+        }
+    }
+
+    pub fn commit_unbond_payout(
+        &self,
+        correlation_id: CorrelationId,
+        unbond_payout_request: UnbondPayoutRequest,
+    ) -> Result<UnbondPayoutResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let pre_state_hash = unbond_payout_request.pre_state_hash();
+        let protocol_version = unbond_payout_request.protocol_version();
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(UnbondPayoutResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let args = {
+            let args = (POS_METHOD_UNBOND_PAYOUT, unbond_payout_request.era_height());
+            ArgsParser::parse(args)
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()
+                .expect("args should serialize")
+        };
+
+        let execution_result = self.exec_proof_of_stake(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            BlockTime::default(),
+            args,
+            tracking_copy,
+        )?;
+
+        match execution_result {
+            ExecutionResult::Failure { error, .. } => {
+                let transfer_failed: u32 = ApiError::from(pos::Error::UnbondTransferFailed).into();
+                let unbond_payout_error = match error {
+                    Error::Exec(execution::Error::Revert(status)) if status == transfer_failed => {
+                        UnbondPayoutError::TransferFailed
+                    }
+                    error => UnbondPayoutError::Execution(error),
+                };
+                Ok(UnbondPayoutResult::Failure(unbond_payout_error))
+            }
+            ExecutionResult::Success { effect, .. } => {
+                let commit_result = self.apply_effect(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    effect.transforms,
+                )?;
+                Ok(UnbondPayoutResult::from_commit_result(commit_result))
+            }
+        }
+    }
+
+    pub fn get_bid_state(
+        &self,
+        correlation_id: CorrelationId,
+        bid_state_request: BidStateRequest,
+    ) -> Result<BidStateResult, Error> {
+        let protocol_version = bid_state_request.protocol_version();
+
+        let tracking_copy = match self.tracking_copy(bid_state_request.pre_state_hash())? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(BidStateResult::RootNotFound),
+        };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        let stakes = self.get_stakes(correlation_id, &tracking_copy, &protocol_data)?;
+        let bonding_queue =
+            self.get_pos_queue(correlation_id, &tracking_copy, &protocol_data, BONDING_KEY)?;
+        let unbonding_queue = self.get_pos_queue(
+            correlation_id,
+            &tracking_copy,
+            &protocol_data,
+            UNBONDING_KEY,
+        )?;
+
+        Ok(BidStateResult::Success(BidState::new(
+            stakes.0,
+            bonding_queue.0.into_iter().map(Into::into).collect(),
+            unbonding_queue.0.into_iter().map(Into::into).collect(),
+        )))
+    }
+
+    /// Reads a bonding or unbonding queue from the local state of the Proof of Stake contract.
+    fn get_pos_queue(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        protocol_data: &ProtocolData,
+        queue_key: u8,
+    ) -> Result<Queue, Error> {
+        let local_key_bytes = queue_key.to_bytes()?;
+        let local_key = Key::local(protocol_data.proof_of_stake().addr(), &local_key_bytes);
+        let maybe_stored_value = tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &local_key)
+            .map_err(Into::<execution::Error>::into)?;
+        match maybe_stored_value {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                Ok(cl_value.into_t().map_err(execution::Error::from)?)
+            }
+            // The queue is only written once the first request is enqueued.
+            None => Ok(Queue::default()),
+        }
+    }
+
+    /// Reads the stakes from the named keys of the Proof of Stake contract.
+    fn get_stakes(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        protocol_data: &ProtocolData,
+    ) -> Result<Stakes, Error> {
+        let proof_of_stake_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, Key::URef(protocol_data.proof_of_stake()))?;
+        let stakes = proof_of_stake_contract
+            .named_keys()
+            .keys()
+            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
+            .collect();
+        Ok(Stakes::new(stakes))
+    }
+
+    /// Executes a method of the Proof of Stake contract on behalf of the system account, outside
+    /// of any deploy.
+    fn exec_proof_of_stake(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        blocktime: BlockTime,
+        args: Vec<u8>,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+    ) -> Result<ExecutionResult, Error> {
+        let executor = Executor::new(self.config, ModuleCache::clone(&self.module_cache));
+        let proof_of_stake_reference = protocol_data.proof_of_stake();
+
+        let proof_of_stake_contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, Key::URef(proof_of_stake_reference))?;
+
+        let proof_of_stake_module = match self.system_contract_cache.get(&proof_of_stake_reference)
+        {
+            Some(module) => module,
+            None => {
+                let module = engine_wasm_prep::deserialize(proof_of_stake_contract.bytes())?;
+                self.system_contract_cache
+                    .insert(proof_of_stake_reference, module.clone());
+                module
+            }
+        };
+
+        let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
+
+        let system_account = Account::new(
+            SYSTEM_ACCOUNT_ADDR,
+            Default::default(),
+            URef::new(Default::default(), AccessRights::READ_ADD_WRITE),
+            Default::default(),
+            Default::default(),
+        );
+        let authorization_keys = BTreeSet::new();
+        // There is no deploy hash to seed the address generator with, so the arguments are used
+        // instead.
+        let deploy_hash = Blake2bHash::new(&args).value();
+        let base_key = Key::from(proof_of_stake_reference);
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        Ok(executor.exec_finalize(
+            proof_of_stake_module,
+            args,
+            &mut proof_of_stake_keys,
+            base_key,
+            &system_account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::System,
+            protocol_data,
+            system_contract_cache,
+        ))
+    }
+
     /// Calculates bonded validators at `root_hash` state.
     ///
     /// Should only be called with a valid root hash after a successful call to
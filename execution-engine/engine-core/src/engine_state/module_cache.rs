@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use parity_wasm::elements::Module;
+
+use types::{Key, ProtocolVersion};
+
+/// The default maximum number of modules held by a [`ModuleCache`].
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 256;
+
+type CacheKey = (Key, ProtocolVersion);
+
+#[derive(Debug, Default)]
+struct Entries {
+    // Each cached module along with the tick at which it was last used
+    modules: HashMap<CacheKey, (Module, u64)>,
+    // Cache keys ordered from least to most recently used
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+}
+
+impl Entries {
+    fn touch(&mut self, cache_key: CacheKey) -> u64 {
+        self.tick += 1;
+        self.recency.insert(self.tick, cache_key);
+        self.tick
+    }
+}
+
+/// A bounded cache of deserialized stored contracts, shared by all executions of an
+/// [`EngineState`](super::EngineState).  When full, the least recently used module is evicted.
+///
+/// Modules are keyed by the contract's key and the protocol version they're executed under.  Only
+/// contracts stored under a [`Key::Hash`] are cached, as these are immutable whereas contracts
+/// stored under a `URef` can be upgraded in place.
+#[derive(Clone, Debug)]
+pub struct ModuleCache {
+    capacity: usize,
+    entries: Arc<Mutex<Entries>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl ModuleCache {
+    /// Creates a cache holding at most `capacity` modules.  A capacity of zero disables caching.
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            capacity,
+            entries: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+        }
+    }
+
+    /// Returns `true` if a contract stored under `key` can be cached.
+    pub fn is_cacheable(key: &Key) -> bool {
+        match key {
+            Key::Hash(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns a clone of the module cached for `key` and `protocol_version`, marking it as the
+    /// most recently used.
+    pub fn get(&self, key: &Key, protocol_version: ProtocolVersion) -> Option<Module> {
+        if !Self::is_cacheable(key) {
+            return None;
+        }
+
+        let cache_key = (key.normalize(), protocol_version);
+        let mut entries = self.entries.lock().unwrap();
+        let last_used = match entries.modules.get(&cache_key) {
+            Some((_, last_used)) => *last_used,
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+        entries.recency.remove(&last_used);
+        let tick = entries.touch(cache_key);
+
+        let (module, last_used) = entries.modules.get_mut(&cache_key).unwrap();
+        *last_used = tick;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(module.clone())
+    }
+
+    /// Inserts `module` into the cache for `key` and `protocol_version`, evicting the least
+    /// recently used module if the cache is full.
+    ///
+    /// Does nothing if a contract stored under `key` can't be cached.
+    pub fn insert(&self, key: Key, protocol_version: ProtocolVersion, module: Module) {
+        if self.capacity == 0 || !Self::is_cacheable(&key) {
+            return;
+        }
+
+        let cache_key = (key.normalize(), protocol_version);
+        let mut entries = self.entries.lock().unwrap();
+        if let Some((_, last_used)) = entries.modules.remove(&cache_key) {
+            entries.recency.remove(&last_used);
+        } else if entries.modules.len() >= self.capacity {
+            let least_recently_used = entries.recency.keys().next().copied();
+            if let Some(last_used) = least_recently_used {
+                let evicted = entries.recency.remove(&last_used).unwrap();
+                entries.modules.remove(&evicted);
+            }
+        }
+        let tick = entries.touch(cache_key);
+        entries.modules.insert(cache_key, (module, tick));
+    }
+
+    /// Returns the cached module for `key` and `protocol_version`, or loads it with `load` and
+    /// caches the result.
+    pub fn get_or_load<E, F>(
+        &self,
+        key: Key,
+        protocol_version: ProtocolVersion,
+        load: F,
+    ) -> Result<Module, E>
+    where
+        F: FnOnce() -> Result<Module, E>,
+    {
+        if let Some(module) = self.get(&key, protocol_version) {
+            return Ok(module);
+        }
+        let module = load()?;
+        self.insert(key, protocol_version, module.clone());
+        Ok(module)
+    }
+
+    /// Returns the number of modules in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().modules.len()
+    }
+
+    /// Returns `true` if the cache holds no modules.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups of cacheable contracts which found a cached module.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups of cacheable contracts which found no cached module.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        ModuleCache::new(DEFAULT_MODULE_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{Module, ModuleNameSection, NameSection, Section};
+
+    use types::{AccessRights, Key, ProtocolVersion, URef};
+
+    use super::ModuleCache;
+
+    const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+
+    fn named_module(name: &str) -> Module {
+        let section = NameSection::Module(ModuleNameSection::new(name));
+        Module::new(vec![Section::Name(section)])
+    }
+
+    fn hash_key(byte: u8) -> Key {
+        Key::Hash([byte; 32])
+    }
+
+    #[test]
+    fn should_get_inserted_module_and_count_hits_and_misses() {
+        let cache = ModuleCache::default();
+        let module = named_module("a");
+
+        assert!(cache.get(&hash_key(1), PROTOCOL_VERSION).is_none());
+        cache.insert(hash_key(1), PROTOCOL_VERSION, module.clone());
+        assert_eq!(cache.get(&hash_key(1), PROTOCOL_VERSION), Some(module));
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn should_key_by_protocol_version() {
+        let cache = ModuleCache::default();
+        cache.insert(hash_key(1), PROTOCOL_VERSION, named_module("a"));
+
+        let next_version = ProtocolVersion::from_parts(2, 0, 0);
+        assert!(cache.get(&hash_key(1), next_version).is_none());
+    }
+
+    #[test]
+    fn should_not_cache_urefs() {
+        let cache = ModuleCache::default();
+        let key = Key::URef(URef::new([1; 32], AccessRights::READ));
+        cache.insert(key, PROTOCOL_VERSION, named_module("a"));
+
+        assert!(cache.is_empty());
+        assert!(cache.get(&key, PROTOCOL_VERSION).is_none());
+        assert_eq!(cache.misses(), 0);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache::new(2);
+        cache.insert(hash_key(1), PROTOCOL_VERSION, named_module("a"));
+        cache.insert(hash_key(2), PROTOCOL_VERSION, named_module("b"));
+
+        // Using the first module makes the second the least recently used
+        assert!(cache.get(&hash_key(1), PROTOCOL_VERSION).is_some());
+        cache.insert(hash_key(3), PROTOCOL_VERSION, named_module("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&hash_key(1), PROTOCOL_VERSION).is_some());
+        assert!(cache.get(&hash_key(2), PROTOCOL_VERSION).is_none());
+        assert!(cache.get(&hash_key(3), PROTOCOL_VERSION).is_some());
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = ModuleCache::new(0);
+        let module = cache
+            .get_or_load::<(), _>(hash_key(1), PROTOCOL_VERSION, || Ok(named_module("a")))
+            .unwrap();
+
+        assert_eq!(module, named_module("a"));
+        assert!(cache.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_storage::{global_state::ReadWithProofResult, trie::merkle_proof::TrieMerkleProof};
 use types::Key;
 
 use crate::tracking_copy::TrackingCopyQueryResult;
@@ -50,3 +51,39 @@ impl From<TrackingCopyQueryResult> for QueryResult {
         }
     }
 }
+
+pub enum QueryWithProofResult {
+    RootNotFound,
+    ValueNotFound(TrieMerkleProof<Key, StoredValue>),
+    Success(TrieMerkleProof<Key, StoredValue>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryWithProofRequest {
+    state_hash: Blake2bHash,
+    key: Key,
+}
+
+impl QueryWithProofRequest {
+    pub fn new(state_hash: Blake2bHash, key: Key) -> Self {
+        QueryWithProofRequest { state_hash, key }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn key(&self) -> Key {
+        self.key
+    }
+}
+
+impl From<ReadWithProofResult<Key, StoredValue>> for QueryWithProofResult {
+    fn from(read_with_proof_result: ReadWithProofResult<Key, StoredValue>) -> Self {
+        match read_with_proof_result {
+            ReadWithProofResult::Found(proof) => QueryWithProofResult::Success(proof),
+            ReadWithProofResult::NotFound(proof) => QueryWithProofResult::ValueNotFound(proof),
+            ReadWithProofResult::RootNotFound => QueryWithProofResult::RootNotFound,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Records of executed deploys, used to reject a deploy whose hash has already been executed.
+//!
+//! Each executed deploy hash is recorded in global state along with the block time at which it was
+//! executed.  The records live in a key space of their own: [`Key::Local`] keys whose seed is the
+//! hash of a fixed string, which no account or contract can derive as the seed of its own local
+//! keys.
+//!
+//! Each record is accompanied by an index key in a second such key space, whose seed ends with
+//! the block time of the execution in big-endian order.  As keys are ordered by their serialized
+//! forms, the index keys are ordered by execution time, so the expired records can be found and
+//! deleted by walking the index from its start.
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use types::{bytesrepr::ToBytes, BlockTime, CLValue, Key, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH};
+
+use crate::DeployHash;
+
+const EXECUTED_DEPLOYS_SEED_PREIMAGE: &[u8] = b"executed-deploys";
+const EXECUTED_DEPLOYS_INDEX_SEED_PREIMAGE: &[u8] = b"executed-deploys-index";
+const BLOCKTIME_LENGTH: usize = 8;
+const INDEX_SEED_PREFIX_LENGTH: usize = KEY_LOCAL_SEED_LENGTH - BLOCKTIME_LENGTH;
+
+fn index_seed_prefix() -> [u8; INDEX_SEED_PREFIX_LENGTH] {
+    let mut prefix = [0; INDEX_SEED_PREFIX_LENGTH];
+    prefix.copy_from_slice(
+        &Blake2bHash::new(EXECUTED_DEPLOYS_INDEX_SEED_PREIMAGE).value()[..INDEX_SEED_PREFIX_LENGTH],
+    );
+    prefix
+}
+
+/// Returns the key under which the execution of the deploy with the given hash is recorded.
+pub fn executed_deploy_key(deploy_hash: DeployHash) -> Key {
+    let seed = Blake2bHash::new(EXECUTED_DEPLOYS_SEED_PREIMAGE).value();
+    Key::local(seed, &deploy_hash)
+}
+
+/// Returns the record of a deploy executed at `blocktime`.
+pub fn executed_deploy_record(blocktime: BlockTime) -> StoredValue {
+    let blocktime: u64 = blocktime.into();
+    // from_t for u64 is assumed to never panic
+    StoredValue::CLValue(CLValue::from_t(blocktime).unwrap())
+}
+
+/// Returns the index key of the deploy with the given hash executed at `blocktime`.
+pub fn executed_deploy_index_key(deploy_hash: DeployHash, blocktime: BlockTime) -> Key {
+    let blocktime: u64 = blocktime.into();
+    let mut seed = [0; KEY_LOCAL_SEED_LENGTH];
+    seed[..INDEX_SEED_PREFIX_LENGTH].copy_from_slice(&index_seed_prefix());
+    seed[INDEX_SEED_PREFIX_LENGTH..].copy_from_slice(&blocktime.to_be_bytes());
+    Key::Local {
+        seed,
+        hash: deploy_hash,
+    }
+}
+
+/// Returns the value stored under an index key.
+pub fn executed_deploy_index_value() -> StoredValue {
+    // from_t for unit is assumed to never panic
+    StoredValue::CLValue(CLValue::from_t(()).unwrap())
+}
+
+/// Returns the prefix shared by the serialized forms of all index keys.
+pub fn executed_deploy_index_prefix() -> Vec<u8> {
+    // to_bytes for a local key is assumed to never fail
+    let mut prefix = executed_deploy_index_key([0; 32], BlockTime::new(0))
+        .to_bytes()
+        .unwrap();
+    prefix.truncate(prefix.len() - KEY_LOCAL_LENGTH + INDEX_SEED_PREFIX_LENGTH);
+    prefix
+}
+
+/// Returns the deploy hash and the block time of execution held by an index key, or `None` if
+/// `key` isn't an index key.
+pub fn parse_executed_deploy_index_key(key: &Key) -> Option<(DeployHash, BlockTime)> {
+    match key {
+        Key::Local { seed, hash } if seed[..INDEX_SEED_PREFIX_LENGTH] == index_seed_prefix() => {
+            let mut blocktime_bytes = [0; BLOCKTIME_LENGTH];
+            blocktime_bytes.copy_from_slice(&seed[INDEX_SEED_PREFIX_LENGTH..]);
+            Some((*hash, BlockTime::new(u64::from_be_bytes(blocktime_bytes))))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of bytes the record of the deploy with the given hash executed at
+/// `blocktime` adds to global state: the serialized length of the keys and values of the record
+/// and of its index entry.
+pub fn executed_deploy_record_size(deploy_hash: DeployHash, blocktime: BlockTime) -> u64 {
+    let key = executed_deploy_key(deploy_hash);
+    let record = executed_deploy_record(blocktime);
+    let index_key = executed_deploy_index_key(deploy_hash, blocktime);
+    let index_value = executed_deploy_index_value();
+    (key.serialized_length()
+        + record.serialized_length()
+        + index_key.serialized_length()
+        + index_value.serialized_length()) as u64
+}
+
+/// Returns the block time held by the record of an executed deploy, or `None` if `stored_value`
+/// isn't such a record.
+pub fn executed_at(stored_value: StoredValue) -> Option<BlockTime> {
+    let blocktime: u64 = stored_value.as_cl_value()?.to_owned().into_t().ok()?;
+    Some(BlockTime::new(blocktime))
+}
+
+/// Returns `true` if a deploy executed at `executed_at` is a replay when executed again at
+/// `blocktime`, i.e. if its record hasn't expired yet.
+pub fn is_replay(executed_at: BlockTime, blocktime: BlockTime, expiry_millis: u64) -> bool {
+    let executed_at: u64 = executed_at.into();
+    let blocktime: u64 = blocktime.into();
+    blocktime < executed_at.saturating_add(expiry_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPIRY_MILLIS: u64 = 1_000;
+
+    #[test]
+    fn should_record_each_deploy_under_its_own_key() {
+        let key_1 = executed_deploy_key([1; 32]);
+        let key_2 = executed_deploy_key([2; 32]);
+        assert_ne!(key_1, key_2);
+        assert_eq!(key_1, executed_deploy_key([1; 32]));
+        assert!(match key_1 {
+            Key::Local { .. } => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn should_read_back_recorded_block_time() {
+        let blocktime = BlockTime::new(42);
+        let record = executed_deploy_record(blocktime);
+        assert_eq!(executed_at(record), Some(blocktime));
+    }
+
+    #[test]
+    fn should_size_record_as_its_serialized_keys_and_values() {
+        let blocktime = BlockTime::new(42);
+        let key = executed_deploy_key([1; 32]);
+        let record = executed_deploy_record(blocktime);
+        let index_key = executed_deploy_index_key([1; 32], blocktime);
+        let index_value = executed_deploy_index_value();
+        assert_eq!(
+            executed_deploy_record_size([1; 32], blocktime),
+            (key.serialized_length()
+                + record.serialized_length()
+                + index_key.serialized_length()
+                + index_value.serialized_length()) as u64
+        );
+    }
+
+    #[test]
+    fn should_parse_index_key() {
+        let blocktime = BlockTime::new(42);
+        let index_key = executed_deploy_index_key([1; 32], blocktime);
+        assert_eq!(
+            parse_executed_deploy_index_key(&index_key),
+            Some(([1; 32], blocktime))
+        );
+        assert_eq!(
+            parse_executed_deploy_index_key(&executed_deploy_key([1; 32])),
+            None
+        );
+        assert_eq!(parse_executed_deploy_index_key(&Key::Hash([1; 32])), None);
+    }
+
+    #[test]
+    fn should_order_index_keys_by_block_time_under_common_prefix() {
+        let prefix = executed_deploy_index_prefix();
+        let earlier = executed_deploy_index_key([255; 32], BlockTime::new(255))
+            .to_bytes()
+            .unwrap();
+        let later = executed_deploy_index_key([0; 32], BlockTime::new(256))
+            .to_bytes()
+            .unwrap();
+        assert!(earlier.starts_with(&prefix));
+        assert!(later.starts_with(&prefix));
+        assert!(earlier < later);
+        assert!(!executed_deploy_key([1; 32])
+            .to_bytes()
+            .unwrap()
+            .starts_with(&prefix));
+    }
+
+    #[test]
+    fn should_be_replay_until_record_expires() {
+        let executed_at = BlockTime::new(10_000);
+        assert!(is_replay(executed_at, executed_at, EXPIRY_MILLIS));
+        assert!(is_replay(
+            executed_at,
+            BlockTime::new(10_000 + EXPIRY_MILLIS - 1),
+            EXPIRY_MILLIS
+        ));
+        assert!(!is_replay(
+            executed_at,
+            BlockTime::new(10_000 + EXPIRY_MILLIS),
+            EXPIRY_MILLIS
+        ));
+    }
+
+    #[test]
+    fn should_not_overflow_expiry() {
+        let executed_at = BlockTime::new(u64::max_value());
+        assert!(is_replay(executed_at, executed_at, u64::max_value()));
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, system_contract_errors::pos, ProtocolVersion, U512};
+
+use crate::engine_state::{system_call::SystemCallResult, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    slashes: Vec<(PublicKey, U512)>,
+}
+
+impl SlashRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        slashes: Vec<(PublicKey, U512)>,
+    ) -> Self {
+        SlashRequest {
+            pre_state_hash,
+            protocol_version,
+            slashes,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn slashes(&self) -> &[(PublicKey, U512)] {
+        &self.slashes
+    }
+}
+
+#[derive(Debug)]
+pub enum SlashError {
+    /// The slashed validator is not bonded.
+    UnknownValidator(PublicKey),
+    /// The slashed amount exceeds the validator's stake.
+    Overdrawn {
+        validator: PublicKey,
+        stake: U512,
+        amount: U512,
+    },
+    /// Slashing would leave no bonded validators.
+    LastValidator(PublicKey),
+    /// The Proof of Stake contract rejected the slash.
+    ProofOfStake(pos::Error),
+    /// Executing the Proof of Stake contract failed.
+    Execution(Error),
+}
+
+impl fmt::Display for SlashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SlashError::UnknownValidator(validator) => {
+                write!(f, "Unknown validator: {}", validator)
+            }
+            SlashError::Overdrawn {
+                validator,
+                stake,
+                amount,
+            } => write!(
+                f,
+                "Slash of {} exceeds stake of {} for validator {}",
+                amount, stake, validator
+            ),
+            SlashError::LastValidator(validator) => {
+                write!(f, "Cannot slash last validator: {}", validator)
+            }
+            SlashError::ProofOfStake(error) => write!(f, "Proof of stake error: {:?}", error),
+            SlashError::Execution(error) => write!(f, "Execution error: {}", error),
+        }
+    }
+}
+
+pub type SlashResult = SystemCallResult<SlashError>;
@@ -0,0 +1,58 @@
+use std::{collections::HashMap, fmt};
+
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{account::PublicKey, bytesrepr, Key, U512};
+
+/// The result of a system call into the Proof of Stake contract whose effects are committed on
+/// success, with variants shared by every such call and a `Failure` variant holding the error `E`
+/// specific to the call.
+pub enum SystemCallResult<E> {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Failure(E),
+    Success {
+        post_state_hash: Blake2bHash,
+        bonded_validators: HashMap<PublicKey, U512>,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for SystemCallResult<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SystemCallResult::RootNotFound => write!(f, "Root not found"),
+            SystemCallResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            SystemCallResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            SystemCallResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            SystemCallResult::Failure(error) => write!(f, "Failure: {}", error),
+            SystemCallResult::Success {
+                post_state_hash,
+                bonded_validators,
+            } => write!(f, "Success: {} {:?}", post_state_hash, bonded_validators),
+        }
+    }
+}
+
+impl<E> SystemCallResult<E> {
+    pub fn from_commit_result(commit_result: CommitResult) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => SystemCallResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => SystemCallResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => {
+                SystemCallResult::TypeMismatch(type_mismatch)
+            }
+            CommitResult::Serialization(error) => SystemCallResult::Serialization(error),
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => SystemCallResult::Success {
+                post_state_hash: state_root,
+                bonded_validators,
+            },
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+use crate::engine_state::{system_call::SystemCallResult, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnbondPayoutRequest {
+    pre_state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+    era_height: u64,
+}
+
+impl UnbondPayoutRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        era_height: u64,
+    ) -> Self {
+        UnbondPayoutRequest {
+            pre_state_hash,
+            protocol_version,
+            era_height,
+        }
+    }
+
+    pub fn pre_state_hash(&self) -> Blake2bHash {
+        self.pre_state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn era_height(&self) -> u64 {
+        self.era_height
+    }
+}
+
+#[derive(Debug)]
+pub enum UnbondPayoutError {
+    /// Transferring a matured unbonding request from the bonding purse to the validator failed.
+    TransferFailed,
+    /// Executing the Proof of Stake contract failed.
+    Execution(Error),
+}
+
+impl fmt::Display for UnbondPayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            UnbondPayoutError::TransferFailed => write!(f, "Transfer from bonding purse failed"),
+            UnbondPayoutError::Execution(error) => write!(f, "Execution error: {}", error),
+        }
+    }
+}
+
+pub type UnbondPayoutResult = SystemCallResult<UnbondPayoutError>;
@@ -2,7 +2,10 @@ use std::fmt;
 
 use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+    wasm_limits::WasmLimits,
+};
 use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -60,10 +63,15 @@ pub struct UpgradeConfig {
     upgrade_installer_args: Option<Vec<u8>>,
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
+    host_function_costs: Option<HostFunctionCosts>,
+    storage_costs: Option<StorageCosts>,
+    wasm_limits: Option<WasmLimits>,
+    deploy_hash_expiry_millis: Option<u64>,
     activation_point: Option<ActivationPoint>,
 }
 
 impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pre_state_hash: Blake2bHash,
         current_protocol_version: ProtocolVersion,
@@ -71,6 +79,10 @@ impl UpgradeConfig {
         upgrade_installer_args: Option<Vec<u8>>,
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
+        host_function_costs: Option<HostFunctionCosts>,
+        storage_costs: Option<StorageCosts>,
+        wasm_limits: Option<WasmLimits>,
+        deploy_hash_expiry_millis: Option<u64>,
         activation_point: Option<ActivationPoint>,
     ) -> Self {
         UpgradeConfig {
@@ -80,6 +92,10 @@ impl UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
             activation_point,
         }
     }
@@ -110,6 +126,22 @@ impl UpgradeConfig {
         self.wasm_costs
     }
 
+    pub fn host_function_costs(&self) -> Option<HostFunctionCosts> {
+        self.host_function_costs
+    }
+
+    pub fn storage_costs(&self) -> Option<StorageCosts> {
+        self.storage_costs
+    }
+
+    pub fn wasm_limits(&self) -> Option<WasmLimits> {
+        self.wasm_limits
+    }
+
+    pub fn deploy_hash_expiry_millis(&self) -> Option<u64> {
+        self.deploy_hash_expiry_millis
+    }
+
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
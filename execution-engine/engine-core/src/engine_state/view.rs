@@ -0,0 +1,38 @@
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, CLValue, ProtocolVersion};
+
+use crate::engine_state::executable_deploy_item::ExecutableDeployItem;
+
+pub struct ViewRequest {
+    pub state_hash: Blake2bHash,
+    pub block_time: u64,
+    /// The account on whose behalf the contract is called.
+    pub address: PublicKey,
+    /// The stored contract to call, and its arguments.
+    pub contract: ExecutableDeployItem,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl ViewRequest {
+    pub fn new(
+        state_hash: Blake2bHash,
+        block_time: u64,
+        address: PublicKey,
+        contract: ExecutableDeployItem,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        ViewRequest {
+            state_hash,
+            block_time,
+            address,
+            contract,
+            protocol_version,
+        }
+    }
+}
+
+pub enum ViewResult {
+    RootNotFound,
+    /// The value returned by the contract.
+    Success(CLValue),
+}
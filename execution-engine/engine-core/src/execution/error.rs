@@ -24,6 +24,14 @@ pub enum Error {
     ForgedReference(URef),
     URefNotFound(String),
     FunctionNotFound(String),
+    /// The called contract does not export the named entry point
+    NoSuchEntryPoint(String),
+    /// The contract package has no such version
+    InvalidContractVersion(u32),
+    /// The requested version of the contract package has been disabled
+    DisabledContractVersion(u32),
+    /// The contract package under the given key has no enabled versions
+    NoEnabledContractVersion(Key),
     ParityWasm(elements::Error),
     GasLimit,
     Ret(Vec<URef>),
@@ -47,6 +55,8 @@ pub enum Error {
     CLValue(CLValueError),
     HostBufferEmpty,
     UnsupportedWasmStart,
+    /// A read-only context, such as a view call, attempted to write to the given key
+    ReadOnlyWrite(Key),
 }
 
 impl fmt::Display for Error {
@@ -19,9 +19,13 @@ use types::{
 
 use crate::{
     engine_state::{
-        execution_result::ExecutionResult, system_contract_cache::SystemContractCache, EngineConfig,
+        execution_result::ExecutionResult, module_cache::ModuleCache,
+        system_contract_cache::SystemContractCache, EngineConfig,
+    },
+    execution::{
+        address_generator::AddressGenerator, random_generator::RandomGenerator, Error,
+        FN_STORE_ID_INITIAL,
     },
-    execution::{address_generator::AddressGenerator, Error, FN_STORE_ID_INITIAL},
     runtime::{extract_access_rights_from_keys, instance_and_memory, Runtime},
     runtime_context::{self, RuntimeContext},
     tracking_copy::TrackingCopy,
@@ -48,6 +52,7 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    storage_bytes: 0,
                 };
             }
         }
@@ -62,6 +67,7 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    storage_bytes: 0,
                 };
             }
         }
@@ -70,12 +76,16 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    module_cache: ModuleCache,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
-    pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+    pub fn new(config: EngineConfig, module_cache: ModuleCache) -> Self {
+        Executor {
+            config,
+            module_cache,
+        }
     }
 
     pub fn config(&self) -> EngineConfig {
@@ -103,8 +113,11 @@ impl Executor {
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
     {
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_limits().max_memory_pages
+        ));
 
         let mut named_keys = account.named_keys().clone();
 
@@ -118,12 +131,18 @@ impl Executor {
             };
 
         let address_generator = AddressGenerator::new(&deploy_hash, phase);
+        let random_generator = RandomGenerator::new(&deploy_hash, phase);
         let gas_counter: Gas = Gas::default();
 
         // Snapshot of effects before execution, so in case of error
         // only nonce update can be returned.
         let effects_snapshot = tc.borrow().effect();
 
+        // Size of global state growth before execution, so that only the growth caused by this
+        // execution is charged for.
+        let storage_size_before =
+            on_fail_charge!(tc.borrow_mut().storage_size_delta(correlation_id));
+
         let args: Vec<CLValue> = if args.is_empty() {
             Vec::new()
         } else {
@@ -147,15 +166,18 @@ impl Executor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             Rc::new(RefCell::new(address_generator)),
+            Rc::new(RefCell::new(random_generator)),
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            false,
         );
 
         let mut runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            ModuleCache::clone(&self.module_cache),
             memory,
             parity_module,
             context,
@@ -170,16 +192,24 @@ impl Executor {
                     Default::default(),
                 ) {
                     Ok(_value) => {
+                        let storage_bytes = on_fail_charge!(
+                            runtime.charge_storage(storage_size_before),
+                            runtime.context().gas_counter(),
+                            effects_snapshot
+                        );
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
-                        }
+                            storage_bytes,
+                            events: runtime.context().events().to_vec(),
+                        };
                     }
                     Err(error) => {
                         return ExecutionResult::Failure {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            storage_bytes: 0,
                         }
                     }
                 }
@@ -191,16 +221,24 @@ impl Executor {
                     Default::default(),
                 ) {
                     Ok(_value) => {
+                        let storage_bytes = on_fail_charge!(
+                            runtime.charge_storage(storage_size_before),
+                            runtime.context().gas_counter(),
+                            effects_snapshot
+                        );
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
-                        }
+                            storage_bytes,
+                            events: runtime.context().events().to_vec(),
+                        };
                     }
                     Err(error) => {
                         return ExecutionResult::Failure {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            storage_bytes: 0,
                         }
                     }
                 }
@@ -213,9 +251,17 @@ impl Executor {
             effects_snapshot
         );
 
+        let storage_bytes = on_fail_charge!(
+            runtime.charge_storage(storage_size_before),
+            runtime.context().gas_counter(),
+            effects_snapshot
+        );
+
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            storage_bytes,
+            events: runtime.context().events().to_vec(),
         }
     }
 
@@ -259,6 +305,10 @@ impl Executor {
             let address_generator = AddressGenerator::new(&deploy_hash, phase);
             Rc::new(RefCell::new(address_generator))
         };
+        let random_generator = {
+            let random_generator = RandomGenerator::new(&deploy_hash, phase);
+            Rc::new(RefCell::new(random_generator))
+        };
         let gas_counter = Gas::default(); // maybe const?
 
         // Snapshot of effects before execution, so in case of error only nonce update
@@ -286,18 +336,24 @@ impl Executor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             address_generator,
+            random_generator,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            false,
         );
 
-        let (instance, memory) =
-            on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
+        let (instance, memory) = on_fail_charge!(instance_and_memory(
+            parity_module.clone(),
+            protocol_version,
+            protocol_data.wasm_limits().max_memory_pages
+        ));
 
         let mut runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            ModuleCache::clone(&self.module_cache),
             memory,
             parity_module,
             context,
@@ -314,6 +370,8 @@ impl Executor {
                     return ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        storage_bytes: 0,
+                        events: runtime.context().events().to_vec(),
                     }
                 }
                 Err(error) => {
@@ -321,6 +379,7 @@ impl Executor {
                         error: error.into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        storage_bytes: 0,
                     }
                 }
             }
@@ -332,6 +391,8 @@ impl Executor {
                 return ExecutionResult::Success {
                     effect: runtime.context().effect(),
                     cost: runtime.context().gas_counter(),
+                    storage_bytes: 0,
+                    events: runtime.context().events().to_vec(),
                 }
             }
         };
@@ -343,6 +404,8 @@ impl Executor {
                     return ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        storage_bytes: 0,
+                        events: runtime.context().events().to_vec(),
                     };
                 }
                 Error::Revert(status) => {
@@ -350,6 +413,7 @@ impl Executor {
                         error: Error::Revert(*status).into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        storage_bytes: 0,
                     };
                 }
                 _ => {}
@@ -360,6 +424,7 @@ impl Executor {
             error: Error::Interpreter(error).into(),
             effect: effects_snapshot,
             cost: runtime.context().gas_counter(),
+            storage_bytes: 0,
         }
     }
 
@@ -381,6 +446,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        read_only: bool,
     ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
@@ -402,6 +468,7 @@ impl Executor {
         };
 
         let gas_counter = Gas::default();
+        let random_generator = Rc::new(RefCell::new(RandomGenerator::new(&deploy_hash, phase)));
 
         let runtime_context = RuntimeContext::new(
             state,
@@ -417,17 +484,24 @@ impl Executor {
             gas_counter,
             FN_STORE_ID_INITIAL,
             address_generator,
+            random_generator,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            read_only,
         );
 
-        let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
+        let (instance, memory) = instance_and_memory(
+            module.clone(),
+            protocol_version,
+            protocol_data.wasm_limits().max_memory_pages,
+        )?;
 
         let runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            ModuleCache::clone(&self.module_cache),
             memory,
             module,
             runtime_context,
@@ -459,6 +533,58 @@ impl Executor {
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
         T: FromBytes + CLTyped,
+    {
+        let return_value = self.exec_with_return_value(
+            module,
+            args,
+            keys,
+            base_key,
+            account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            state,
+            phase,
+            protocol_data,
+            system_contract_cache,
+            false,
+        )?;
+        let ret = return_value.into_t()?;
+        Ok(ret)
+    }
+
+    /// Executes a module and returns the value it passes to `runtime::ret`, or the unit type if it
+    /// returns without calling `runtime::ret`.
+    ///
+    /// If `read_only` is set, the module and every contract it calls fail with
+    /// [`Error::ReadOnlyWrite`] on their first attempt to write to global state.
+    pub fn exec_with_return_value<R>(
+        &self,
+        module: Module,
+        args: Vec<u8>,
+        keys: &mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &Account,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+        read_only: bool,
+    ) -> Result<CLValue, Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
     {
         let (instance, mut runtime) = self.create_runtime(
             module,
@@ -477,6 +603,7 @@ impl Executor {
             phase,
             protocol_data,
             system_contract_cache,
+            read_only,
         )?;
 
         let error: wasmi::Error = match instance.invoke_export("call", &[], &mut runtime) {
@@ -489,23 +616,18 @@ impl Executor {
                 // Treat as though the execution returned the unit type `()` as per Rust functions
                 // which don't specify a return value.
                 let result = runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?);
-                let ret = result.into_t()?;
-                return Ok(ret);
+                return Ok(result);
             }
         };
 
-        let return_value: CLValue = match error
+        match error
             .as_host_error()
             .and_then(|host_error| host_error.downcast_ref::<Error>())
         {
-            Some(Error::Ret(_)) => runtime
-                .take_host_buffer()
-                .ok_or(Error::ExpectedReturnValue)?,
-            Some(Error::Revert(code)) => return Err(Error::Revert(*code)),
-            _ => return Err(Error::Interpreter(error)),
-        };
-
-        let ret = return_value.into_t()?;
-        Ok(ret)
+            Some(Error::Ret(_)) => runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue),
+            Some(Error::Revert(code)) => Err(Error::Revert(*code)),
+            Some(Error::ReadOnlyWrite(key)) => Err(Error::ReadOnlyWrite(*key)),
+            _ => Err(Error::Interpreter(error)),
+        }
     }
 }
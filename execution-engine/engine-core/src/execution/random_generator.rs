@@ -0,0 +1,82 @@
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+use types::Phase;
+
+const SEED_LENGTH: usize = 32;
+
+/// Domain separator mixed into the seed, so that the stream never coincides with the one used by
+/// the [`AddressGenerator`](super::AddressGenerator) for the same deploy and phase.
+const RANDOM_BYTES_DOMAIN: &[u8] = b"random_bytes";
+
+/// A [`RandomGenerator`] produces the bytes returned to contracts by the `random_bytes` host
+/// function.
+///
+/// It is independent of the [`AddressGenerator`](super::AddressGenerator), so drawing random bytes
+/// doesn't affect the addresses of [`URef`](types::URef)s created later in the same deploy.
+pub struct RandomGenerator(ChaChaRng);
+
+impl RandomGenerator {
+    /// Creates a [`RandomGenerator`] from a 32-byte hash digest and [`Phase`].
+    pub fn new(hash: &[u8], phase: Phase) -> RandomGenerator {
+        let mut seed: [u8; SEED_LENGTH] = [0u8; SEED_LENGTH];
+        let mut hasher = VarBlake2b::new(SEED_LENGTH).unwrap();
+        hasher.input(RANDOM_BYTES_DOMAIN);
+        hasher.input(hash);
+        hasher.input(&[phase as u8]);
+        hasher.variable_result(|hash| seed.clone_from_slice(hash));
+        RandomGenerator(ChaChaRng::from_seed(seed))
+    }
+
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::Phase;
+
+    use super::RandomGenerator;
+    use crate::execution::AddressGenerator;
+
+    const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
+    const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
+
+    fn draw(random_generator: &mut RandomGenerator) -> [u8; 32] {
+        let mut buff = [0u8; 32];
+        random_generator.fill_bytes(&mut buff);
+        buff
+    }
+
+    #[test]
+    fn should_generate_same_bytes_for_same_seed() {
+        let mut rg_a = RandomGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+        let mut rg_b = RandomGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+
+        assert_eq!(draw(&mut rg_a), draw(&mut rg_b))
+    }
+
+    #[test]
+    fn should_generate_different_bytes_for_different_seeds_and_phases() {
+        let mut rg_a = RandomGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+        let mut rg_b = RandomGenerator::new(&DEPLOY_HASH_2, Phase::Session);
+        let mut rg_c = RandomGenerator::new(&DEPLOY_HASH_1, Phase::Payment);
+        let random_a = draw(&mut rg_a);
+
+        assert_ne!(random_a, draw(&mut rg_b));
+        assert_ne!(random_a, draw(&mut rg_c));
+    }
+
+    #[test]
+    fn should_not_generate_same_bytes_as_address_generator() {
+        let mut rg = RandomGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+        let mut ag = AddressGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+
+        assert_ne!(draw(&mut rg), ag.create_address())
+    }
+}
@@ -15,6 +15,8 @@ fn on_fail_charge_test_helper<T>(
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        storage_bytes: 0,
+        events: Vec::new(),
     }
 }
 
@@ -55,6 +57,8 @@ fn on_fail_charge_with_action() {
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            storage_bytes: 0,
+            events: Vec::new(),
         }
     };
     match f() {
@@ -7,28 +7,59 @@ use wasmi::ModuleImportResolver;
 
 use types::ProtocolVersion;
 
+#[cfg(test)]
+use engine_wasm_prep::wasm_limits::DEFAULT_MAX_MEMORY_PAGES;
+
 use self::error::ResolverError;
 use crate::resolvers::memory_resolver::MemoryResolver;
 
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
+/// * `max_memory_pages` Max number of pages of memory a module may import.
 pub fn create_module_resolver(
     protocol_version: ProtocolVersion,
+    max_memory_pages: u32,
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            max_memory_pages,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
 #[test]
 fn resolve_invalid_module() {
-    assert!(create_module_resolver(ProtocolVersion::default()).is_err());
+    assert!(create_module_resolver(ProtocolVersion::default(), DEFAULT_MAX_MEMORY_PAGES).is_err());
 }
 
 #[test]
 fn protocol_version_1_always_resolves() {
-    assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
+    assert!(create_module_resolver(ProtocolVersion::V1_0_0, DEFAULT_MAX_MEMORY_PAGES).is_ok());
+}
+
+#[test]
+fn should_resolve_every_host_function_known_to_validation() {
+    use std::convert::TryFrom;
+
+    use engine_wasm_prep::validation::HOST_FUNCTION_NAMES;
+    use wasmi::Signature;
+
+    use self::v1_function_index::FunctionIndex;
+
+    let resolver =
+        create_module_resolver(ProtocolVersion::V1_0_0, DEFAULT_MAX_MEMORY_PAGES).unwrap();
+    let signature = Signature::new(&[][..], None);
+    for name in HOST_FUNCTION_NAMES {
+        assert!(
+            resolver.resolve_func(name, &signature).is_ok(),
+            "should resolve {}",
+            name
+        );
+    }
+    // Every function index should be covered by exactly one name
+    assert!(FunctionIndex::try_from(HOST_FUNCTION_NAMES.len() - 1).is_ok());
+    assert!(FunctionIndex::try_from(HOST_FUNCTION_NAMES.len()).is_err());
 }
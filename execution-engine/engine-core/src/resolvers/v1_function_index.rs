@@ -43,6 +43,17 @@ pub enum FunctionIndex {
     GetMainPurseIndex,
     GetArgSizeFuncIndex,
     ReadHostBufferIndex,
+    EmitEventIndex,
+    Blake2bIndex,
+    VerifySignatureIndex,
+    CallEntryPointIndex,
+    StoreEntryPointsIndex,
+    StoreEntryPointsAtHashIndex,
+    CreateContractPackageAtURefIndex,
+    AddContractVersionIndex,
+    SetContractVersionEnabledIndex,
+    CallVersionedContractIndex,
+    RandomBytesIndex,
 }
 
 impl Into<usize> for FunctionIndex {
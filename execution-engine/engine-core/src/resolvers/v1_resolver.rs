@@ -14,11 +14,11 @@ pub struct RuntimeModuleImportResolver {
     max_memory: u32,
 }
 
-impl Default for RuntimeModuleImportResolver {
-    fn default() -> Self {
+impl RuntimeModuleImportResolver {
+    pub fn new(max_memory: u32) -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
-            max_memory: 64,
+            max_memory,
         }
     }
 }
@@ -188,6 +188,50 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadHostBufferIndex.into(),
             ),
+            "emit_event" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], None),
+                FunctionIndex::EmitEventIndex.into(),
+            ),
+            "blake2b" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Blake2bIndex.into(),
+            ),
+            "verify_signature" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::VerifySignatureIndex.into(),
+            ),
+            "call_entry_point" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
+                FunctionIndex::CallEntryPointIndex.into(),
+            ),
+            "store_entry_points" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], None),
+                FunctionIndex::StoreEntryPointsIndex.into(),
+            ),
+            "store_entry_points_at_hash" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], None),
+                FunctionIndex::StoreEntryPointsAtHashIndex.into(),
+            ),
+            "create_contract_package_at_uref" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::CreateContractPackageAtURefIndex.into(),
+            ),
+            "add_contract_version" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], None),
+                FunctionIndex::AddContractVersionIndex.into(),
+            ),
+            "set_contract_version_enabled" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::SetContractVersionEnabledIndex.into(),
+            ),
+            "call_versioned_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
+                FunctionIndex::CallVersionedContractIndex.into(),
+            ),
+            "random_bytes" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::RandomBytesIndex.into(),
+            ),
             _ => {
                 return Err(InterpreterError::Function(format!(
                     "host module doesn't export function with name {}",
@@ -13,7 +13,10 @@ use engine_shared::{gas::Gas, stored_value::StoredValue};
 use engine_storage::global_state::StateReader;
 
 use super::{args::Args, Error, Runtime};
-use crate::resolvers::v1_function_index::FunctionIndex;
+use crate::{
+    engine_state::executable_deploy_item::DEFAULT_ENTRY_POINT_NAME,
+    resolvers::v1_function_index::FunctionIndex,
+};
 
 impl<'a, R> Externals for Runtime<'a, R>
 where
@@ -32,7 +35,12 @@ where
                 // args(1) = size of key in Wasm memory
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                let cost = self.context.host_function_costs().read;
+                self.charge_host_function_call(cost, key_size)?;
                 let ret = self.read(key_ptr, key_size, output_size_ptr)?;
+                if ret.is_ok() {
+                    self.charge_host_buffer_bytes(cost)?;
+                }
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -41,7 +49,12 @@ where
                 // args(1) = size of key in Wasm memory
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                let cost = self.context.host_function_costs().read_local;
+                self.charge_host_function_call(cost, key_size)?;
                 let ret = self.read_local(key_ptr, key_size, output_size_ptr)?;
+                if ret.is_ok() {
+                    self.charge_host_buffer_bytes(cost)?;
+                }
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -49,6 +62,10 @@ where
                 // args(0) = pointer to amount of keys (output)
                 // args(1) = pointer to amount of serialized bytes (output)
                 let (total_keys_ptr, result_size_ptr) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().load_named_keys,
+                    0,
+                )?;
                 let ret = self.load_named_keys(total_keys_ptr, result_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -58,7 +75,12 @@ where
                 // args(1) = size of key
                 // args(2) = pointer to value
                 // args(3) = size of value
-                let (key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                let (key_ptr, key_size, value_ptr, value_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().write,
+                    key_size.saturating_add(value_size),
+                )?;
                 self.write(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -68,7 +90,12 @@ where
                 // args(1) = size of key
                 // args(2) = pointer to value
                 // args(3) = size of value
-                let (key_bytes_ptr, key_bytes_size, value_ptr, value_size) = Args::parse(args)?;
+                let (key_bytes_ptr, key_bytes_size, value_ptr, value_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().write_local,
+                    key_bytes_size.saturating_add(value_size),
+                )?;
                 self.write_local(key_bytes_ptr, key_bytes_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -78,7 +105,12 @@ where
                 // args(1) = size of key
                 // args(2) = pointer to value
                 // args(3) = size of value
-                let (key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                let (key_ptr, key_size, value_ptr, value_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().add,
+                    key_size.saturating_add(value_size),
+                )?;
                 self.add(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -88,7 +120,12 @@ where
                 // args(1) = size of key
                 // args(2) = pointer to value
                 // args(3) = size of value
-                let (key_bytes_ptr, key_bytes_size, value_ptr, value_size) = Args::parse(args)?;
+                let (key_bytes_ptr, key_bytes_size, value_ptr, value_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().add_local,
+                    key_bytes_size.saturating_add(value_size),
+                )?;
                 self.add_local(key_bytes_ptr, key_bytes_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -98,6 +135,10 @@ where
                 // args(1) = pointer to initial value
                 // args(2) = size of initial value
                 let (key_ptr, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().new_uref,
+                    value_size,
+                )?;
                 self.new_uref(key_ptr, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -106,6 +147,7 @@ where
                 // args(0) = index of host runtime arg to load
                 // args(1) = pointer to a argument size (output)
                 let (index, size_ptr): (u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().get_arg_size, 0)?;
                 let ret = self.get_arg_size(index as usize, size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -115,6 +157,10 @@ where
                 // args(1) = pointer to destination in Wasm memory
                 // args(2) = size of destination pointer memory
                 let (index, dest_ptr, dest_size): (u32, _, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_arg,
+                    dest_size,
+                )?;
                 let ret = self.get_arg(index as usize, dest_ptr, dest_size as usize)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -123,6 +169,7 @@ where
                 // args(0) = pointer to value
                 // args(1) = size of value
                 let (value_ptr, value_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().ret, value_size)?;
 
                 Err(self.ret(value_ptr, value_size as usize))
             }
@@ -133,14 +180,23 @@ where
                 // args(2) = pointer to function arguments in Wasm memory
                 // args(3) = size of arguments
                 // args(4) = pointer to result size (output)
-                let (key_ptr, key_size, args_ptr, args_size, result_size_ptr): (_, _, _, u32, _) =
+                let (key_ptr, key_size, args_ptr, args_size, result_size_ptr): (_, u32, _, u32, _) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().call_contract,
+                    key_size.saturating_add(args_size),
+                )?;
 
                 let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
                 let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
 
-                let ret =
-                    self.call_contract_host_buffer(key_contract, args_bytes, result_size_ptr)?;
+                let ret = self.call_contract_host_buffer(
+                    key_contract,
+                    None,
+                    DEFAULT_ENTRY_POINT_NAME,
+                    args_bytes,
+                    result_size_ptr,
+                )?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -157,6 +213,10 @@ where
                     u32,
                     u32,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_key,
+                    name_size,
+                )?;
                 let ret = self.load_key(
                     name_ptr,
                     name_size,
@@ -171,6 +231,10 @@ where
                 // args(0) = pointer to key name in Wasm memory
                 // args(1) = size of key name
                 let (name_ptr, name_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().has_key,
+                    name_size,
+                )?;
                 let result = self.has_key(name_ptr, name_size)?;
                 Ok(Some(RuntimeValue::I32(result)))
             }
@@ -180,7 +244,12 @@ where
                 // args(1) = size of key name
                 // args(2) = pointer to key in Wasm memory
                 // args(3) = size of key
-                let (name_ptr, name_size, key_ptr, key_size) = Args::parse(args)?;
+                let (name_ptr, name_size, key_ptr, key_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().put_key,
+                    name_size.saturating_add(key_size),
+                )?;
                 self.put_key(name_ptr, name_size, key_ptr, key_size)?;
                 Ok(None)
             }
@@ -189,6 +258,10 @@ where
                 // args(0) = pointer to key name in Wasm memory
                 // args(1) = size of key name
                 let (name_ptr, name_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().remove_key,
+                    name_size,
+                )?;
                 self.remove_key(name_ptr, name_size)?;
                 Ok(None)
             }
@@ -196,6 +269,7 @@ where
             FunctionIndex::GetCallerIndex => {
                 // args(0) = pointer where a size of serialized bytes will be stored
                 let output_size = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().get_caller, 0)?;
                 let ret = self.get_caller(output_size)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -203,6 +277,10 @@ where
             FunctionIndex::GetBlocktimeIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_blocktime,
+                    0,
+                )?;
                 self.get_blocktime(dest_ptr)?;
                 Ok(None)
             }
@@ -221,7 +299,17 @@ where
                 // args(3) = size of the additional unforgable names
                 // args(4) = pointer to a Wasm memory where we will save
                 //           uref address of the new function
-                let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr) = Args::parse(args)?;
+                let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().store_function,
+                    name_size.saturating_add(urefs_size),
+                )?;
                 let _uref_type: u32 = urefs_size;
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
                 let uref_bytes = self
@@ -242,7 +330,17 @@ where
                 // args(3) = size of the additional unforgable names
                 // args(4) = pointer to a Wasm memory where we will save
                 //           hash of the new function
-                let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr) = Args::parse(args)?;
+                let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().store_function_at_hash,
+                    name_size.saturating_add(urefs_size),
+                )?;
                 let _uref_type: u32 = urefs_size;
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
                 let uref_bytes = self
@@ -259,6 +357,10 @@ where
                 // args(0) = pointer to value to validate
                 // args(1) = size of value
                 let (uref_ptr, uref_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().is_valid_uref,
+                    uref_size,
+                )?;
 
                 Ok(Some(RuntimeValue::I32(i32::from(
                     self.is_valid_uref(uref_ptr, uref_size)?,
@@ -268,6 +370,7 @@ where
             FunctionIndex::RevertFuncIndex => {
                 // args(0) = status u32
                 let status = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().revert, 0)?;
 
                 Err(self.revert(status))
             }
@@ -278,6 +381,10 @@ where
                 // args(2) = weight of the key
                 let (public_key_ptr, public_key_size, weight_value): (u32, u32, u8) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().add_associated_key,
+                    public_key_size,
+                )?;
                 let value = self.add_associated_key(
                     public_key_ptr,
                     public_key_size as usize,
@@ -290,6 +397,10 @@ where
                 // args(0) = pointer to array of bytes of a public key
                 // args(1) = size of a public key
                 let (public_key_ptr, public_key_size): (_, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().remove_associated_key,
+                    public_key_size,
+                )?;
                 let value = self.remove_associated_key(public_key_ptr, public_key_size as usize)?;
                 Ok(Some(RuntimeValue::I32(value)))
             }
@@ -300,6 +411,10 @@ where
                 // args(2) = weight of the key
                 let (public_key_ptr, public_key_size, weight_value): (u32, u32, u8) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().update_associated_key,
+                    public_key_size,
+                )?;
                 let value = self.update_associated_key(
                     public_key_ptr,
                     public_key_size as usize,
@@ -312,6 +427,10 @@ where
                 // args(0) = action type
                 // args(1) = new threshold
                 let (action_type_value, threshold_value): (u32, u8) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().set_action_threshold,
+                    0,
+                )?;
                 let value = self.set_action_threshold(action_type_value, threshold_value)?;
                 Ok(Some(RuntimeValue::I32(value)))
             }
@@ -320,6 +439,7 @@ where
                 // args(0) = pointer to array for return value
                 // args(1) = length of array for return value
                 let (dest_ptr, dest_size): (u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().create_purse, 0)?;
                 let purse = self.create_purse()?;
                 let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
                 assert_eq!(dest_size, purse_bytes.len() as u32);
@@ -336,6 +456,10 @@ where
                 // args(3) = length of array of bytes of an amount
                 let (key_ptr, key_size, amount_ptr, amount_size): (u32, u32, u32, u32) =
                     Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().transfer_to_account,
+                    key_size.saturating_add(amount_size),
+                )?;
                 let public_key: PublicKey = {
                     let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
                     bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
@@ -363,6 +487,14 @@ where
                     u32,
                     u32,
                 ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context
+                        .host_function_costs()
+                        .transfer_from_purse_to_account,
+                    source_size
+                        .saturating_add(key_size)
+                        .saturating_add(amount_size),
+                )?;
 
                 let source_purse = {
                     let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
@@ -387,8 +519,22 @@ where
                 // args(3) = length of array of bytes in Wasm memory of a target purse
                 // args(4) = pointer to array of bytes in Wasm memory of an amount
                 // args(5) = length of array of bytes in Wasm memory of an amount
-                let (source_ptr, source_size, target_ptr, target_size, amount_ptr, amount_size) =
-                    Args::parse(args)?;
+                let (source_ptr, source_size, target_ptr, target_size, amount_ptr, amount_size): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context
+                        .host_function_costs()
+                        .transfer_from_purse_to_purse,
+                    source_size
+                        .saturating_add(target_size)
+                        .saturating_add(amount_size),
+                )?;
                 let ret = self.transfer_from_purse_to_purse(
                     source_ptr,
                     source_size,
@@ -405,6 +551,10 @@ where
                 // args(1) = length of purse
                 // args(2) = pointer to output size (output)
                 let (ptr, ptr_size, output_size_ptr): (_, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_balance,
+                    ptr_size,
+                )?;
                 let ret = self.get_balance_host_buffer(ptr, ptr_size as usize, output_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -412,6 +562,7 @@ where
             FunctionIndex::GetPhaseIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(self.context.host_function_costs().get_phase, 0)?;
                 self.get_phase(dest_ptr)?;
                 Ok(None)
             }
@@ -421,7 +572,12 @@ where
                 // args(1) = size of name in Wasm memory
                 // args(2) = pointer to key in Wasm memory
                 // args(3) = size of key
-                let (name_ptr, name_size, key_ptr, key_size) = Args::parse(args)?;
+                let (name_ptr, name_size, key_ptr, key_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().upgrade_contract_at_uref,
+                    name_size.saturating_add(key_size),
+                )?;
                 let ret = self.upgrade_contract_at_uref(name_ptr, name_size, key_ptr, key_size)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -431,6 +587,10 @@ where
                 // args(1) = dest pointer for storing serialized result
                 // args(2) = dest pointer size
                 let (system_contract_index, dest_ptr, dest_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_system_contract,
+                    0,
+                )?;
                 let ret = self.get_system_contract(system_contract_index, dest_ptr, dest_size)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -438,6 +598,10 @@ where
             FunctionIndex::GetMainPurseIndex => {
                 // args(0) = pointer to Wasm memory where to write.
                 let dest_ptr = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().get_main_purse,
+                    0,
+                )?;
                 self.get_main_purse(dest_ptr)?;
                 Ok(None)
             }
@@ -445,9 +609,283 @@ where
             FunctionIndex::ReadHostBufferIndex => {
                 // args(0) = pointer to Wasm memory where to write size.
                 let (dest_ptr, dest_size, bytes_written_ptr): (_, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().read_host_buffer,
+                    dest_size,
+                )?;
                 let ret = self.read_host_buffer(dest_ptr, dest_size as usize, bytes_written_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to event name in Wasm memory
+                // args(1) = size of event name
+                // args(2) = pointer to event value in Wasm memory
+                // args(3) = size of event value
+                let (name_ptr, name_size, value_ptr, value_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().emit_event,
+                    name_size.saturating_add(value_size),
+                )?;
+                self.emit_event(name_ptr, name_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::Blake2bIndex => {
+                // args(0) = pointer to input bytes in Wasm memory
+                // args(1) = size of input bytes
+                // args(2) = pointer to output buffer in Wasm memory
+                // args(3) = size of output buffer
+                let (in_ptr, in_size, out_ptr, out_size): (u32, u32, u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().blake2b,
+                    in_size,
+                )?;
+                let ret = self.blake2b(in_ptr, in_size, out_ptr, out_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifySignatureIndex => {
+                // args(0) = pointer to message bytes in Wasm memory
+                // args(1) = size of message bytes
+                // args(2) = pointer to signature bytes in Wasm memory
+                // args(3) = size of signature bytes
+                // args(4) = pointer to serialized public key in Wasm memory
+                // args(5) = size of serialized public key
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                ): (u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().verify_signature,
+                    message_size,
+                )?;
+                let is_valid = self.verify_signature(
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(is_valid))))
+            }
+
+            FunctionIndex::CallEntryPointIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to result size (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    result_size_ptr,
+                ): (_, u32, _, u32, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().call_entry_point,
+                    key_size
+                        .saturating_add(entry_point_size)
+                        .saturating_add(args_size),
+                )?;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+
+                let ret = self.call_contract_host_buffer(
+                    key_contract,
+                    None,
+                    &entry_point,
+                    args_bytes,
+                    result_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::StoreEntryPointsIndex => {
+                // args(0) = pointer to serialized entry point names in Wasm memory
+                // args(1) = size of the names
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the function bodies
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to a Wasm memory where we will save
+                //           uref address of the new contract
+                let (names_ptr, names_size, urefs_ptr, urefs_size, hash_ptr): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().store_entry_points,
+                    names_size.saturating_add(urefs_size),
+                )?;
+                let fn_bytes = self.get_functions_by_names(names_ptr, names_size)?;
+                let uref_bytes = self
+                    .memory
+                    .get(urefs_ptr, urefs_size as usize)
+                    .map_err(Error::Interpreter)?;
+                let urefs = bytesrepr::deserialize(uref_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_function(fn_bytes, urefs)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::StoreEntryPointsAtHashIndex => {
+                // args(0) = pointer to serialized entry point names in Wasm memory
+                // args(1) = size of the names
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the function bodies
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to a Wasm memory where we will save
+                //           hash of the new contract
+                let (names_ptr, names_size, urefs_ptr, urefs_size, hash_ptr): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context
+                        .host_function_costs()
+                        .store_entry_points_at_hash,
+                    names_size.saturating_add(urefs_size),
+                )?;
+                let fn_bytes = self.get_functions_by_names(names_ptr, names_size)?;
+                let uref_bytes = self
+                    .memory
+                    .get(urefs_ptr, urefs_size as usize)
+                    .map_err(Error::Interpreter)?;
+                let urefs = bytesrepr::deserialize(uref_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_function_at_hash(fn_bytes, urefs)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::CreateContractPackageAtURefIndex => {
+                // args(0) = pointer to key destination in Wasm memory
+                let key_ptr = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context
+                        .host_function_costs()
+                        .create_contract_package_at_uref,
+                    0,
+                )?;
+                self.create_contract_package_at_uref(key_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::AddContractVersionIndex => {
+                // args(0) = pointer to key of the contract package in Wasm memory
+                // args(1) = size of the package key
+                // args(2) = pointer to key of the contract to add in Wasm memory
+                // args(3) = size of the contract key
+                // args(4) = pointer to a Wasm memory where we will save the new version number
+                let (
+                    package_key_ptr,
+                    package_key_size,
+                    contract_key_ptr,
+                    contract_key_size,
+                    version_ptr,
+                ): (u32, u32, u32, u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().add_contract_version,
+                    package_key_size.saturating_add(contract_key_size),
+                )?;
+                self.add_contract_version(
+                    package_key_ptr,
+                    package_key_size,
+                    contract_key_ptr,
+                    contract_key_size,
+                    version_ptr,
+                )?;
+                Ok(None)
+            }
+
+            FunctionIndex::SetContractVersionEnabledIndex => {
+                // args(0) = pointer to key of the contract package in Wasm memory
+                // args(1) = size of the package key
+                // args(2) = version number
+                // args(3) = 1 to enable the version, 0 to disable it
+                let (package_key_ptr, package_key_size, version, is_enabled): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context
+                        .host_function_costs()
+                        .set_contract_version_enabled,
+                    package_key_size,
+                )?;
+                let package_key = self.key_from_mem(package_key_ptr, package_key_size)?;
+                let ret =
+                    self.set_contract_version_enabled(package_key, version, is_enabled != 0)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CallVersionedContractIndex => {
+                // args(0) = pointer to key of the contract package in global state
+                // args(1) = size of key
+                // args(2) = version number
+                // args(3) = pointer to entry point name in Wasm memory
+                // args(4) = size of entry point name
+                // args(5) = pointer to function arguments in Wasm memory
+                // args(6) = size of arguments
+                // args(7) = pointer to result size (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    version,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    result_size_ptr,
+                ): (_, u32, _, _, u32, _, u32, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().call_versioned_contract,
+                    key_size
+                        .saturating_add(entry_point_size)
+                        .saturating_add(args_size),
+                )?;
+
+                let package_key: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+
+                let ret = self.call_contract_host_buffer(
+                    package_key,
+                    Some(version),
+                    &entry_point,
+                    args_bytes,
+                    result_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::RandomBytesIndex => {
+                // args(0) = pointer to output buffer in Wasm memory
+                // args(1) = size of output buffer
+                let (out_ptr, out_size): (u32, u32) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    self.context.host_function_costs().random_bytes,
+                    out_size,
+                )?;
+                let ret = self.random_bytes(out_ptr, out_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
         }
     }
 }
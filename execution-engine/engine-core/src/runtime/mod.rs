@@ -11,14 +11,23 @@ use std::{
     iter::IntoIterator,
 };
 
+use ed25519_dalek::Verifier;
 use itertools::Itertools;
 use parity_wasm::elements::Module;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use ::mint::Mint;
 use contract::args_parser::ArgsParser;
-use engine_shared::{account::Account, contract::Contract, gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    account::Account,
+    contract::Contract,
+    contract_package::ContractPackage,
+    gas::Gas,
+    newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH},
+    stored_value::StoredValue,
+};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::host_function_costs::HostFunctionCost;
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
@@ -27,11 +36,14 @@ use types::{
     system_contract_errors,
     system_contract_errors::mint,
     AccessRights, ApiError, CLType, CLTyped, CLValue, Key, ProtocolVersion, SystemContractType,
-    TransferResult, TransferredTo, URef, U128, U256, U512,
+    TransferResult, TransferredTo, URef, RANDOM_BYTES_COUNT, U128, U256, U512,
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+    engine_state::{
+        executable_deploy_item::DEFAULT_ENTRY_POINT_NAME, module_cache::ModuleCache,
+        system_contract_cache::SystemContractCache, EngineConfig,
+    },
     execution::{Error, MINT_NAME, POS_NAME},
     resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
     runtime_context::RuntimeContext,
@@ -40,6 +52,7 @@ use crate::{
 
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
     config: EngineConfig,
     memory: MemoryRef,
     module: Module,
@@ -63,12 +76,66 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
     main_export.push_str("call");
 }
 
+/// Makes the function exported as `entry_point` the one which wasmi invokes as `call`.
+///
+/// Contracts stored with several entry points keep their original export names, so any existing
+/// `call` export is dropped in favour of the requested one.
+pub fn select_entry_point(module: &mut Module, entry_point: &str) -> Result<(), Error> {
+    let has_entry_point = module
+        .export_section()
+        .map(|export_section| {
+            export_section
+                .entries()
+                .iter()
+                .any(|export_entry| export_entry.field() == entry_point)
+        })
+        .unwrap_or_default();
+
+    if !has_entry_point {
+        return Err(Error::NoSuchEntryPoint(entry_point.to_string()));
+    }
+
+    if entry_point != DEFAULT_ENTRY_POINT_NAME {
+        if let Some(export_section) = module.export_section_mut() {
+            export_section
+                .entries_mut()
+                .retain(|export_entry| export_entry.field() != DEFAULT_ENTRY_POINT_NAME);
+        }
+        rename_export_to_call(module, entry_point.to_string());
+    }
+
+    Ok(())
+}
+
+/// Returns the key of the contract to be called from `contract_package`, which is stored under
+/// `package_key`: the given `version` if one is specified, otherwise the latest enabled version.
+pub fn resolve_contract_version(
+    contract_package: &ContractPackage,
+    package_key: Key,
+    version: Option<u32>,
+) -> Result<Key, Error> {
+    match version {
+        Some(version) => match contract_package.get_version(version) {
+            Some(contract_version) if contract_version.is_enabled() => {
+                Ok(contract_version.contract_key())
+            }
+            Some(_) => Err(Error::DisabledContractVersion(version)),
+            None => Err(Error::InvalidContractVersion(version)),
+        },
+        None => contract_package
+            .current_version()
+            .map(|(_, contract_version)| contract_version.contract_key())
+            .ok_or(Error::NoEnabledContractVersion(package_key)),
+    }
+}
+
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
+    max_memory_pages: u32,
 ) -> Result<(ModuleRef, MemoryRef), Error> {
     let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
-    let resolver = create_module_resolver(protocol_version)?;
+    let resolver = create_module_resolver(protocol_version, max_memory_pages)?;
     let mut imports = ImportsBuilder::new();
     imports.push_resolver("env", &resolver);
     let not_started_module = ModuleInstance::new(&module, &imports)?;
@@ -1347,6 +1414,7 @@ where
     pub fn new(
         config: EngineConfig,
         system_contract_cache: SystemContractCache,
+        module_cache: ModuleCache,
         memory: MemoryRef,
         module: Module,
         context: RuntimeContext<'a, R>,
@@ -1354,6 +1422,7 @@ where
         Runtime {
             config,
             system_contract_cache,
+            module_cache,
             memory,
             module,
             host_buffer: None,
@@ -1403,6 +1472,39 @@ where
         }
     }
 
+    /// Charges for the net number of bytes added to global state since the context's storage
+    /// size delta was `storage_size_before`, returning the number of bytes charged for.
+    pub fn charge_storage(&mut self, storage_size_before: i64) -> Result<u64, Error> {
+        let storage_size_after = self.context.storage_size_delta()?;
+        let storage_bytes = cmp::max(storage_size_after - storage_size_before, 0) as u64;
+        let cost = Gas::new(self.context.storage_costs().calculate(storage_bytes));
+        if self.charge_gas(cost) {
+            Ok(storage_bytes)
+        } else {
+            Err(Error::GasLimit)
+        }
+    }
+
+    /// Charges for a call to a host function which passes `byte_count` bytes in Wasm memory.
+    fn charge_host_function_call(
+        &mut self,
+        cost: HostFunctionCost,
+        byte_count: u32,
+    ) -> Result<(), Trap> {
+        self.gas(Gas::new(cost.calculate(byte_count).into()))
+    }
+
+    /// Charges the per-byte part of `cost` for the value a read left in the host buffer.
+    fn charge_host_buffer_bytes(&mut self, cost: HostFunctionCost) -> Result<(), Trap> {
+        let value_size = self
+            .host_buffer
+            .as_ref()
+            .map(|cl_value| cl_value.inner_bytes().len())
+            .unwrap_or_default();
+        let value_size = u32::try_from(value_size).unwrap_or(u32::max_value());
+        self.charge_host_function_call(HostFunctionCost::new(0, cost.per_byte), value_size)
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -1455,6 +1557,37 @@ where
         }
     }
 
+    /// Returns the serialized module with only the functions exported under `names` callable.
+    /// Unlike [`Runtime::get_function_by_name`], the exports keep their names, so that each can be
+    /// invoked as an entry point of the stored contract.
+    fn get_functions_by_names(&mut self, names_ptr: u32, names_size: u32) -> Result<Vec<u8>, Trap> {
+        let names_bytes = self.bytes_from_mem(names_ptr, names_size as usize)?;
+        let names: Vec<String> = bytesrepr::deserialize(names_bytes).map_err(Error::BytesRepr)?;
+
+        let export_names: HashSet<&str> = self
+            .module
+            .export_section()
+            .map(|export_section| {
+                export_section
+                    .entries()
+                    .iter()
+                    .map(|export_entry| export_entry.field())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(name) = names
+            .iter()
+            .find(|name| !export_names.contains(name.as_str()))
+        {
+            return Err(Error::FunctionNotFound(name.clone()).into());
+        }
+
+        let mut module = self.module.clone();
+        pwasm_utils::optimize(&mut module, names.iter().map(String::as_str).collect()).unwrap();
+        parity_wasm::serialize(module).map_err(|e| Error::ParityWasm(e).into())
+    }
+
     fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Trap> {
         let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
         let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
@@ -1567,6 +1700,82 @@ where
         self.context.put_key(name, key).map_err(Into::into)
     }
 
+    /// Records an event with the name and value held in the Wasm memory.
+    fn emit_event(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.context.emit_event(name, value);
+        Ok(())
+    }
+
+    /// Writes the blake2b-256 hash of the input bytes into the first `BLAKE2B_DIGEST_LENGTH` bytes
+    /// of the output buffer, which must be at least that long.
+    fn blake2b(
+        &mut self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if (out_size as usize) < BLAKE2B_DIGEST_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let input = self.bytes_from_mem(in_ptr, in_size as usize)?;
+        let digest = Blake2bHash::new(&input);
+        self.memory
+            .set(out_ptr, &digest.value())
+            .map_err(Error::Interpreter)?;
+        Ok(Ok(()))
+    }
+
+    /// Fills the first `RANDOM_BYTES_COUNT` bytes of the output buffer in the Wasm memory with the
+    /// next bytes of the deploy's random stream.  The buffer must be at least that long.
+    fn random_bytes(&mut self, out_ptr: u32, out_size: u32) -> Result<Result<(), ApiError>, Trap> {
+        if (out_size as usize) < RANDOM_BYTES_COUNT {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let mut random_bytes = [0u8; RANDOM_BYTES_COUNT];
+        self.context.random_bytes(&mut random_bytes);
+        self.memory
+            .set(out_ptr, &random_bytes)
+            .map_err(Error::Interpreter)?;
+        Ok(Ok(()))
+    }
+
+    /// Checks whether the signature held in the Wasm memory is a valid ed25519 signature of the
+    /// message by the given public key.  Malformed signatures are simply reported as invalid.
+    fn verify_signature(
+        &mut self,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        public_key_ptr: u32,
+        public_key_size: u32,
+    ) -> Result<bool, Trap> {
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature_bytes = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        let public_key_bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        let public_key: PublicKey =
+            bytesrepr::deserialize(public_key_bytes).map_err(Error::BytesRepr)?;
+
+        let verifying_key = match ed25519_dalek::PublicKey::from_bytes(public_key.as_bytes()) {
+            Ok(verifying_key) => verifying_key,
+            Err(_) => return Ok(false),
+        };
+        let signature = match ed25519_dalek::Signature::from_bytes(&signature_bytes) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+        Ok(verifying_key.verify(&message, &signature).is_ok())
+    }
+
     fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         self.context.remove_key(&name)?;
@@ -1710,6 +1919,7 @@ where
         let gas_counter = self.context.gas_counter();
         let fn_store_id = self.context.fn_store_id();
         let address_generator = self.context.address_generator();
+        let random_generator = self.context.random_generator();
         let correlation_id = self.context.correlation_id();
         let phase = self.context.phase();
         let protocol_data = self.context.protocol_data();
@@ -1728,14 +1938,22 @@ where
             gas_counter,
             fn_store_id,
             address_generator,
+            random_generator,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            self.context.is_read_only(),
         );
 
         let method_name: String = Self::get_argument(&args, 0)?;
 
+        // The host-side mint can't surface a failed write, so reject anything but a balance query
+        // up front in a read-only context.
+        if self.context.is_read_only() && method_name != METHOD_BALANCE {
+            return Err(Error::ReadOnlyWrite(base_key));
+        }
+
         let ret: CLValue = match method_name.as_str() {
             // Type: `fn mint(amount: U512) -> Result<URef, Error>`
             METHOD_MINT => {
@@ -1784,6 +2002,9 @@ where
         const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
         const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
         const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+        const METHOD_SLASH: &str = "slash";
+        const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+        const METHOD_UNBOND_PAYOUT: &str = "unbond_payout";
 
         let state = self.context.state();
         let access_rights = {
@@ -1802,6 +2023,7 @@ where
         let gas_counter = self.context.gas_counter();
         let fn_store_id = self.context.fn_store_id();
         let address_generator = self.context.address_generator();
+        let random_generator = self.context.random_generator();
         let correlation_id = self.context.correlation_id();
         let phase = self.context.phase();
         let protocol_data = self.context.protocol_data();
@@ -1820,15 +2042,18 @@ where
             gas_counter,
             fn_store_id,
             address_generator,
+            random_generator,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            self.context.is_read_only(),
         );
 
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            ModuleCache::clone(&self.module_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1836,6 +2061,14 @@ where
 
         let method_name: String = Self::get_argument(&args, 0)?;
 
+        // As with the mint, only the purse getters may run in a read-only context.
+        if self.context.is_read_only()
+            && method_name != METHOD_GET_PAYMENT_PURSE
+            && method_name != METHOD_GET_REFUND_PURSE
+        {
+            return Err(Error::ReadOnlyWrite(base_key));
+        }
+
         let ret: CLValue = match method_name.as_str() {
             METHOD_BOND => {
                 if self.config.highway() {
@@ -1886,6 +2119,23 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_SLASH => {
+                let slashes: BTreeMap<PublicKey, U512> = Self::get_argument(&args, 1)?;
+                runtime.slash(slashes).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_DISTRIBUTE_REWARDS => {
+                let rewards: BTreeMap<PublicKey, U512> = Self::get_argument(&args, 1)?;
+                runtime
+                    .distribute_rewards(rewards)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_UNBOND_PAYOUT => {
+                let era_height: u64 = Self::get_argument(&args, 1)?;
+                runtime.unbond_payout(era_height).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1905,15 +2155,62 @@ where
 
     /// Calls contract living under a `key`, with supplied `args`.
     pub fn call_contract(&mut self, key: Key, args_bytes: Vec<u8>) -> Result<CLValue, Error> {
-        let contract = match self.context.read_gs(&key)? {
-            Some(StoredValue::Contract(contract)) => contract,
-            Some(_) => {
+        self.call_entry_point(key, DEFAULT_ENTRY_POINT_NAME, args_bytes)
+    }
+
+    /// Calls the function exported as `entry_point` by the contract living under a `key`, with
+    /// supplied `args`.
+    pub fn call_entry_point(
+        &mut self,
+        key: Key,
+        entry_point: &str,
+        args_bytes: Vec<u8>,
+    ) -> Result<CLValue, Error> {
+        self.call_versioned_contract(key, None, entry_point, args_bytes)
+    }
+
+    /// Calls the function exported as `entry_point` by the contract living under a `key`, with
+    /// supplied `args`.
+    ///
+    /// If `key` holds a contract package, the contract called is the given `version` of the
+    /// package, or its latest enabled version if `version` is `None`.
+    pub fn call_versioned_contract(
+        &mut self,
+        key: Key,
+        version: Option<u32>,
+        entry_point: &str,
+        args_bytes: Vec<u8>,
+    ) -> Result<CLValue, Error> {
+        let (key, contract) = match (self.context.read_gs(&key)?, version) {
+            (Some(StoredValue::Contract(contract)), None) => (key, contract),
+            (Some(StoredValue::Contract(_)), Some(_)) => {
+                return Err(Error::FunctionNotFound(format!(
+                    "Value at {:?} is not a contract package",
+                    key
+                )))
+            }
+            (Some(StoredValue::ContractPackage(contract_package)), version) => {
+                let contract_key = resolve_contract_version(&contract_package, key, version)?;
+                // Holding the package key is what grants access to its versions, so the contract
+                // key itself needn't be known to the caller.
+                match self.context.read_gs_direct(&contract_key)? {
+                    Some(StoredValue::Contract(contract)) => (contract_key, contract),
+                    Some(_) => {
+                        return Err(Error::FunctionNotFound(format!(
+                            "Value at {:?} is not a contract",
+                            contract_key
+                        )))
+                    }
+                    None => return Err(Error::KeyNotFound(contract_key)),
+                }
+            }
+            (Some(_), _) => {
                 return Err(Error::FunctionNotFound(format!(
                     "Value at {:?} is not a contract",
                     key
                 )))
             }
-            None => return Err(Error::KeyNotFound(key)),
+            (None, _) => return Err(Error::KeyNotFound(key)),
         };
 
         // Check for major version compatibility before calling
@@ -1964,14 +2261,19 @@ where
             _ => None,
         };
 
-        let module = match maybe_module {
+        let mut module = match maybe_module {
             Some(module) => module,
-            None => parity_wasm::deserialize_buffer(contract.bytes())?,
+            None => self.module_cache.get_or_load(key, current_version, || {
+                parity_wasm::deserialize_buffer(contract.bytes())
+            })?,
         };
+        select_entry_point(&mut module, entry_point)?;
 
         let mut named_keys = contract.take_named_keys();
 
-        let (instance, memory) = instance_and_memory(module.clone(), contract_version)?;
+        let max_memory_pages = self.context.protocol_data().wasm_limits().max_memory_pages;
+        let (instance, memory) =
+            instance_and_memory(module.clone(), contract_version, max_memory_pages)?;
 
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -1983,6 +2285,8 @@ where
 
         let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
+        let module_cache = ModuleCache::clone(&self.module_cache);
+
         let config = self.config;
 
         let host_buffer = None;
@@ -2001,14 +2305,17 @@ where
             self.context.gas_counter(),
             self.context.fn_store_id(),
             self.context.address_generator(),
+            self.context.random_generator(),
             contract_version,
             self.context.correlation_id(),
             self.context.phase(),
             self.context.protocol_data(),
+            self.context.is_read_only(),
         );
 
         let mut runtime = Runtime {
             system_contract_cache,
+            module_cache,
             config,
             memory,
             module,
@@ -2023,6 +2330,10 @@ where
         // counter from there to our counter
         self.context.set_gas_counter(runtime.context.gas_counter());
 
+        // Likewise, events emitted by the sub-call belong to our context.  If the sub-call failed,
+        // the error is propagated and the execution's events are discarded along with its effects.
+        self.context.extend_events(runtime.context.take_events());
+
         let error = match result {
             Err(error) => error,
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
@@ -2065,6 +2376,8 @@ where
     fn call_contract_host_buffer(
         &mut self,
         key: Key,
+        version: Option<u32>,
+        entry_point: &str,
         args_bytes: Vec<u8>,
         result_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
@@ -2073,7 +2386,7 @@ where
             return Ok(Err(ApiError::HostBufferFull));
         }
 
-        let result = self.call_contract(key, args_bytes)?;
+        let result = self.call_versioned_contract(key, version, entry_point, args_bytes)?;
         let result_size = result.inner_bytes().len() as u32; // considered to be safe
 
         // leave the host buffer set to `None` if there's nothing to write there
@@ -2464,6 +2777,19 @@ where
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
 
+    /// Calls the "mint" method on the mint contract at the given mint
+    /// contract key, creating a new purse holding `amount` motes.
+    fn mint_purse(&mut self, mint_contract_key: Key, amount: U512) -> Result<URef, Error> {
+        let args_bytes = {
+            let args = ("mint", amount);
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result: Result<URef, mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
     /// Creates a new account at a given public key, transferring a given amount
     /// of motes from the given source purse to the new account's purse.
     fn transfer_to_new_account(
@@ -2721,6 +3047,55 @@ where
         }
     }
 
+    /// Creates an empty contract package under a new `URef` and writes the key to the Wasm memory
+    /// at `key_ptr`.
+    fn create_contract_package_at_uref(&mut self, key_ptr: u32) -> Result<(), Trap> {
+        let key = self
+            .context
+            .new_uref(StoredValue::ContractPackage(ContractPackage::default()))?;
+        self.memory
+            .set(key_ptr, &key.into_bytes().map_err(Error::BytesRepr)?)
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// If the contract package key is in named_keys with AccessRights::Write, appends the contract
+    /// under the given key as its newest version and writes the version number at `version_ptr`.
+    fn add_contract_version(
+        &mut self,
+        package_key_ptr: u32,
+        package_key_size: u32,
+        contract_key_ptr: u32,
+        contract_key_size: u32,
+        version_ptr: u32,
+    ) -> Result<(), Trap> {
+        let package_key = self.key_from_mem(package_key_ptr, package_key_size)?;
+        let contract_key = self.key_from_mem(contract_key_ptr, contract_key_size)?;
+        let version = self
+            .context
+            .add_contract_version(package_key, contract_key)?;
+        self.memory
+            .set(version_ptr, &version.to_le_bytes()) // Wasm is little-endian
+            .map_err(|e| Error::Interpreter(e).into())
+    }
+
+    /// If the contract package key is in named_keys with AccessRights::Write, enables or disables
+    /// the given version of the package.
+    fn set_contract_version_enabled(
+        &mut self,
+        package_key: Key,
+        version: u32,
+        is_enabled: bool,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if self
+            .context
+            .set_contract_version_enabled(package_key, version, is_enabled)?
+        {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(ApiError::InvalidContractVersion))
+        }
+    }
+
     fn get_system_contract(
         &mut self,
         system_contract_index: u32,
@@ -7,6 +7,7 @@ use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
     MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    BONDING_KEY, ERA_HEIGHT_KEY, UNBONDING_KEY,
 };
 use types::{
     account::PublicKey, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
@@ -15,9 +16,6 @@ use types::{
 
 use crate::{execution, runtime::Runtime};
 
-const BONDING_KEY: u8 = 1;
-const UNBONDING_KEY: u8 = 2;
-
 // TODO: Update MintProvider to better handle errors
 impl<'a, R> MintProvider for Runtime<'a, R>
 where
@@ -54,6 +52,11 @@ where
     fn balance(&mut self, purse: URef) -> Option<U512> {
         self.get_balance(purse).expect("should get balance")
     }
+
+    fn mint(&mut self, amount: U512) -> Result<URef, ()> {
+        let mint_contract_key = self.get_mint_contract_uref().into();
+        self.mint_purse(mint_contract_key, amount).map_err(|_| ())
+    }
 }
 
 // TODO: Update QueueProvider to better handle errors
@@ -93,6 +96,22 @@ where
             .write_ls(&key, value)
             .expect("should write local state")
     }
+
+    fn read_era_height(&mut self) -> Option<u64> {
+        let key = ERA_HEIGHT_KEY.to_bytes().expect("should serialize");
+        match self.context.read_ls(&key) {
+            Ok(Some(cl_value)) => Some(cl_value.into_t().expect("should convert")),
+            _ => None,
+        }
+    }
+
+    fn write_era_height(&mut self, era_height: u64) {
+        let key = ERA_HEIGHT_KEY.to_bytes().expect("should serialize");
+        let value = CLValue::from_t(era_height).expect("should convert");
+        self.context
+            .write_ls(&key, value)
+            .expect("should write local state")
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    mem,
     rc::Rc,
 };
 
@@ -12,10 +13,11 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, contract::Contract, gas::Gas, newtypes::CorrelationId,
-    stored_value::StoredValue,
+    account::Account, contract::Contract, contract_package::ContractPackage, gas::Gas,
+    newtypes::CorrelationId, stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::{host_function_costs::HostFunctionCosts, storage_costs::StorageCosts};
 use types::{
     account::{
         ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure,
@@ -27,8 +29,8 @@ use types::{
 };
 
 use crate::{
-    engine_state::{execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
-    execution::{AddressGenerator, Error},
+    engine_state::{event::Event, execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
+    execution::{AddressGenerator, Error, RandomGenerator},
     tracking_copy::{AddResult, TrackingCopy},
     Address,
 };
@@ -70,10 +72,15 @@ pub struct RuntimeContext<'a, R> {
     gas_counter: Gas,
     fn_store_id: u32,
     address_generator: Rc<RefCell<AddressGenerator>>,
+    random_generator: Rc<RefCell<RandomGenerator>>,
     protocol_version: ProtocolVersion,
     correlation_id: CorrelationId,
     phase: Phase,
     protocol_data: ProtocolData,
+    // Events emitted so far by this context and the contracts it called
+    events: Vec<Event>,
+    // Rejects every write to global state, as when calling a contract as a view
+    read_only: bool,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -96,10 +103,12 @@ where
         gas_counter: Gas,
         fn_store_id: u32,
         address_generator: Rc<RefCell<AddressGenerator>>,
+        random_generator: Rc<RefCell<RandomGenerator>>,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         phase: Phase,
         protocol_data: ProtocolData,
+        read_only: bool,
     ) -> Self {
         RuntimeContext {
             state,
@@ -115,10 +124,13 @@ where
             gas_counter,
             fn_store_id,
             address_generator,
+            random_generator,
             protocol_version,
             correlation_id,
             phase,
             protocol_data,
+            events: Vec::new(),
+            read_only,
         }
     }
 
@@ -153,7 +165,7 @@ where
 
         let contract_value = StoredValue::Contract(contract);
 
-        self.state.borrow_mut().write(key, contract_value);
+        self.write_unsafe(key, contract_value)?;
 
         Ok(())
     }
@@ -172,7 +184,7 @@ where
                 };
                 self.named_keys.remove(name);
                 let account_value = self.account_to_validated_value(account)?;
-                self.state.borrow_mut().write(public_key, account_value);
+                self.write_unsafe(public_key, account_value)?;
                 Ok(())
             }
             contract_uref @ Key::URef(_) => {
@@ -231,6 +243,15 @@ where
         Rc::clone(&self.address_generator)
     }
 
+    pub fn random_generator(&self) -> Rc<RefCell<RandomGenerator>> {
+        Rc::clone(&self.random_generator)
+    }
+
+    /// Fills `dest` with the next bytes of this deploy's random stream.
+    pub fn random_bytes(&mut self, dest: &mut [u8]) {
+        self.random_generator.borrow_mut().fill_bytes(dest)
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.state)
     }
@@ -276,6 +297,10 @@ where
         self.phase
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Generates new function address.
     /// Function address is deterministic. It is a hash of public key, nonce and
     /// `fn_store_id`, which is a counter that is being incremented after
@@ -302,6 +327,7 @@ where
             URef::new(addr, AccessRights::READ_ADD_WRITE)
         };
         let key = Key::URef(uref);
+        self.validate_not_read_only(&key)?;
         self.insert_uref(uref);
         self.write_gs(key, value)?;
         Ok(key)
@@ -347,10 +373,7 @@ where
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
-        self.state
-            .borrow_mut()
-            .write(key, StoredValue::CLValue(cl_value));
-        Ok(())
+        self.write_unsafe(key, StoredValue::CLValue(cl_value))
     }
 
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
@@ -397,7 +420,7 @@ where
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
-        self.state.borrow_mut().write(key, value);
+        self.write_unsafe(key, value)?;
         Ok(())
     }
 
@@ -417,7 +440,7 @@ where
         if let Key::Account(_) = key {
             self.validate_key(&key)?;
             let account_value = self.account_to_validated_value(account)?;
-            self.state.borrow_mut().write(key, account_value);
+            self.write_unsafe(key, account_value)?;
             Ok(())
         } else {
             panic!("Do not use this function for writing non-account keys")
@@ -438,7 +461,7 @@ where
         let new_hash = self.new_function_address()?;
         self.validate_value(&contract)?;
         let hash_key = Key::Hash(new_hash);
-        self.state.borrow_mut().write(hash_key, contract);
+        self.write_unsafe(hash_key, contract)?;
         Ok(new_hash)
     }
 
@@ -462,6 +485,25 @@ where
         self.state.borrow_mut().effect()
     }
 
+    /// Records an event emitted by the entity we are currently running.
+    pub fn emit_event(&mut self, name: String, value: CLValue) {
+        self.events.push(Event::new(self.base_key, name, value));
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Takes the events emitted so far, leaving none behind.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+
+    /// Appends events emitted by a contract called from this context.
+    pub fn extend_events(&mut self, events: Vec<Event>) {
+        self.events.extend(events)
+    }
+
     /// Validates whether keys used in the `value` are not forged.
     fn validate_value(&self, value: &StoredValue) -> Result<(), Error> {
         match value {
@@ -511,6 +553,12 @@ where
                 .named_keys()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            StoredValue::ContractPackage(contract_package) => contract_package
+                .versions()
+                .iter()
+                .try_for_each(|contract_version| {
+                    self.validate_key(&contract_version.contract_key())
+                }),
         }
     }
 
@@ -617,6 +665,22 @@ where
         }
     }
 
+    /// Writes `value` to `key` without validating either, unless this context is read-only.
+    fn write_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_not_read_only(&key)?;
+        self.state.borrow_mut().write(key, value);
+        Ok(())
+    }
+
+    /// Fails with [`Error::ReadOnlyWrite`] if this context is read-only.
+    fn validate_not_read_only(&self, key: &Key) -> Result<(), Error> {
+        if self.read_only {
+            Err(Error::ReadOnlyWrite(*key))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Tests whether writing to `key` is valid.
     pub fn is_writeable(&self, key: &Key) -> bool {
         match key {
@@ -645,6 +709,7 @@ where
     }
 
     fn add_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_not_read_only(&key)?;
         match self.state.borrow_mut().add(self.correlation_id, key, value) {
             Err(storage_error) => Err(storage_error.into()),
             Ok(AddResult::Success) => Ok(()),
@@ -689,7 +754,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -723,7 +788,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -761,7 +826,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -799,7 +864,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -817,14 +882,63 @@ where
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
 
-        self.state.borrow_mut().write(key, contract);
+        self.write_unsafe(key, contract)?;
         Ok(())
     }
 
+    /// Appends the contract under `contract_key` as the newest version of the contract package
+    /// under `package_key`, returning the new version number.
+    pub fn add_contract_version(
+        &mut self,
+        package_key: Key,
+        contract_key: Key,
+    ) -> Result<u32, Error> {
+        self.validate_writeable(&package_key)?;
+        let mut contract_package: ContractPackage = self.read_gs_typed(&package_key)?;
+        let _contract: Contract = self.read_gs_typed(&contract_key)?;
+
+        let version = contract_package.add_version(contract_key);
+        self.write_gs(package_key, StoredValue::ContractPackage(contract_package))?;
+        Ok(version)
+    }
+
+    /// Enables or disables the given version of the contract package under `package_key`.
+    /// Returns `false` if the package has no such version.
+    pub fn set_contract_version_enabled(
+        &mut self,
+        package_key: Key,
+        version: u32,
+        is_enabled: bool,
+    ) -> Result<bool, Error> {
+        self.validate_writeable(&package_key)?;
+        let mut contract_package: ContractPackage = self.read_gs_typed(&package_key)?;
+        if !contract_package.set_version_enabled(version, is_enabled) {
+            return Ok(false);
+        }
+        self.write_gs(package_key, StoredValue::ContractPackage(contract_package))?;
+        Ok(true)
+    }
+
     pub fn protocol_data(&self) -> ProtocolData {
         self.protocol_data
     }
 
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        self.protocol_data.host_function_costs()
+    }
+
+    pub fn storage_costs(&self) -> &StorageCosts {
+        self.protocol_data.storage_costs()
+    }
+
+    /// Returns the net number of bytes by which the writes made so far grow global state.
+    pub fn storage_size_delta(&self) -> Result<i64, Error> {
+        self.state
+            .borrow_mut()
+            .storage_size_delta(self.correlation_id)
+            .map_err(Into::into)
+    }
+
     /// Attenuates URef for a given account.
     ///
     /// If the account is system account, then given URef receives
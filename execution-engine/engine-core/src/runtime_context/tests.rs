@@ -29,8 +29,10 @@ use types::{
 
 use super::{attenuate_uref_for_account, Address, Error, RuntimeContext};
 use crate::{
-    engine_state::SYSTEM_ACCOUNT_ADDR, execution::AddressGenerator,
-    runtime::extract_access_rights_from_keys, tracking_copy::TrackingCopy,
+    engine_state::SYSTEM_ACCOUNT_ADDR,
+    execution::{AddressGenerator, RandomGenerator},
+    runtime::extract_access_rights_from_keys,
+    tracking_copy::TrackingCopy,
 };
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
@@ -127,10 +129,12 @@ fn mock_runtime_context<'a>(
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(&DEPLOY_HASH, PHASE))),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         Phase::Session,
         Default::default(),
+        false,
     )
 }
 
@@ -458,10 +462,12 @@ fn contract_key_addable_valid() {
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(&DEPLOY_HASH, PHASE))),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
         Default::default(),
+        false,
     );
 
     let uref_name = "NewURef".to_owned();
@@ -520,10 +526,12 @@ fn contract_key_addable_invalid() {
         Gas::default(),
         0,
         Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(&DEPLOY_HASH, PHASE))),
         ProtocolVersion::V1_0_0,
         CorrelationId::new(),
         PHASE,
         Default::default(),
+        false,
     );
 
     let uref_name = "NewURef".to_owned();
@@ -974,3 +982,51 @@ fn attenuate_uref_for_user_account() {
     let access_rights = attenuated_uref.access_rights();
     assert_eq!(access_rights, AccessRights::READ);
 }
+
+#[test]
+fn read_only_context_rejects_writes() {
+    let base_acc = PublicKey::ed25519_from([0u8; 32]);
+    let (key, account) = mock_account(base_acc);
+    let mut address_generator = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut address_generator, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let tc = mock_tc(key, account.clone());
+    let mut uref_map = BTreeMap::new();
+
+    let mut runtime_context = RuntimeContext::new(
+        Rc::new(RefCell::new(tc)),
+        &mut uref_map,
+        access_rights,
+        Vec::new(),
+        BTreeSet::new(),
+        &account,
+        key,
+        BlockTime::new(0),
+        [0u8; 32],
+        Gas::default(),
+        Gas::default(),
+        0,
+        Rc::new(RefCell::new(address_generator)),
+        Rc::new(RefCell::new(RandomGenerator::new(&DEPLOY_HASH, PHASE))),
+        ProtocolVersion::V1_0_0,
+        CorrelationId::new(),
+        PHASE,
+        Default::default(),
+        true,
+    );
+    let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    match runtime_context.write_gs(uref_key, value.clone()) {
+        Err(Error::ReadOnlyWrite(written)) => assert_eq!(written, uref_key),
+        other => panic!("expected ReadOnlyWrite, got {:?}", other),
+    }
+    match runtime_context.add_gs(uref_key, value.clone()) {
+        Err(Error::ReadOnlyWrite(added)) => assert_eq!(added, uref_key),
+        other => panic!("expected ReadOnlyWrite, got {:?}", other),
+    }
+    match runtime_context.new_uref(value) {
+        Err(Error::ReadOnlyWrite(_)) => {}
+        other => panic!("expected ReadOnlyWrite, got {:?}", other),
+    }
+    assert!(runtime_context.effect().transforms.is_empty());
+}
@@ -1,6 +1,11 @@
 use std::{collections::BTreeMap, mem};
 
-use engine_shared::{account::Account, contract::Contract, stored_value::StoredValue};
+use engine_shared::{
+    account::Account,
+    contract::Contract,
+    contract_package::{ContractPackage, ContractVersion},
+    stored_value::StoredValue,
+};
 use types::Key;
 
 /// Returns byte size of the element - both heap size and stack size.
@@ -50,6 +55,7 @@ impl ByteSize for StoredValue {
                 StoredValue::CLValue(cl_value) => cl_value.serialized_length(),
                 StoredValue::Account(account) => account.heap_size(),
                 StoredValue::Contract(contract) => contract.heap_size(),
+                StoredValue::ContractPackage(contract_package) => contract_package.heap_size(),
             }
     }
 }
@@ -80,6 +86,12 @@ impl HeapSizeOf for Contract {
     }
 }
 
+impl HeapSizeOf for ContractPackage {
+    fn heap_size(&self) -> usize {
+        self.versions().len() * mem::size_of::<ContractVersion>()
+    }
+}
+
 // NOTE: We're ignoring size of the tree's nodes.
 impl<K: HeapSizeOf, V: HeapSizeOf> HeapSizeOf for BTreeMap<K, V> {
     fn heap_size(&self) -> usize {
@@ -7,7 +7,7 @@ use engine_shared::{
 use engine_storage::global_state::StateReader;
 use types::{account::PublicKey, bytesrepr::ToBytes, CLValue, Key, URef, U512};
 
-use crate::{execution, tracking_copy::TrackingCopy};
+use crate::{execution, runtime, tracking_copy::TrackingCopy};
 
 pub trait TrackingCopyExt<R> {
     type Error;
@@ -34,7 +34,7 @@ pub trait TrackingCopyExt<R> {
         balance_key: Key,
     ) -> Result<Motes, Self::Error>;
 
-    /// Gets a contract by Key
+    /// Gets a contract by Key, resolving a contract package to its latest enabled version
     fn get_contract(
         &mut self,
         correlation_id: CorrelationId,
@@ -123,6 +123,10 @@ where
             .map_err(Into::into)?
         {
             Some(StoredValue::Contract(contract)) => Ok(contract),
+            Some(StoredValue::ContractPackage(contract_package)) => {
+                let contract_key = runtime::resolve_contract_version(&contract_package, key, None)?;
+                self.get_contract(correlation_id, contract_key)
+            }
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
                 "Contract".to_string(),
                 other.type_name(),
@@ -20,7 +20,10 @@ use engine_shared::{
     TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+use types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValueError, Key,
+};
 
 use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
 
@@ -147,6 +150,21 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
         self.reads_cached.get_refresh(key).map(|v| &*v)
     }
+
+    /// Gets value from `key` in the cache without refreshing its place in the Read cache.
+    fn peek(&self, key: &Key) -> Option<&StoredValue> {
+        self.muts_cached
+            .get(key)
+            .or_else(|| self.reads_cached.get(key))
+    }
+}
+
+/// Returns the number of bytes taken up in global state by `key` holding `maybe_value`.
+fn entry_size(key: &Key, maybe_value: Option<&StoredValue>) -> i64 {
+    match maybe_value {
+        Some(value) => (key.serialized_length() + value.serialized_length()) as i64,
+        None => 0,
+    }
 }
 
 pub struct TrackingCopy<R> {
@@ -154,6 +172,12 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    /// The net number of bytes by which the writes and adds made so far grow the state read
+    /// against, less the prior size of each key in `unsized_keys`.
+    storage_size_delta: i64,
+    /// Keys first written while their prior value wasn't cached.  Their prior sizes are
+    /// subtracted from `storage_size_delta` on the next call to `storage_size_delta()`.
+    unsized_keys: HashSet<Key>,
 }
 
 #[derive(Debug)]
@@ -186,6 +210,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                                                                  * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            storage_size_delta: 0,
+            unsized_keys: HashSet::new(),
         }
     }
 
@@ -242,6 +268,14 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        let prior_size = match self.cache.peek(&normalized_key) {
+            Some(prior_value) => entry_size(&normalized_key, Some(prior_value)),
+            None => {
+                self.unsized_keys.insert(normalized_key);
+                0
+            }
+        };
+        self.storage_size_delta += entry_size(&normalized_key, Some(&value)) - prior_size;
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
@@ -310,8 +344,11 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
             _ => return mismatch(),
         };
 
+        let prior_size = entry_size(&normalized_key, Some(&current_value));
         match transform.clone().apply(current_value) {
             Ok(new_value) => {
+                self.storage_size_delta +=
+                    entry_size(&normalized_key, Some(&new_value)) - prior_size;
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
                 self.fns.insert_add(normalized_key, transform);
@@ -328,6 +365,26 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
 
+    /// Returns the net number of bytes by which the values written or added via this
+    /// `TrackingCopy` grow the state it reads against.
+    ///
+    /// A newly created entry counts the serialized length of its key and value, while a modified
+    /// entry counts only the change in the serialized length of its value.  Lengths are measured
+    /// in their serialized form so the result does not depend on the platform.
+    ///
+    /// The delta is kept up to date as values are written or added.  Only the keys first written
+    /// since the previous call without their prior value being cached are read here, once each.
+    pub fn storage_size_delta(&mut self, correlation_id: CorrelationId) -> Result<i64, R::Error> {
+        let mut prior_sizes = 0i64;
+        for key in self.unsized_keys.iter() {
+            let prior_value = self.reader.read(correlation_id, key)?;
+            prior_sizes += entry_size(key, prior_value.as_ref());
+        }
+        self.storage_size_delta -= prior_sizes;
+        self.unsized_keys.clear();
+        Ok(self.storage_size_delta)
+    }
+
     /// Calling `query()` avoids calling into `self.cache`, so this will not return any values
     /// written or mutated in this `TrackingCopy` via previous calls to `write()` or `add()`, since
     /// these updates are only held in `self.cache`.
@@ -378,6 +435,15 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                     }
                 }
 
+                StoredValue::ContractPackage(contract_package) => {
+                    let msg_prefix = format!(
+                        "Query cannot continue as {:?} is not an account, contract nor key to \
+                        such.  Value found",
+                        contract_package
+                    );
+                    return Ok(query.into_not_found_result(&msg_prefix));
+                }
+
                 StoredValue::CLValue(cl_value) if cl_value.cl_type() == &CLType::Key => {
                     if let Ok(key) = cl_value.into_t::<Key>() {
                         query.current_key = key.normalize();
@@ -421,4 +487,29 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
             Ok(None)
         }
     }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        // The first `limit` keys are among the first `limit` keys of the reader and the cache.
+        let mut keys: Vec<(Vec<u8>, Key)> = self
+            .reader
+            .keys_with_prefix(correlation_id, prefix, start_after, limit)?
+            .into_iter()
+            .chain(self.cache.muts_cached.keys().cloned())
+            .map(|key| (key.to_bytes().expect("should serialize key"), key))
+            .filter(|(key_bytes, _)| key_bytes.starts_with(prefix))
+            .filter(|(key_bytes, _)| match start_after {
+                Some(start_after) => key_bytes.as_slice() > start_after,
+                None => true,
+            })
+            .collect();
+        keys.sort();
+        keys.dedup();
+        Ok(keys.into_iter().take(limit).map(|(_, key)| key).collect())
+    }
 }
@@ -13,6 +13,7 @@ use engine_shared::{
 use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
 use types::{
     account::{PublicKey, Weight, ED25519_LENGTH},
+    bytesrepr::ToBytes,
     gens::*,
     AccessRights, CLValue, Key, ProtocolVersion, URef,
 };
@@ -58,6 +59,16 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn keys_with_prefix(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+        _start_after: Option<&[u8]>,
+        _limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -143,6 +154,73 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_storage_size_delta() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    assert_eq!(tc.storage_size_delta(correlation_id).unwrap(), 0);
+
+    // overwriting an `i32` with a `String` grows the value by the difference in their lengths
+    let old_value = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let new_value = StoredValue::CLValue(CLValue::from_t(String::from("Hello, world!")).unwrap());
+    tc.write(k, new_value.clone());
+    let expected_delta =
+        new_value.serialized_length() as i64 - old_value.serialized_length() as i64;
+    assert_eq!(
+        tc.storage_size_delta(correlation_id).unwrap(),
+        expected_delta
+    );
+
+    // overwriting it back with an `i32` leaves the state unchanged in size
+    tc.write(k, old_value);
+    assert_eq!(tc.storage_size_delta(correlation_id).unwrap(), 0);
+}
+
+#[test]
+fn tracking_copy_storage_size_delta_of_new_key() {
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+    let k = Key::Hash([0u8; 32]);
+    let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    tc.write(k, value.clone());
+    let expected_delta = (k.serialized_length() + value.serialized_length()) as i64;
+    assert_eq!(
+        tc.storage_size_delta(correlation_id).unwrap(),
+        expected_delta
+    );
+}
+
+#[test]
+fn tracking_copy_storage_size_delta_reads_each_key_once() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+    let value = StoredValue::CLValue(CLValue::from_t(String::from("Hello, world!")).unwrap());
+
+    // the prior value of a key written without reading it is read on the first call only
+    tc.write(k, value.clone());
+    let delta = tc.storage_size_delta(correlation_id).unwrap();
+    tc.write(k, value.clone());
+    assert_eq!(tc.storage_size_delta(correlation_id).unwrap(), delta);
+    assert_eq!(counter.get(), 1);
+
+    // the prior value of a key read before being written is taken from the cache
+    let k2 = Key::Hash([1u8; 32]);
+    tc.get(correlation_id, &k2).unwrap();
+    tc.write(k2, value);
+    tc.storage_size_delta(correlation_id).unwrap();
+    assert_eq!(counter.get(), 2);
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -285,6 +363,66 @@ fn tracking_copy_aw() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_keys_with_prefix_include_cached_writes() {
+    let correlation_id = CorrelationId::new();
+    let value = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+    let stored_key = Key::Hash([2u8; 32]);
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &[(stored_key, value.clone())]).unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let written_key = Key::Hash([1u8; 32]);
+    let uref_key = Key::URef(URef::new([1u8; 32], AccessRights::READ));
+    tc.write(written_key, value.clone());
+    tc.write(uref_key, value);
+
+    let hash_prefix = [1u8];
+    let keys = (&tc)
+        .keys_with_prefix(correlation_id, &hash_prefix, None, usize::max_value())
+        .unwrap();
+    assert_eq!(keys, vec![written_key, stored_key]);
+
+    let mut all_keys = (&tc)
+        .keys_with_prefix(correlation_id, &[], None, usize::max_value())
+        .unwrap();
+    all_keys.sort();
+    let mut expected_keys = vec![written_key, stored_key, uref_key];
+    expected_keys.sort();
+    assert_eq!(all_keys, expected_keys);
+}
+
+#[test]
+fn tracking_copy_keys_with_prefix_pages_through_cached_writes() {
+    let correlation_id = CorrelationId::new();
+    let value = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+    let stored_keys = [Key::Hash([1u8; 32]), Key::Hash([3u8; 32])];
+    let pairs: Vec<(Key, StoredValue)> = stored_keys
+        .iter()
+        .map(|key| (*key, value.clone()))
+        .collect();
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let written_key = Key::Hash([2u8; 32]);
+    tc.write(written_key, value);
+
+    let hash_prefix = [1u8];
+    let first_page = (&tc)
+        .keys_with_prefix(correlation_id, &hash_prefix, None, 2)
+        .unwrap();
+    assert_eq!(first_page, vec![stored_keys[0], written_key]);
+
+    let page_token = written_key.to_bytes().unwrap();
+    let second_page = (&tc)
+        .keys_with_prefix(correlation_id, &hash_prefix, Some(&page_token), 2)
+        .unwrap();
+    assert_eq!(second_page, vec![stored_keys[1]]);
+}
+
 proptest! {
     #[test]
     fn query_empty_path(k in key_arb(), missing_key in key_arb(), v in stored_value_arb()) {
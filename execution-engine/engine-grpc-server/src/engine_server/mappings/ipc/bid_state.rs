@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::bid_state::{BidState, BidStateRequest, QueuedChange};
+use types::{account::PublicKey, U512};
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::BidStateRequest> for BidStateRequest {
+    type Error = MappingError;
+
+    fn try_from(mut pb_bid_state_request: ipc::BidStateRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash = parse_state_hash(pb_bid_state_request.get_parent_state_hash())?;
+
+        let protocol_version = pb_bid_state_request.take_protocol_version().into();
+
+        Ok(BidStateRequest::new(pre_state_hash, protocol_version))
+    }
+}
+
+impl From<(PublicKey, U512)> for ipc::BidState_Bid {
+    fn from((validator, stake): (PublicKey, U512)) -> Self {
+        let mut pb_bid = ipc::BidState_Bid::new();
+        pb_bid.set_id(validator.as_bytes().to_vec());
+        pb_bid.set_value(stake.into());
+        pb_bid
+    }
+}
+
+impl From<QueuedChange> for ipc::BidState_QueueEntry {
+    fn from(queued_change: QueuedChange) -> Self {
+        let mut pb_entry = ipc::BidState_QueueEntry::new();
+        pb_entry.set_id(queued_change.validator().as_bytes().to_vec());
+        pb_entry.set_value(queued_change.amount().into());
+        pb_entry.set_timestamp(queued_change.timestamp().into());
+        pb_entry.set_era_height(queued_change.era_height());
+        pb_entry
+    }
+}
+
+impl From<BidState> for ipc::BidState {
+    fn from(bid_state: BidState) -> Self {
+        let mut pb_bid_state = ipc::BidState::new();
+
+        let bids = bid_state
+            .bids()
+            .iter()
+            .map(|(validator, stake)| (*validator, *stake).into())
+            .collect();
+        pb_bid_state.set_bids(bids);
+
+        let bonding_queue = bid_state
+            .bonding_queue()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        pb_bid_state.set_bonding_queue(bonding_queue);
+
+        let unbonding_queue = bid_state
+            .unbonding_queue()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        pb_bid_state.set_unbonding_queue(unbonding_queue);
+
+        pb_bid_state
+    }
+}
@@ -7,18 +7,38 @@ use engine_core::{
 };
 use engine_shared::gas::Gas;
 
-use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
+use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult, Event};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
-        match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+        let storage_bytes = execution_result.storage_bytes();
+        let mut pb_deploy_result: DeployResult = match execution_result {
+            ExecutionResult::Success {
+                effect,
+                cost,
+                events,
+                ..
+            } => {
+                let mut pb_deploy_result = detail::execution_success(effect, cost);
+                let pb_events: Vec<Event> = events.into_iter().map(Into::into).collect();
+                pb_deploy_result
+                    .mut_execution_result()
+                    .set_events(pb_events.into());
+                pb_deploy_result
+            }
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                ..
             } => (error, effect, cost).into(),
+        };
+        if pb_deploy_result.has_execution_result() {
+            pb_deploy_result
+                .mut_execution_result()
+                .set_storage_bytes(storage_bytes);
         }
+        pb_deploy_result
     }
 }
 
@@ -32,6 +52,8 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::InvalidPublicKeyLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
             | error @ EngineStateError::InvalidUpgradeConfig
+            | error @ EngineStateError::InvalidWasmLimits(_)
+            | error @ EngineStateError::DeployReplayed
             | error @ EngineStateError::WasmPreprocessing(_)
             | error @ EngineStateError::WasmSerialization(_)
             | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
@@ -48,7 +70,11 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::Deploy
             | error @ EngineStateError::Finalization
             | error @ EngineStateError::Serialization(_)
-            | error @ EngineStateError::Mint(_) => detail::execution_error(error, effect, cost),
+            | error @ EngineStateError::Mint(_)
+            | error @ EngineStateError::ExpectedStoredContract
+            | error @ EngineStateError::ViewWrite(_) => {
+                detail::execution_error(error, effect, cost)
+            }
             EngineStateError::Exec(exec_error) => (exec_error, effect, cost).into(),
         }
     }
@@ -163,8 +189,9 @@ mod detail {
 mod tests {
     use std::convert::TryInto;
 
+    use engine_core::engine_state::event::Event as EngineEvent;
     use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-    use types::{bytesrepr::Error as BytesReprError, AccessRights, Key, URef, U512};
+    use types::{bytesrepr::Error as BytesReprError, AccessRights, CLValue, Key, URef, U512};
 
     use super::*;
 
@@ -180,15 +207,31 @@ mod tests {
         };
         let execution_effect = ExecutionEffect::new(AdditiveMap::new(), input_transforms.clone());
         let cost = Gas::new(U512::from(123));
+        let storage_bytes = 42;
+        let event = EngineEvent::new(
+            Key::Hash([2u8; 32]),
+            "transfer".to_string(),
+            CLValue::from_t(10u64).unwrap(),
+        );
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            storage_bytes,
+            events: vec![event.clone()],
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
         let mut success = ipc_deploy_result.take_execution_result();
         let execution_cost: U512 = success.take_cost().try_into().expect("should map to U512");
         assert_eq!(execution_cost, cost.value());
+        assert_eq!(success.get_storage_bytes(), storage_bytes);
+        let ipc_events = success.take_events().into_vec();
+        assert_eq!(ipc_events.len(), 1);
+        let parsed_event: EngineEvent = ipc_events[0]
+            .clone()
+            .try_into()
+            .expect("should parse event");
+        assert_eq!(parsed_event, event);
 
         // Extract transform map from the IPC message and parse it back to the domain
         let ipc_transforms: AdditiveMap<Key, Transform> = {
@@ -208,6 +251,7 @@ mod tests {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            storage_bytes: 0,
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -251,6 +295,7 @@ mod tests {
             error: EngineStateError::Exec(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            storage_bytes: 0,
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
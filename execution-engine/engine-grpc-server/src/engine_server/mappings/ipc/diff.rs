@@ -0,0 +1,38 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::diff::DiffRequest;
+use engine_storage::global_state::TrieDiff;
+use types::Key;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+    state,
+};
+
+impl TryFrom<ipc::DiffRequest> for DiffRequest {
+    type Error = MappingError;
+
+    fn try_from(diff_request: ipc::DiffRequest) -> Result<Self, Self::Error> {
+        let left_state_hash = parse_state_hash(diff_request.get_left_state_hash())?;
+        let right_state_hash = parse_state_hash(diff_request.get_right_state_hash())?;
+        Ok(DiffRequest::new(left_state_hash, right_state_hash))
+    }
+}
+
+impl From<TrieDiff<Key>> for ipc::StateDiff {
+    fn from(trie_diff: TrieDiff<Key>) -> Self {
+        let mut pb_state_diff = ipc::StateDiff::new();
+
+        let added: Vec<state::Key> = trie_diff.added.into_iter().map(Into::into).collect();
+        pb_state_diff.set_added(added.into());
+
+        let removed: Vec<state::Key> = trie_diff.removed.into_iter().map(Into::into).collect();
+        pb_state_diff.set_removed(removed.into());
+
+        let changed: Vec<state::Key> = trie_diff.changed.into_iter().map(Into::into).collect();
+        pb_state_diff.set_changed(changed.into());
+
+        pb_state_diff
+    }
+}
@@ -0,0 +1,49 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::distribute_rewards::DistributeRewardsRequest;
+use types::{account::PublicKey, U512};
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::DistributeRewardsRequest_ValidatorReward> for (PublicKey, U512) {
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_reward: ipc::DistributeRewardsRequest_ValidatorReward,
+    ) -> Result<Self, Self::Error> {
+        let public_key = PublicKey::ed25519_try_from(pb_reward.get_validator_id())
+            .map_err(|_| MappingError::invalid_public_key_length(pb_reward.validator_id.len()))?;
+
+        let amount = pb_reward.take_value().try_into()?;
+
+        Ok((public_key, amount))
+    }
+}
+
+impl TryFrom<ipc::DistributeRewardsRequest> for DistributeRewardsRequest {
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_distribute_rewards_request: ipc::DistributeRewardsRequest,
+    ) -> Result<Self, Self::Error> {
+        let pre_state_hash =
+            parse_state_hash(pb_distribute_rewards_request.get_parent_state_hash())?;
+
+        let protocol_version = pb_distribute_rewards_request.take_protocol_version().into();
+
+        let rewards = pb_distribute_rewards_request
+            .take_rewards()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<(PublicKey, U512)>, MappingError>>()?;
+
+        Ok(DistributeRewardsRequest::new(
+            pre_state_hash,
+            protocol_version,
+            rewards,
+        ))
+    }
+}
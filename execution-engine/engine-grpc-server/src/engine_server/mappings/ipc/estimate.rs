@@ -0,0 +1,29 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::estimate::EstimateRequest;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::EstimateRequest> for EstimateRequest {
+    type Error = MappingError;
+
+    fn try_from(mut estimate_request: ipc::EstimateRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_state_hash(estimate_request.get_state_hash())?;
+
+        let block_time = estimate_request.get_block_time();
+
+        let deploy_item = estimate_request.take_deploy().try_into()?;
+
+        let protocol_version = estimate_request.take_protocol_version().into();
+
+        Ok(EstimateRequest::new(
+            state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        ))
+    }
+}
@@ -0,0 +1,45 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::event::Event;
+
+use crate::engine_server::{ipc, mappings::ParsingError};
+
+impl From<Event> for ipc::Event {
+    fn from(event: Event) -> Self {
+        let (source, name, value) = event.destructure();
+        let mut pb_event = ipc::Event::new();
+        pb_event.set_source(source.into());
+        pb_event.set_name(name);
+        pb_event.set_value(value.into());
+        pb_event
+    }
+}
+
+impl TryFrom<ipc::Event> for Event {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_event: ipc::Event) -> Result<Self, Self::Error> {
+        let source = pb_event.take_source().try_into()?;
+        let name = pb_event.take_name();
+        let value = pb_event.take_value().try_into()?;
+        Ok(Event::new(source, name, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(source in gens::key_arb(), name in "\\PC*", value in gens::cl_value_arb()) {
+            let event = Event::new(source, name, value);
+            test_utils::protobuf_round_trip::<Event, ipc::Event>(event);
+        }
+    }
+}
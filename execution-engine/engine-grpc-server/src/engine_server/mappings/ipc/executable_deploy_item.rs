@@ -1,7 +1,18 @@
-use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use engine_core::engine_state::executable_deploy_item::{
+    ExecutableDeployItem, DEFAULT_ENTRY_POINT_NAME,
+};
 
 use crate::engine_server::ipc::{DeployPayload, DeployPayload_oneof_payload};
 
+/// An empty entry point name in the protobuf means the default entry point.
+fn entry_point_or_default(entry_point: String) -> String {
+    if entry_point.is_empty() {
+        DEFAULT_ENTRY_POINT_NAME.to_string()
+    } else {
+        entry_point
+    }
+}
+
 impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
     fn from(pb_deploy_payload: DeployPayload_oneof_payload) -> Self {
         match pb_deploy_payload {
@@ -14,18 +25,21 @@ impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
             DeployPayload_oneof_payload::stored_contract_hash(pb_stored_contract_hash) => {
                 ExecutableDeployItem::StoredContractByHash {
                     hash: pb_stored_contract_hash.hash,
+                    entry_point: entry_point_or_default(pb_stored_contract_hash.entry_point_name),
                     args: pb_stored_contract_hash.args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_name(pb_stored_contract_name) => {
                 ExecutableDeployItem::StoredContractByName {
                     name: pb_stored_contract_name.stored_contract_name,
+                    entry_point: entry_point_or_default(pb_stored_contract_name.entry_point_name),
                     args: pb_stored_contract_name.args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_uref(pb_stored_contract_uref) => {
                 ExecutableDeployItem::StoredContractByURef {
                     uref: pb_stored_contract_uref.uref,
+                    entry_point: entry_point_or_default(pb_stored_contract_uref.entry_point_name),
                     args: pb_stored_contract_uref.args,
                 }
             }
@@ -42,19 +56,34 @@ impl From<ExecutableDeployItem> for DeployPayload {
                 code.set_code(module_bytes);
                 code.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByHash { hash, args } => {
+            ExecutableDeployItem::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_hash();
                 inner.set_hash(hash);
+                inner.set_entry_point_name(entry_point);
                 inner.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByName { name, args } => {
+            ExecutableDeployItem::StoredContractByName {
+                name,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_name();
                 inner.set_stored_contract_name(name);
+                inner.set_entry_point_name(entry_point);
                 inner.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByURef { uref, args } => {
+            ExecutableDeployItem::StoredContractByURef {
+                uref,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_uref();
                 inner.set_uref(uref);
+                inner.set_entry_point_name(entry_point);
                 inner.set_args(args);
             }
         }
@@ -50,6 +50,18 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
     }
 }
 
+/// Maps the response to a request whose parent state hash is malformed, the only failure of the
+/// `ExecuteRequest` mapping, into a block execution response reporting the missing parent.
+impl From<ipc::ExecuteResponse> for ipc::ExecuteBlockResponse {
+    fn from(mut exec_response: ipc::ExecuteResponse) -> Self {
+        let mut commit_response = ipc::CommitResponse::new();
+        commit_response.set_missing_prestate(exec_response.take_missing_parent());
+        let mut exec_block_response = ipc::ExecuteBlockResponse::new();
+        exec_block_response.set_commit_response(commit_response);
+        exec_block_response
+    }
+}
+
 impl From<ExecuteRequest> for ipc::ExecuteRequest {
     fn from(req: ExecuteRequest) -> Self {
         let mut result = ipc::ExecuteRequest::new();
@@ -68,3 +80,24 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_malformed_parent_into_execute_block_response() {
+        let malformed_hash = vec![1u8; BLAKE2B_DIGEST_LENGTH - 1];
+        let mut pb_request = ipc::ExecuteRequest::new();
+        pb_request.set_parent_state_hash(malformed_hash.clone());
+
+        let exec_response = ExecuteRequest::try_from(pb_request)
+            .expect_err("should fail to map malformed parent state hash");
+        let exec_block_response: ipc::ExecuteBlockResponse = exec_response.into();
+
+        assert!(exec_block_response.get_deploy_results().is_empty());
+        let commit_response = exec_block_response.get_commit_response();
+        assert!(commit_response.has_missing_prestate());
+        assert_eq!(commit_response.get_missing_prestate().get_hash(), &malformed_hash[..]);
+    }
+}
@@ -1,6 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
+use engine_storage::protocol_data::DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS;
+use engine_wasm_prep::wasm_limits::WasmLimits;
 
 use crate::engine_server::{
     ipc::{ChainSpec_GenesisAccount, ChainSpec_GenesisConfig},
@@ -33,6 +35,20 @@ impl From<GenesisConfig> for ChainSpec_GenesisConfig {
             .mut_costs()
             .set_wasm(genesis_config.wasm_costs().into());
         pb_genesis_config
+            .mut_costs()
+            .set_host(genesis_config.host_function_costs().into());
+        pb_genesis_config
+            .mut_costs()
+            .set_storage(genesis_config.storage_costs().into());
+        pb_genesis_config.set_wasm_limits(genesis_config.wasm_limits().into());
+        {
+            let max_ttl_millis = u32::try_from(genesis_config.deploy_hash_expiry_millis())
+                .unwrap_or_else(|_| u32::max_value());
+            pb_genesis_config
+                .mut_deploy_config()
+                .set_max_ttl_millis(max_ttl_millis);
+        }
+        pb_genesis_config
     }
 }
 
@@ -48,7 +64,28 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             .into_iter()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
-        let wasm_costs = pb_genesis_config.take_costs().take_wasm().into();
+        let (wasm_costs, host_function_costs, storage_costs) = {
+            let mut costs = pb_genesis_config.take_costs();
+            (
+                costs.take_wasm().into(),
+                costs.take_host().into(),
+                costs.take_storage().into(),
+            )
+        };
+        let wasm_limits = if pb_genesis_config.has_wasm_limits() {
+            pb_genesis_config.take_wasm_limits().into()
+        } else {
+            WasmLimits::default()
+        };
+        // A deploy hash is rejected for as long as a deploy with that hash could still be valid.
+        let deploy_hash_expiry_millis = if pb_genesis_config.has_deploy_config() {
+            pb_genesis_config
+                .get_deploy_config()
+                .get_max_ttl_millis()
+                .into()
+        } else {
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS
+        };
         let mint_initializer_bytes = pb_genesis_config.mint_installer;
         let proof_of_stake_initializer_bytes = pb_genesis_config.pos_installer;
         let standard_payment_installer_bytes = pb_genesis_config.standard_payment_installer;
@@ -61,6 +98,10 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
         ))
     }
 }
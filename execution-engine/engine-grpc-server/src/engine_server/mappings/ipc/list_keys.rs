@@ -0,0 +1,44 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::list_keys::ListKeysRequest;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+/// Number of keys returned per page if the request doesn't specify a page size.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+impl TryFrom<ipc::ListKeysRequest> for ListKeysRequest {
+    type Error = MappingError;
+
+    fn try_from(mut list_keys_request: ipc::ListKeysRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_state_hash(list_keys_request.get_state_hash())?;
+
+        let prefix = list_keys_request.take_prefix();
+
+        let page_size = match list_keys_request.get_page_size() {
+            0 => DEFAULT_PAGE_SIZE,
+            page_size => page_size as usize,
+        };
+
+        let maybe_page_token = if list_keys_request.has_page_token() {
+            Some(
+                list_keys_request
+                    .take_page_token()
+                    .try_into()
+                    .map_err(MappingError::Parsing)?,
+            )
+        } else {
+            None
+        };
+
+        Ok(ListKeysRequest::new(
+            state_hash,
+            prefix,
+            page_size,
+            maybe_page_token,
+        ))
+    }
+}
@@ -1,14 +1,28 @@
 //! Functions for converting between CasperLabs types and their Protobuf equivalents which are
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
+mod bid_state;
 mod bond;
 mod deploy_item;
 mod deploy_result;
+mod diff;
+mod distribute_rewards_request;
+mod estimate;
+mod event;
 mod executable_deploy_item;
 mod execute_request;
 mod execution_effect;
 mod genesis_account;
 mod genesis_config;
+mod host_function_costs;
+mod list_keys;
 mod query_request;
+mod query_with_proof;
+mod slash_request;
+mod storage_costs;
+mod unbond_payout_request;
 mod upgrade_request;
+mod validation;
+mod view;
 mod wasm_costs;
+mod wasm_limits;
@@ -0,0 +1,45 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::query::QueryWithProofRequest;
+use engine_shared::stored_value::StoredValue;
+use engine_storage::trie::merkle_proof::TrieMerkleProof;
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key,
+};
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::QueryWithProofRequest> for QueryWithProofRequest {
+    type Error = MappingError;
+
+    fn try_from(mut query_request: ipc::QueryWithProofRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_state_hash(query_request.get_state_hash())?;
+
+        let key = query_request
+            .take_key()
+            .try_into()
+            .map_err(MappingError::Parsing)?;
+
+        Ok(QueryWithProofRequest::new(state_hash, key))
+    }
+}
+
+impl TryFrom<TrieMerkleProof<Key, StoredValue>> for ipc::TrieMerkleProof {
+    type Error = bytesrepr::Error;
+
+    fn try_from(proof: TrieMerkleProof<Key, StoredValue>) -> Result<Self, Self::Error> {
+        let mut pb_proof = ipc::TrieMerkleProof::new();
+        pb_proof.set_key(proof.key().to_bytes()?);
+        let steps = proof
+            .steps()
+            .iter()
+            .map(ToBytes::to_bytes)
+            .collect::<Result<_, _>>()?;
+        pb_proof.set_steps(steps);
+        Ok(pb_proof)
+    }
+}
@@ -0,0 +1,40 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::slash::SlashRequest;
+use types::{account::PublicKey, U512};
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::SlashRequest_ValidatorSlash> for (PublicKey, U512) {
+    type Error = MappingError;
+
+    fn try_from(mut pb_slash: ipc::SlashRequest_ValidatorSlash) -> Result<Self, Self::Error> {
+        let public_key = PublicKey::ed25519_try_from(pb_slash.get_validator_id())
+            .map_err(|_| MappingError::invalid_public_key_length(pb_slash.validator_id.len()))?;
+
+        let amount = pb_slash.take_value().try_into()?;
+
+        Ok((public_key, amount))
+    }
+}
+
+impl TryFrom<ipc::SlashRequest> for SlashRequest {
+    type Error = MappingError;
+
+    fn try_from(mut pb_slash_request: ipc::SlashRequest) -> Result<Self, Self::Error> {
+        let pre_state_hash = parse_state_hash(pb_slash_request.get_parent_state_hash())?;
+
+        let protocol_version = pb_slash_request.take_protocol_version().into();
+
+        let slashes = pb_slash_request
+            .take_slashes()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<(PublicKey, U512)>, MappingError>>()?;
+
+        Ok(SlashRequest::new(pre_state_hash, protocol_version, slashes))
+    }
+}
@@ -0,0 +1,39 @@
+use engine_wasm_prep::storage_costs::StorageCosts;
+
+use crate::engine_server::ipc::ChainSpec_CostTable_StorageCosts;
+
+impl From<StorageCosts> for ChainSpec_CostTable_StorageCosts {
+    fn from(storage_costs: StorageCosts) -> Self {
+        ChainSpec_CostTable_StorageCosts {
+            gas_per_byte: storage_costs.gas_per_byte,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_CostTable_StorageCosts> for StorageCosts {
+    fn from(pb_storage_costs: ChainSpec_CostTable_StorageCosts) -> Self {
+        StorageCosts {
+            gas_per_byte: pb_storage_costs.gas_per_byte,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_wasm_prep::storage_costs::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(storage_costs in gens::storage_costs_arb()) {
+            test_utils::protobuf_round_trip::<StorageCosts, ChainSpec_CostTable_StorageCosts>(
+                storage_costs
+            );
+        }
+    }
+}
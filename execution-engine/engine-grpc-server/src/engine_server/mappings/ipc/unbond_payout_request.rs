@@ -0,0 +1,28 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::unbond_payout::UnbondPayoutRequest;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::UnbondPayoutRequest> for UnbondPayoutRequest {
+    type Error = MappingError;
+
+    fn try_from(
+        mut pb_unbond_payout_request: ipc::UnbondPayoutRequest,
+    ) -> Result<Self, Self::Error> {
+        let pre_state_hash = parse_state_hash(pb_unbond_payout_request.get_parent_state_hash())?;
+
+        let protocol_version = pb_unbond_payout_request.take_protocol_version().into();
+
+        let era_height = pb_unbond_payout_request.get_era_height();
+
+        Ok(UnbondPayoutRequest::new(
+            pre_state_hash,
+            protocol_version,
+            era_height,
+        ))
+    }
+}
@@ -35,6 +35,34 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
         } else {
             Some(upgrade_point.mut_new_costs().take_wasm().into())
         };
+        let host_function_costs =
+            if !upgrade_point.has_new_costs() || !upgrade_point.get_new_costs().has_host() {
+                None
+            } else {
+                Some(upgrade_point.mut_new_costs().take_host().into())
+            };
+        let storage_costs =
+            if !upgrade_point.has_new_costs() || !upgrade_point.get_new_costs().has_storage() {
+                None
+            } else {
+                Some(upgrade_point.mut_new_costs().take_storage().into())
+            };
+        let wasm_limits = if !upgrade_point.has_new_wasm_limits() {
+            None
+        } else {
+            Some(upgrade_point.take_new_wasm_limits().into())
+        };
+        // A deploy hash is rejected for as long as a deploy with that hash could still be valid.
+        let deploy_hash_expiry_millis = if !upgrade_point.has_new_deploy_config() {
+            None
+        } else {
+            Some(
+                upgrade_point
+                    .get_new_deploy_config()
+                    .get_max_ttl_millis()
+                    .into(),
+            )
+        };
         let activation_point = if !upgrade_point.has_activation_point() {
             None
         } else {
@@ -48,6 +76,10 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
             activation_point,
         ))
     }
@@ -0,0 +1,18 @@
+use engine_wasm_prep::validation::ValidationReport;
+
+use crate::engine_server::ipc;
+
+impl From<ValidationReport> for ipc::ValidationReport {
+    fn from(validation_report: ValidationReport) -> Self {
+        let mut pb_validation_report = ipc::ValidationReport::new();
+
+        let errors: Vec<String> = validation_report
+            .take_errors()
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect();
+        pb_validation_report.set_errors(errors.into());
+
+        pb_validation_report
+    }
+}
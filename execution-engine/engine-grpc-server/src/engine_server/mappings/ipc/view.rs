@@ -0,0 +1,38 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::view::ViewRequest;
+use types::account::PublicKey;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_state_hash, MappingError},
+};
+
+impl TryFrom<ipc::ViewRequest> for ViewRequest {
+    type Error = MappingError;
+
+    fn try_from(mut view_request: ipc::ViewRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_state_hash(view_request.get_state_hash())?;
+
+        let block_time = view_request.get_block_time();
+
+        let address = PublicKey::ed25519_try_from(view_request.get_address())
+            .map_err(|_| MappingError::invalid_public_key_length(view_request.address.len()))?;
+
+        let contract = view_request
+            .take_contract()
+            .payload
+            .map(Into::into)
+            .ok_or_else(|| MappingError::MissingPayload)?;
+
+        let protocol_version = view_request.take_protocol_version().into();
+
+        Ok(ViewRequest::new(
+            state_hash,
+            block_time,
+            address,
+            contract,
+            protocol_version,
+        ))
+    }
+}
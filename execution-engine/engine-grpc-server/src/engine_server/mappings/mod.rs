@@ -11,6 +11,7 @@ use std::{
 };
 
 use engine_core::{engine_state, DEPLOY_HASH_LENGTH};
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
 use types::account::ED25519_LENGTH;
 
 pub use transforms::TransformMap;
@@ -33,6 +34,20 @@ pub(crate) fn vec_to_array64(input: Vec<u8>, input_name: &str) -> Result<[u8; 64
     Ok(result)
 }
 
+/// Try to convert a state hash held in a protobuf message to a `Blake2bHash`.
+pub(crate) fn parse_state_hash(state_hash: &[u8]) -> Result<Blake2bHash, MappingError> {
+    let length = state_hash.len();
+    if length != BLAKE2B_DIGEST_LENGTH {
+        return Err(MappingError::InvalidStateHashLength {
+            expected: BLAKE2B_DIGEST_LENGTH,
+            actual: length,
+        });
+    }
+    state_hash
+        .try_into()
+        .map_err(|_| MappingError::TryFromSlice)
+}
+
 #[derive(Debug)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
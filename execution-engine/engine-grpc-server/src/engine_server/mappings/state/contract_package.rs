@@ -0,0 +1,69 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_shared::contract_package::{ContractPackage, ContractVersion};
+
+use crate::engine_server::{mappings::ParsingError, state};
+
+impl From<ContractVersion> for state::ContractPackage_Version {
+    fn from(contract_version: ContractVersion) -> Self {
+        let mut pb_version = state::ContractPackage_Version::new();
+        pb_version.set_contract(contract_version.contract_key().into());
+        pb_version.set_is_enabled(contract_version.is_enabled());
+        pb_version
+    }
+}
+
+impl TryFrom<state::ContractPackage_Version> for ContractVersion {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_version: state::ContractPackage_Version) -> Result<Self, Self::Error> {
+        let contract_key = pb_version.take_contract().try_into()?;
+        Ok(ContractVersion::new(contract_key, pb_version.is_enabled))
+    }
+}
+
+impl From<ContractPackage> for state::ContractPackage {
+    fn from(contract_package: ContractPackage) -> Self {
+        let mut pb_contract_package = state::ContractPackage::new();
+        let versions: Vec<state::ContractPackage_Version> = contract_package
+            .versions()
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect();
+        pb_contract_package.set_versions(versions.into());
+        pb_contract_package
+    }
+}
+
+impl TryFrom<state::ContractPackage> for ContractPackage {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_contract_package: state::ContractPackage) -> Result<Self, Self::Error> {
+        let versions = pb_contract_package
+            .take_versions()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<ContractVersion>, ParsingError>>()?;
+        Ok(ContractPackage::new(versions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::contract_package::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(contract_package in gens::contract_package_arb()) {
+            test_utils::protobuf_round_trip::<ContractPackage, state::ContractPackage>(
+                contract_package
+            );
+        }
+    }
+}
@@ -19,7 +19,7 @@ pub mod mappings;
 use std::{
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
-    fmt::Debug,
+    fmt::{Debug, Display},
     io::ErrorKind,
     iter::FromIterator,
     marker::{Send, Sync},
@@ -30,14 +30,24 @@ use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
 use log::{info, warn, Level};
 
 use engine_core::engine_state::{
+    bid_state::{BidStateRequest, BidStateResult},
+    diff::{DiffRequest, DiffResult},
+    distribute_rewards::DistributeRewardsRequest,
+    estimate::{EstimateRequest, EstimateResult},
     execute_request::ExecuteRequest,
     genesis::{GenesisConfig, GenesisResult},
-    query::{QueryRequest, QueryResult},
+    list_keys::{ListKeysRequest, ListKeysResult},
+    module_cache::ModuleCache,
+    query::{QueryRequest, QueryResult, QueryWithProofRequest, QueryWithProofResult},
+    slash::SlashRequest,
+    system_call::SystemCallResult,
+    unbond_payout::UnbondPayoutRequest,
     upgrade::{UpgradeConfig, UpgradeResult},
+    view::{ViewRequest, ViewResult},
     EngineState, Error as EngineError,
 };
 use engine_shared::{
-    logging::{self, log_duration},
+    logging::{self, log_duration, log_metric},
     newtypes::{Blake2bHash, CorrelationId},
 };
 use engine_storage::global_state::{CommitResult, StateProvider};
@@ -45,26 +55,52 @@ use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
     ipc::{
-        BidStateRequest, BidStateResponse, ChainSpec_GenesisConfig, CommitRequest, CommitResponse,
-        DistributeRewardsRequest, DistributeRewardsResponse, ExecuteResponse, GenesisResponse,
-        QueryResponse, SlashRequest, SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse,
-        UpgradeRequest, UpgradeResponse,
+        BidStateResponse, ChainSpec_GenesisConfig, CommitRequest, CommitResponse, DiffResponse,
+        DistributeRewardsResponse, EstimateResponse, ExecuteBlockResponse, ExecuteResponse,
+        GenesisResponse, ListKeysResponse, QueryResponse, QueryWithProofResponse, SlashResponse,
+        UnbondPayoutResponse, UpgradeRequest, UpgradeResponse, ValidateRequest, ValidateResponse,
+        ViewResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
-    mappings::{ParsingError, TransformMap},
+    mappings::{MappingError, ParsingError, TransformMap},
 };
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_EXEC_BLOCK: &str = "exec_block_duration";
+const METRIC_DURATION_ESTIMATE: &str = "estimate_duration";
+const METRIC_DURATION_VIEW: &str = "view_duration";
+const METRIC_DURATION_VALIDATE: &str = "validate_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_QUERY_WITH_PROOF: &str = "query_with_proof_duration";
+const METRIC_DURATION_DIFF: &str = "diff_duration";
+const METRIC_DURATION_LIST_KEYS: &str = "list_keys_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_SLASH: &str = "slash_duration";
+const METRIC_DURATION_BID_STATE: &str = "bid_state_duration";
+const METRIC_DURATION_UNBOND_PAYOUT: &str = "unbond_payout_duration";
+const METRIC_DURATION_DISTRIBUTE_REWARDS: &str = "distribute_rewards_duration";
+
+const METRIC_MODULE_CACHE_HITS: &str = "module_cache_hits";
+const METRIC_MODULE_CACHE_MISSES: &str = "module_cache_misses";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_EXEC_BLOCK: &str = "exec_block_response";
+const TAG_RESPONSE_ESTIMATE: &str = "estimate_response";
+const TAG_RESPONSE_VIEW: &str = "view_response";
+const TAG_RESPONSE_VALIDATE: &str = "validate_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_QUERY_WITH_PROOF: &str = "query_with_proof_response";
+const TAG_RESPONSE_DIFF: &str = "diff_response";
+const TAG_RESPONSE_LIST_KEYS: &str = "list_keys_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_SLASH: &str = "slash_response";
+const TAG_RESPONSE_BID_STATE: &str = "bid_state_response";
+const TAG_RESPONSE_UNBOND_PAYOUT: &str = "unbond_payout_response";
+const TAG_RESPONSE_DISTRIBUTE_REWARDS: &str = "distribute_rewards_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
 
@@ -77,7 +113,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -162,6 +198,209 @@ where
         SingleResponse::completed(response)
     }
 
+    fn query_with_proof(
+        &self,
+        _request_options: RequestOptions,
+        query_request: ipc::QueryWithProofRequest,
+    ) -> SingleResponse<QueryWithProofResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: QueryWithProofRequest = match query_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::QueryWithProofResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_QUERY_WITH_PROOF,
+                    TAG_RESPONSE_QUERY_WITH_PROOF,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let result = self.run_query_with_proof(correlation_id, request);
+
+        let response = match result {
+            // A proof of absence is as much a successful answer as a proof of inclusion.
+            Ok(QueryWithProofResult::Success(proof))
+            | Ok(QueryWithProofResult::ValueNotFound(proof)) => {
+                let mut result = ipc::QueryWithProofResponse::new();
+                match proof.try_into() {
+                    Ok(pb_proof) => {
+                        info!(
+                            "query with proof successful; correlation_id: {}",
+                            correlation_id
+                        );
+                        result.set_success(pb_proof);
+                    }
+                    Err(error_msg) => {
+                        let log_message = format!("Failed to serialize proof: {}", error_msg);
+                        warn!("{}", log_message);
+                        result.set_failure(log_message);
+                    }
+                }
+                result
+            }
+            Ok(QueryWithProofResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                let mut result = ipc::QueryWithProofResponse::new();
+                result.set_failure(log_message.to_string());
+                result
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::QueryWithProofResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_QUERY_WITH_PROOF,
+            TAG_RESPONSE_QUERY_WITH_PROOF,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn diff(
+        &self,
+        _request_options: RequestOptions,
+        diff_request: ipc::DiffRequest,
+    ) -> SingleResponse<DiffResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: DiffRequest = match diff_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::DiffResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_DIFF,
+                    TAG_RESPONSE_DIFF,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let result = self.run_diff(correlation_id, request);
+
+        let response = match result {
+            Ok(DiffResult::Success(trie_diff)) => {
+                info!("diff successful; correlation_id: {}", correlation_id);
+                let mut result = ipc::DiffResponse::new();
+                result.set_success(trie_diff.into());
+                result
+            }
+            Ok(DiffResult::RootNotFound(root)) => {
+                let log_message = format!("Root not found: {}", root);
+                info!("{}", log_message);
+                let mut result = ipc::DiffResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::DiffResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_DIFF,
+            TAG_RESPONSE_DIFF,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn list_keys(
+        &self,
+        _request_options: RequestOptions,
+        list_keys_request: ipc::ListKeysRequest,
+    ) -> SingleResponse<ListKeysResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: ListKeysRequest = match list_keys_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::ListKeysResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_LIST_KEYS,
+                    TAG_RESPONSE_LIST_KEYS,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let result = self.run_list_keys(correlation_id, request);
+
+        let response = match result {
+            Ok(ListKeysResult::Success {
+                keys,
+                maybe_next_page_token,
+            }) => {
+                info!("list keys successful; correlation_id: {}", correlation_id);
+                let mut list_keys_result = ipc::ListKeysResult::new();
+                let keys: Vec<state::Key> = keys.into_iter().map(Into::into).collect();
+                list_keys_result.set_keys(keys.into());
+                if let Some(next_page_token) = maybe_next_page_token {
+                    list_keys_result.set_next_page_token(next_page_token.into());
+                }
+                let mut result = ipc::ListKeysResponse::new();
+                result.set_success(list_keys_result);
+                result
+            }
+            Ok(ListKeysResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                let mut result = ipc::ListKeysResponse::new();
+                result.set_failure(log_message.to_string());
+                result
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::ListKeysResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_LIST_KEYS,
+            TAG_RESPONSE_LIST_KEYS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn execute(
         &self,
         _request_options: RequestOptions,
@@ -198,6 +437,7 @@ where
         exec_response
             .mut_success()
             .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        log_module_cache_metrics(correlation_id, TAG_RESPONSE_EXEC, self.module_cache());
         log_duration(
             correlation_id,
             METRIC_DURATION_EXEC,
@@ -207,6 +447,222 @@ where
         SingleResponse::completed(exec_response)
     }
 
+    fn execute_block(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> SingleResponse<ExecuteBlockResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_request: ExecuteRequest = match exec_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                return SingleResponse::completed(err.into());
+            }
+        };
+        let parent_state_hash = exec_request.parent_state_hash;
+
+        let mut exec_block_response = ExecuteBlockResponse::new();
+
+        match self.run_execute_block(correlation_id, exec_request) {
+            Ok(exec_block_result) => {
+                let (results, commit_result) = exec_block_result.take();
+                let protobuf_results_iter = results.into_iter().map(Into::into);
+                exec_block_response
+                    .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+                exec_block_response
+                    .set_commit_response(commit_response(parent_state_hash, Ok(commit_result)));
+            }
+            Err(error) => {
+                exec_block_response
+                    .set_commit_response(commit_response(parent_state_hash, Err(error)));
+            }
+        }
+
+        log_module_cache_metrics(correlation_id, TAG_RESPONSE_EXEC_BLOCK, self.module_cache());
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_EXEC_BLOCK,
+            TAG_RESPONSE_EXEC_BLOCK,
+            start.elapsed(),
+        );
+        SingleResponse::completed(exec_block_response)
+    }
+
+    fn estimate(
+        &self,
+        _request_options: RequestOptions,
+        estimate_request: ipc::EstimateRequest,
+    ) -> SingleResponse<EstimateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: EstimateRequest = match estimate_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = EstimateResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_ESTIMATE,
+                    TAG_RESPONSE_ESTIMATE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.run_estimate(correlation_id, request) {
+            Ok(EstimateResult::Success {
+                payment_result,
+                maybe_session_result,
+                effect,
+            }) => {
+                info!("estimate successful; correlation_id: {}", correlation_id);
+                let mut estimate_result = ipc::EstimateResult::new();
+                estimate_result.set_payment_result(payment_result.into());
+                if let Some(session_result) = maybe_session_result {
+                    estimate_result.set_session_result(session_result.into());
+                }
+                estimate_result.set_effects(effect.into());
+                let mut result = EstimateResponse::new();
+                result.set_success(estimate_result);
+                result
+            }
+            Ok(EstimateResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                let mut result = EstimateResponse::new();
+                result.set_failure(log_message.to_string());
+                result
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = EstimateResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_ESTIMATE,
+            TAG_RESPONSE_ESTIMATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn view(
+        &self,
+        _request_options: RequestOptions,
+        view_request: ipc::ViewRequest,
+    ) -> SingleResponse<ViewResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: ViewRequest = match view_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ViewResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_VIEW,
+                    TAG_RESPONSE_VIEW,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let response = match self.run_view(correlation_id, request) {
+            Ok(ViewResult::Success(return_value)) => match return_value.to_bytes() {
+                Ok(serialized_value) => {
+                    info!("view successful; correlation_id: {}", correlation_id);
+                    let mut result = ViewResponse::new();
+                    result.set_success(serialized_value);
+                    result
+                }
+                Err(err) => {
+                    let log_message = format!("Failed to serialize return value: {:?}", err);
+                    warn!("{}", log_message);
+                    let mut result = ViewResponse::new();
+                    result.set_failure(log_message);
+                    result
+                }
+            },
+            Ok(ViewResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                let mut result = ViewResponse::new();
+                result.set_failure(log_message.to_string());
+                result
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ViewResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_VIEW,
+            TAG_RESPONSE_VIEW,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn validate(
+        &self,
+        _request_options: RequestOptions,
+        mut validate_request: ValidateRequest,
+    ) -> SingleResponse<ValidateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let protocol_version = validate_request.take_protocol_version().into();
+        let module_bytes = validate_request.get_module_bytes();
+
+        let mut result = ValidateResponse::new();
+        match EngineState::validate(self, module_bytes, protocol_version) {
+            Ok(validation_report) => {
+                info!(
+                    "validate successful; valid: {}; correlation_id: {}",
+                    validation_report.is_valid(),
+                    correlation_id
+                );
+                result.set_success(validation_report.into());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                result.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_VALIDATE,
+            TAG_RESPONSE_VALIDATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(result)
+    }
+
     fn commit(
         &self,
         _request_options: RequestOptions,
@@ -253,57 +709,9 @@ where
         };
 
         // "Apply" effects to global state
-        let commit_response = {
-            let mut ret = CommitResponse::new();
-
-            match self.apply_effect(correlation_id, protocol_version, pre_state_hash, transforms) {
-                Ok(CommitResult::Success {
-                    state_root,
-                    bonded_validators,
-                }) => {
-                    let properties = {
-                        let mut tmp = BTreeMap::new();
-                        tmp.insert("post-state-hash", format!("{:?}", state_root));
-                        tmp.insert("success", true.to_string());
-                        tmp
-                    };
-                    logging::log_details(
-                        Level::Info,
-                        "effects applied; new state hash is: {post-state-hash}".to_owned(),
-                        properties,
-                    );
-
-                    let bonds = bonded_validators.into_iter().map(Into::into).collect();
-                    let commit_result = ret.mut_success();
-                    commit_result.set_poststate_hash(state_root.to_vec());
-                    commit_result.set_bonded_validators(bonds);
-                }
-                Ok(CommitResult::RootNotFound) => {
-                    warn!("RootNotFound");
-                    ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
-                }
-                Ok(CommitResult::KeyNotFound(key)) => {
-                    warn!("{:?} not found", key);
-                    ret.set_key_not_found(key.into());
-                }
-                Ok(CommitResult::TypeMismatch(type_mismatch)) => {
-                    warn!("{:?}", type_mismatch);
-                    ret.set_type_mismatch(type_mismatch.into());
-                }
-                Ok(CommitResult::Serialization(error)) => {
-                    warn!("{:?}", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
-                }
-                Err(error) => {
-                    warn!("State error {:?} when applying transforms", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
-                }
-            }
-
-            ret
-        };
+        let commit_result =
+            self.apply_effect(correlation_id, protocol_version, pre_state_hash, transforms);
+        let commit_response = commit_response(pre_state_hash, commit_result);
 
         log_duration(
             correlation_id,
@@ -448,52 +856,305 @@ where
     fn bid_state(
         &self,
         _request_options: RequestOptions,
-        _bid_state_request: BidStateRequest,
+        bid_state_request: ipc::BidStateRequest,
     ) -> SingleResponse<BidStateResponse> {
         if !self.config().highway() {
             return SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()));
         }
-        let response = BidStateResponse::new();
-        SingleResponse::completed(response)
+
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let bid_state_request: BidStateRequest = match bid_state_request.try_into() {
+            Ok(bid_state_request) => bid_state_request,
+            Err(error) => {
+                let err_msg = error.to_string();
+                warn!("{}", err_msg);
+
+                let mut bid_state_response = BidStateResponse::new();
+                bid_state_response.mut_error().set_message(err_msg);
+
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BID_STATE,
+                    TAG_RESPONSE_BID_STATE,
+                    start.elapsed(),
+                );
+
+                return SingleResponse::completed(bid_state_response);
+            }
+        };
+
+        let pre_state_hash = bid_state_request.pre_state_hash();
+
+        let bid_state_response = match self.get_bid_state(correlation_id, bid_state_request) {
+            Ok(BidStateResult::Success(bid_state)) => {
+                let mut ret = BidStateResponse::new();
+                ret.set_success(bid_state.into());
+                ret
+            }
+            Ok(BidStateResult::RootNotFound) => {
+                warn!("RootNotFound");
+                let mut ret = BidStateResponse::new();
+                ret.mut_missing_parent().set_hash(pre_state_hash.to_vec());
+                ret
+            }
+            Err(err) => {
+                let err_msg = err.to_string();
+                warn!("{}", err_msg);
+
+                let mut ret = BidStateResponse::new();
+                ret.mut_error().set_message(err_msg);
+                ret
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_BID_STATE,
+            TAG_RESPONSE_BID_STATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(bid_state_response)
     }
 
     fn distribute_rewards(
         &self,
         _request_options: RequestOptions,
-        _distribute_rewards_request: DistributeRewardsRequest,
+        distribute_rewards_request: ipc::DistributeRewardsRequest,
     ) -> SingleResponse<DistributeRewardsResponse> {
         if !self.config().highway() {
             return SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()));
         }
-        let response = DistributeRewardsResponse::new();
-        SingleResponse::completed(response)
+
+        system_call(
+            "distribute rewards",
+            METRIC_DURATION_DISTRIBUTE_REWARDS,
+            TAG_RESPONSE_DISTRIBUTE_REWARDS,
+            distribute_rewards_request,
+            DistributeRewardsRequest::pre_state_hash,
+            |correlation_id, distribute_rewards_request| {
+                self.commit_distribute_rewards(correlation_id, distribute_rewards_request)
+            },
+        )
     }
 
     fn slash(
         &self,
         _request_options: RequestOptions,
-        _slash_request: SlashRequest,
+        slash_request: ipc::SlashRequest,
     ) -> SingleResponse<SlashResponse> {
         if !self.config().highway() {
             return SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()));
         }
-        let response = SlashResponse::new();
-        SingleResponse::completed(response)
+
+        system_call(
+            "slash",
+            METRIC_DURATION_SLASH,
+            TAG_RESPONSE_SLASH,
+            slash_request,
+            SlashRequest::pre_state_hash,
+            |correlation_id, slash_request| self.commit_slash(correlation_id, slash_request),
+        )
     }
 
     fn unbond_payout(
         &self,
         _request_options: RequestOptions,
-        _unbond_payout_request: UnbondPayoutRequest,
+        unbond_payout_request: ipc::UnbondPayoutRequest,
     ) -> SingleResponse<UnbondPayoutResponse> {
         if !self.config().highway() {
             return SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()));
         }
-        let response = UnbondPayoutResponse::new();
-        SingleResponse::completed(response)
+
+        system_call(
+            "unbond payout",
+            METRIC_DURATION_UNBOND_PAYOUT,
+            TAG_RESPONSE_UNBOND_PAYOUT,
+            unbond_payout_request,
+            UnbondPayoutRequest::pre_state_hash,
+            |correlation_id, unbond_payout_request| {
+                self.commit_unbond_payout(correlation_id, unbond_payout_request)
+            },
+        )
     }
 }
 
+/// The response to a system call into the Proof of Stake contract whose effects are committed on
+/// success.
+trait SystemCallResponse: Default {
+    fn set_commit_result(&mut self, commit_result: ipc::CommitResult);
+
+    fn set_root_not_found(&mut self, pre_state_hash: Blake2bHash);
+
+    fn set_error_message(&mut self, message: String);
+}
+
+macro_rules! system_call_response {
+    ($($response:ty),*) => {
+        $(
+            impl SystemCallResponse for $response {
+                fn set_commit_result(&mut self, commit_result: ipc::CommitResult) {
+                    self.set_success(commit_result);
+                }
+
+                fn set_root_not_found(&mut self, pre_state_hash: Blake2bHash) {
+                    self.mut_missing_parent().set_hash(pre_state_hash.to_vec());
+                }
+
+                fn set_error_message(&mut self, message: String) {
+                    self.mut_error().set_message(message);
+                }
+            }
+        )*
+    };
+}
+
+system_call_response!(
+    DistributeRewardsResponse,
+    SlashResponse,
+    UnbondPayoutResponse
+);
+
+/// Parses the request of a system call, commits the call with `commit` and maps its result into
+/// the response, logging the duration of the whole under `metric` and `tag`.
+fn system_call<PbRequest, Request, Response, E>(
+    name: &str,
+    metric: &str,
+    tag: &str,
+    pb_request: PbRequest,
+    pre_state_hash: impl Fn(&Request) -> Blake2bHash,
+    commit: impl FnOnce(CorrelationId, Request) -> Result<SystemCallResult<E>, EngineError>,
+) -> SingleResponse<Response>
+where
+    PbRequest: TryInto<Request, Error = MappingError>,
+    Response: SystemCallResponse + Send + 'static,
+    E: Display,
+{
+    let start = Instant::now();
+    let correlation_id = CorrelationId::new();
+    let mut response = Response::default();
+
+    let request = match pb_request.try_into() {
+        Ok(request) => request,
+        Err(error) => {
+            let err_msg = error.to_string();
+            warn!("{}", err_msg);
+            response.set_error_message(err_msg);
+            log_duration(correlation_id, metric, tag, start.elapsed());
+            return SingleResponse::completed(response);
+        }
+    };
+
+    let pre_state_hash = pre_state_hash(&request);
+
+    match commit(correlation_id, request) {
+        Ok(SystemCallResult::Success {
+            post_state_hash,
+            bonded_validators,
+        }) => {
+            info!("{} successful: {}", name, post_state_hash);
+            let bonds = bonded_validators.into_iter().map(Into::into).collect();
+            let mut commit_result = ipc::CommitResult::new();
+            commit_result.set_poststate_hash(post_state_hash.to_vec());
+            commit_result.set_bonded_validators(bonds);
+            response.set_commit_result(commit_result);
+        }
+        Ok(SystemCallResult::RootNotFound) => {
+            warn!("RootNotFound");
+            response.set_root_not_found(pre_state_hash);
+        }
+        Ok(system_call_result) => {
+            let err_msg = system_call_result.to_string();
+            warn!("{}", err_msg);
+            response.set_error_message(err_msg);
+        }
+        Err(err) => {
+            let err_msg = err.to_string();
+            warn!("{}", err_msg);
+            response.set_error_message(err_msg);
+        }
+    }
+
+    log_duration(correlation_id, metric, tag, start.elapsed());
+
+    SingleResponse::completed(response)
+}
+
+fn commit_response(
+    pre_state_hash: Blake2bHash,
+    commit_result: Result<CommitResult, EngineError>,
+) -> CommitResponse {
+    let mut ret = CommitResponse::new();
+
+    match commit_result {
+        Ok(CommitResult::Success {
+            state_root,
+            bonded_validators,
+        }) => {
+            let properties = {
+                let mut tmp = BTreeMap::new();
+                tmp.insert("post-state-hash", format!("{:?}", state_root));
+                tmp.insert("success", true.to_string());
+                tmp
+            };
+            logging::log_details(
+                Level::Info,
+                "effects applied; new state hash is: {post-state-hash}".to_owned(),
+                properties,
+            );
+
+            let bonds = bonded_validators.into_iter().map(Into::into).collect();
+            let commit_result = ret.mut_success();
+            commit_result.set_poststate_hash(state_root.to_vec());
+            commit_result.set_bonded_validators(bonds);
+        }
+        Ok(CommitResult::RootNotFound) => {
+            warn!("RootNotFound");
+            ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
+        }
+        Ok(CommitResult::KeyNotFound(key)) => {
+            warn!("{:?} not found", key);
+            ret.set_key_not_found(key.into());
+        }
+        Ok(CommitResult::TypeMismatch(type_mismatch)) => {
+            warn!("{:?}", type_mismatch);
+            ret.set_type_mismatch(type_mismatch.into());
+        }
+        Ok(CommitResult::Serialization(error)) => {
+            warn!("{:?}", error);
+            ret.mut_failed_transform()
+                .set_message(format!("{:?}", error));
+        }
+        Err(error) => {
+            warn!("State error {:?} when applying transforms", error);
+            ret.mut_failed_transform()
+                .set_message(format!("{:?}", error));
+        }
+    }
+
+    ret
+}
+
+/// Logs the cumulative hit and miss counts of the module cache.
+fn log_module_cache_metrics(correlation_id: CorrelationId, tag: &str, module_cache: &ModuleCache) {
+    log_metric(
+        correlation_id,
+        METRIC_MODULE_CACHE_HITS,
+        tag,
+        "count",
+        module_cache.hits() as f64,
+    );
+    log_metric(
+        correlation_id,
+        METRIC_MODULE_CACHE_MISSES,
+        tag,
+        "count",
+        module_cache.misses() as f64,
+    );
+}
+
 // Helper method which returns single DeployResult that is set to be a
 // WasmError.
 pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
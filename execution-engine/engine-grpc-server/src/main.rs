@@ -1,7 +1,10 @@
 use std::{
     collections::BTreeMap,
-    fs,
+    convert::TryFrom,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
     path::PathBuf,
+    process,
     str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -18,11 +21,16 @@ use log::{error, info, Level, LevelFilter};
 
 use engine_shared::{
     logging::{self, Settings, Style},
+    newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
+    global_state::{
+        lmdb::{LmdbGlobalState, PruneResult},
+        snapshot::ExportSnapshotResult,
+    },
+    transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
 };
 
@@ -107,6 +115,58 @@ const ARG_HIGHWAY: &str = "highway";
 const ARG_HIGHWAY_SHORT: &str = "w";
 const ARG_HIGHWAY_HELP: &str = "Highway consensus mode";
 
+// module cache capacity
+const ARG_MODULE_CACHE_CAPACITY: &str = "module-cache-capacity";
+const ARG_MODULE_CACHE_CAPACITY_DEFAULT: &str = "256";
+const ARG_MODULE_CACHE_CAPACITY_VALUE: &str = "NUM";
+const ARG_MODULE_CACHE_CAPACITY_HELP: &str =
+    "Sets the max number of deserialized stored contracts kept in memory; 0 disables the cache";
+const ARG_MODULE_CACHE_CAPACITY_EXPECT: &str = "expected valid module cache capacity";
+
+// prune
+const ARG_PRUNE: &str = "prune";
+const ARG_PRUNE_VALUE: &str = "FILE";
+const ARG_PRUNE_HELP: &str = "Prunes the global state down to the hex-encoded state roots \
+                              listed one per line in FILE, then exits instead of serving";
+const READ_PRUNE_FILE_EXPECT: &str = "Could not read prune file";
+const PARSE_STATE_ROOT_EXPECT: &str = "Could not parse state root";
+
+// prune batch size
+const ARG_PRUNE_BATCH_SIZE: &str = "prune-batch-size";
+const ARG_PRUNE_BATCH_SIZE_DEFAULT: &str = "10000";
+const ARG_PRUNE_BATCH_SIZE_VALUE: &str = "NUM";
+const ARG_PRUNE_BATCH_SIZE_HELP: &str =
+    "Sets the max number of trie nodes visited per write transaction while pruning";
+const ARG_PRUNE_BATCH_SIZE_EXPECT: &str = "expected valid prune batch size";
+
+// export snapshot
+const ARG_EXPORT_SNAPSHOT: &str = "export-snapshot";
+const ARG_EXPORT_SNAPSHOT_VALUE: &str = "FILE";
+const ARG_EXPORT_SNAPSHOT_HELP: &str = "Exports a snapshot of the global state under the state \
+                                        root given by --snapshot-root to FILE, then exits \
+                                        instead of serving";
+const CREATE_SNAPSHOT_FILE_EXPECT: &str = "Could not create snapshot file";
+
+// snapshot root
+const ARG_SNAPSHOT_ROOT: &str = "snapshot-root";
+const ARG_SNAPSHOT_ROOT_VALUE: &str = "HASH";
+const ARG_SNAPSHOT_ROOT_HELP: &str = "Sets the hex-encoded state root of an exported snapshot";
+
+// snapshot chunk size
+const ARG_SNAPSHOT_CHUNK_SIZE: &str = "snapshot-chunk-size";
+const ARG_SNAPSHOT_CHUNK_SIZE_DEFAULT: &str = "10000";
+const ARG_SNAPSHOT_CHUNK_SIZE_VALUE: &str = "NUM";
+const ARG_SNAPSHOT_CHUNK_SIZE_HELP: &str =
+    "Sets the max number of key-value pairs per chunk of an exported snapshot";
+const ARG_SNAPSHOT_CHUNK_SIZE_EXPECT: &str = "expected valid snapshot chunk size";
+
+// import snapshot
+const ARG_IMPORT_SNAPSHOT: &str = "import-snapshot";
+const ARG_IMPORT_SNAPSHOT_VALUE: &str = "FILE";
+const ARG_IMPORT_SNAPSHOT_HELP: &str = "Imports the snapshot in FILE into an empty global state, \
+                                        then exits instead of serving";
+const OPEN_SNAPSHOT_FILE_EXPECT: &str = "Could not open snapshot file";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -118,6 +178,21 @@ fn main() {
 
     let _ = logging::initialize(get_log_settings(&arg_matches));
 
+    if arg_matches.is_present(ARG_PRUNE) {
+        prune_global_state(&arg_matches);
+        return;
+    }
+
+    if arg_matches.is_present(ARG_EXPORT_SNAPSHOT) {
+        export_snapshot(&arg_matches);
+        return;
+    }
+
+    if arg_matches.is_present(ARG_IMPORT_SNAPSHOT) {
+        import_snapshot(&arg_matches);
+        return;
+    }
+
     info!("starting Execution Engine Server");
 
     let socket = get_socket(&arg_matches);
@@ -237,9 +312,64 @@ fn get_args() -> ArgMatches<'static> {
                 .short(ARG_HIGHWAY_SHORT)
                 .help(ARG_HIGHWAY_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_MODULE_CACHE_CAPACITY)
+                .long(ARG_MODULE_CACHE_CAPACITY)
+                .takes_value(true)
+                .default_value(ARG_MODULE_CACHE_CAPACITY_DEFAULT)
+                .value_name(ARG_MODULE_CACHE_CAPACITY_VALUE)
+                .help(ARG_MODULE_CACHE_CAPACITY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_PRUNE)
+                .long(ARG_PRUNE)
+                .value_name(ARG_PRUNE_VALUE)
+                .help(ARG_PRUNE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_PRUNE_BATCH_SIZE)
+                .long(ARG_PRUNE_BATCH_SIZE)
+                .takes_value(true)
+                .default_value(ARG_PRUNE_BATCH_SIZE_DEFAULT)
+                .value_name(ARG_PRUNE_BATCH_SIZE_VALUE)
+                .help(ARG_PRUNE_BATCH_SIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_EXPORT_SNAPSHOT)
+                .long(ARG_EXPORT_SNAPSHOT)
+                .value_name(ARG_EXPORT_SNAPSHOT_VALUE)
+                .help(ARG_EXPORT_SNAPSHOT_HELP)
+                .requires(ARG_SNAPSHOT_ROOT)
+                .conflicts_with_all(&[ARG_PRUNE, ARG_IMPORT_SNAPSHOT])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_SNAPSHOT_ROOT)
+                .long(ARG_SNAPSHOT_ROOT)
+                .value_name(ARG_SNAPSHOT_ROOT_VALUE)
+                .help(ARG_SNAPSHOT_ROOT_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_SNAPSHOT_CHUNK_SIZE)
+                .long(ARG_SNAPSHOT_CHUNK_SIZE)
+                .takes_value(true)
+                .default_value(ARG_SNAPSHOT_CHUNK_SIZE_DEFAULT)
+                .value_name(ARG_SNAPSHOT_CHUNK_SIZE_VALUE)
+                .help(ARG_SNAPSHOT_CHUNK_SIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_IMPORT_SNAPSHOT)
+                .long(ARG_IMPORT_SNAPSHOT)
+                .value_name(ARG_IMPORT_SNAPSHOT_VALUE)
+                .help(ARG_IMPORT_SNAPSHOT_HELP)
+                .conflicts_with(ARG_PRUNE)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
-                .required(true)
+                .required_unless_one(&[ARG_PRUNE, ARG_EXPORT_SNAPSHOT, ARG_IMPORT_SNAPSHOT])
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
@@ -289,6 +419,48 @@ fn get_map_size(arg_matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
+/// Parses prune argument and returns the state roots to keep
+fn get_prune_roots(arg_matches: &ArgMatches) -> Vec<Blake2bHash> {
+    let path = arg_matches
+        .value_of(ARG_PRUNE)
+        .expect("should have prune argument if pruning");
+    fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("{}: {:?}", READ_PRUNE_FILE_EXPECT, path))
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(parse_state_root)
+        .collect()
+}
+
+/// Parses a hex-encoded state root
+fn parse_state_root(hex: &str) -> Blake2bHash {
+    base16::decode(hex)
+        .ok()
+        .and_then(|bytes| Blake2bHash::try_from(bytes.as_slice()).ok())
+        .unwrap_or_else(|| panic!("{}: {:?}", PARSE_STATE_ROOT_EXPECT, hex))
+}
+
+fn get_prune_batch_size(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_PRUNE_BATCH_SIZE)
+        .map(str::parse)
+        .expect(ARG_PRUNE_BATCH_SIZE_EXPECT)
+        .ok()
+        .filter(|batch_size| *batch_size > 0)
+        .expect(ARG_PRUNE_BATCH_SIZE_EXPECT)
+}
+
+fn get_snapshot_chunk_size(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_SNAPSHOT_CHUNK_SIZE)
+        .map(str::parse)
+        .expect(ARG_SNAPSHOT_CHUNK_SIZE_EXPECT)
+        .ok()
+        .filter(|chunk_size| *chunk_size > 0)
+        .expect(ARG_SNAPSHOT_CHUNK_SIZE_EXPECT)
+}
+
 fn get_thread_count(arg_matches: &ArgMatches) -> usize {
     arg_matches
         .value_of(ARG_THREAD_COUNT)
@@ -297,14 +469,24 @@ fn get_thread_count(arg_matches: &ArgMatches) -> usize {
         .expect(ARG_THREAD_COUNT_EXPECT)
 }
 
+fn get_module_cache_capacity(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_MODULE_CACHE_CAPACITY)
+        .map(str::parse)
+        .expect(ARG_MODULE_CACHE_CAPACITY_EXPECT)
+        .expect(ARG_MODULE_CACHE_CAPACITY_EXPECT)
+}
+
 /// Returns an [`EngineConfig`].
 fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let highway = arg_matches.is_present(ARG_HIGHWAY);
+    let module_cache_capacity = get_module_cache_capacity(arg_matches);
     EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_highway(highway)
+        .with_module_cache_capacity(module_cache_capacity)
 }
 
 /// Builds and returns a gRPC server.
@@ -328,6 +510,13 @@ fn get_engine_state(
     map_size: usize,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
+    let global_state = get_global_state(data_dir, map_size);
+
+    EngineState::new(global_state, engine_config)
+}
+
+/// Builds and returns LMDB-backed global state
+fn get_global_state(data_dir: PathBuf, map_size: usize) -> LmdbGlobalState {
     let environment = {
         let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
@@ -345,10 +534,111 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+        .expect(LMDB_GLOBAL_STATE_EXPECT)
+}
 
-    EngineState::new(global_state, engine_config)
+/// Deletes all global state which is unreachable from the state roots to keep
+fn prune_global_state(arg_matches: &ArgMatches) {
+    let data_dir = get_data_dir(arg_matches);
+
+    let map_size = get_map_size(arg_matches);
+
+    let roots_to_keep = get_prune_roots(arg_matches);
+
+    let batch_size = get_prune_batch_size(arg_matches);
+
+    info!(
+        "pruning global state down to {} state roots",
+        roots_to_keep.len()
+    );
+
+    let global_state = get_global_state(data_dir, map_size);
+
+    match global_state.prune(CorrelationId::new(), &roots_to_keep, batch_size) {
+        Ok(PruneResult::Success { kept, deleted }) => {
+            info!("pruned global state; kept: {}, deleted: {}", kept, deleted)
+        }
+        Ok(PruneResult::RootNotFound(root)) => {
+            error!("state root not found, nothing was pruned: {}", root);
+            process::exit(1);
+        }
+        Err(error) => {
+            error!("failed to prune global state: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes a snapshot of the global state under the snapshot root to the export file
+fn export_snapshot(arg_matches: &ArgMatches) {
+    let data_dir = get_data_dir(arg_matches);
+
+    let map_size = get_map_size(arg_matches);
+
+    let state_root = parse_state_root(
+        arg_matches
+            .value_of(ARG_SNAPSHOT_ROOT)
+            .expect("should have snapshot root argument if exporting"),
+    );
+
+    let chunk_size = get_snapshot_chunk_size(arg_matches);
+
+    let path = arg_matches
+        .value_of(ARG_EXPORT_SNAPSHOT)
+        .expect("should have export snapshot argument if exporting");
+
+    info!(
+        "exporting snapshot of state root {} to {}",
+        state_root, path
+    );
+
+    let global_state = get_global_state(data_dir, map_size);
+
+    let mut writer = File::create(path)
+        .map(BufWriter::new)
+        .unwrap_or_else(|_| panic!("{}: {:?}", CREATE_SNAPSHOT_FILE_EXPECT, path));
+
+    match global_state.export_snapshot(CorrelationId::new(), state_root, chunk_size, &mut writer) {
+        Ok(ExportSnapshotResult::Success { leaves }) => {
+            info!("exported snapshot; leaves: {}", leaves)
+        }
+        Ok(ExportSnapshotResult::RootNotFound) => {
+            error!("state root not found, nothing was exported: {}", state_root);
+            process::exit(1);
+        }
+        Err(error) => {
+            error!("failed to export snapshot: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Imports the snapshot in the import file into an empty global state
+fn import_snapshot(arg_matches: &ArgMatches) {
+    let data_dir = get_data_dir(arg_matches);
+
+    let map_size = get_map_size(arg_matches);
+
+    let path = arg_matches
+        .value_of(ARG_IMPORT_SNAPSHOT)
+        .expect("should have import snapshot argument if importing");
+
+    info!("importing snapshot from {}", path);
+
+    let global_state = get_global_state(data_dir, map_size);
+
+    let mut reader = File::open(path)
+        .map(BufReader::new)
+        .unwrap_or_else(|_| panic!("{}: {:?}", OPEN_SNAPSHOT_FILE_EXPECT, path));
+
+    match global_state.import_snapshot(CorrelationId::new(), &mut reader) {
+        Ok(state_root) => info!("imported snapshot of state root {}", state_root),
+        Err(error) => {
+            error!("failed to import snapshot: {}", error);
+            process::exit(1);
+        }
+    }
 }
 
 /// Builds and returns log settings
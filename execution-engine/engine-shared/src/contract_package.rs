@@ -0,0 +1,216 @@
+use types::{
+    bytesrepr::{self, Error, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH},
+    Key,
+};
+
+/// A single version of a contract held in a [`ContractPackage`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ContractVersion {
+    contract_key: Key,
+    is_enabled: bool,
+}
+
+impl ContractVersion {
+    pub fn new(contract_key: Key, is_enabled: bool) -> Self {
+        ContractVersion {
+            contract_key,
+            is_enabled,
+        }
+    }
+
+    /// The key under which this version's contract is stored.
+    pub fn contract_key(&self) -> Key {
+        self.contract_key
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+}
+
+impl ToBytes for ContractVersion {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.contract_key.to_bytes()?);
+        result.append(&mut self.is_enabled.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract_key.serialized_length() + BOOL_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ContractVersion {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (contract_key, rem) = Key::from_bytes(bytes)?;
+        let (is_enabled, rem) = bool::from_bytes(rem)?;
+        Ok((
+            ContractVersion {
+                contract_key,
+                is_enabled,
+            },
+            rem,
+        ))
+    }
+}
+
+/// A stable location holding the ordered versions of a contract.
+///
+/// Versions are numbered from 1 in the order they were added.  Disabled versions are kept so that
+/// they may be re-enabled later, but can't be called.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ContractPackage {
+    versions: Vec<ContractVersion>,
+}
+
+impl ContractPackage {
+    pub fn new(versions: Vec<ContractVersion>) -> Self {
+        ContractPackage { versions }
+    }
+
+    pub fn versions(&self) -> &[ContractVersion] {
+        &self.versions
+    }
+
+    /// Appends an enabled version holding the contract under `contract_key` and returns its
+    /// version number.
+    pub fn add_version(&mut self, contract_key: Key) -> u32 {
+        self.versions.push(ContractVersion::new(contract_key, true));
+        self.versions.len() as u32
+    }
+
+    /// Returns the given version, if it exists.
+    pub fn get_version(&self, version: u32) -> Option<&ContractVersion> {
+        let index = version.checked_sub(1)?;
+        self.versions.get(index as usize)
+    }
+
+    /// Returns the number and contents of the most recently added enabled version, if any.
+    pub fn current_version(&self) -> Option<(u32, &ContractVersion)> {
+        self.versions
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, contract_version)| contract_version.is_enabled())
+            .map(|(index, contract_version)| (index as u32 + 1, contract_version))
+    }
+
+    /// Enables or disables the given version.  Returns `false` if the version doesn't exist.
+    pub fn set_version_enabled(&mut self, version: u32, is_enabled: bool) -> bool {
+        let index = match version.checked_sub(1) {
+            Some(index) => index as usize,
+            None => return false,
+        };
+        match self.versions.get_mut(index) {
+            Some(contract_version) => {
+                contract_version.is_enabled = is_enabled;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl ToBytes for ContractPackage {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.versions.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.versions.serialized_length()
+    }
+}
+
+impl FromBytes for ContractPackage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (versions, rem) = Vec::<ContractVersion>::from_bytes(bytes)?;
+        Ok((ContractPackage { versions }, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection::vec, prelude::*};
+
+    use types::gens::key_arb;
+
+    use super::{ContractPackage, ContractVersion};
+
+    pub fn contract_version_arb() -> impl Strategy<Value = ContractVersion> {
+        (key_arb(), any::<bool>())
+            .prop_map(|(contract_key, is_enabled)| ContractVersion::new(contract_key, is_enabled))
+    }
+
+    pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
+        vec(contract_version_arb(), 0..10).prop_map(ContractPackage::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::{bytesrepr, Key};
+
+    use super::{gens, ContractPackage};
+
+    const CONTRACT_KEY_1: Key = Key::Hash([1; 32]);
+    const CONTRACT_KEY_2: Key = Key::Hash([2; 32]);
+
+    #[test]
+    fn should_number_versions_from_one() {
+        let mut contract_package = ContractPackage::default();
+        assert!(contract_package.current_version().is_none());
+        assert!(contract_package.get_version(0).is_none());
+
+        assert_eq!(contract_package.add_version(CONTRACT_KEY_1), 1);
+        assert_eq!(contract_package.add_version(CONTRACT_KEY_2), 2);
+
+        assert_eq!(
+            contract_package.get_version(1).map(|v| v.contract_key()),
+            Some(CONTRACT_KEY_1)
+        );
+        assert_eq!(
+            contract_package.get_version(2).map(|v| v.contract_key()),
+            Some(CONTRACT_KEY_2)
+        );
+        assert!(contract_package.get_version(3).is_none());
+    }
+
+    #[test]
+    fn should_fall_back_to_latest_enabled_version() {
+        let mut contract_package = ContractPackage::default();
+        contract_package.add_version(CONTRACT_KEY_1);
+        contract_package.add_version(CONTRACT_KEY_2);
+        assert_eq!(
+            contract_package
+                .current_version()
+                .map(|(version, v)| (version, v.contract_key())),
+            Some((2, CONTRACT_KEY_2))
+        );
+
+        assert!(contract_package.set_version_enabled(2, false));
+        assert_eq!(
+            contract_package
+                .current_version()
+                .map(|(version, v)| (version, v.contract_key())),
+            Some((1, CONTRACT_KEY_1))
+        );
+
+        assert!(contract_package.set_version_enabled(1, false));
+        assert!(contract_package.current_version().is_none());
+
+        assert!(contract_package.set_version_enabled(2, true));
+        assert_eq!(contract_package.current_version().map(|(v, _)| v), Some(2));
+
+        assert!(!contract_package.set_version_enabled(0, true));
+        assert!(!contract_package.set_version_enabled(3, true));
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize(contract_package in gens::contract_package_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_package);
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub mod additive_map;
 pub mod gas;
 pub mod account;
 pub mod contract;
+pub mod contract_package;
 pub mod logging;
 pub mod motes;
 pub mod newtypes;
@@ -5,13 +5,16 @@ use types::{
     CLValue,
 };
 
-use crate::{account::Account, contract::Contract, TypeMismatch};
+use crate::{
+    account::Account, contract::Contract, contract_package::ContractPackage, TypeMismatch,
+};
 
 #[repr(u8)]
 enum Tag {
     CLValue = 0,
     Account = 1,
     Contract = 2,
+    ContractPackage = 3,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -19,6 +22,7 @@ pub enum StoredValue {
     CLValue(CLValue),
     Account(Account),
     Contract(Contract),
+    ContractPackage(ContractPackage),
 }
 
 impl StoredValue {
@@ -43,11 +47,19 @@ impl StoredValue {
         }
     }
 
+    pub fn as_contract_package(&self) -> Option<&ContractPackage> {
+        match self {
+            StoredValue::ContractPackage(contract_package) => Some(contract_package),
+            _ => None,
+        }
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             StoredValue::CLValue(cl_value) => format!("{:?}", cl_value.cl_type()),
             StoredValue::Account(_) => "Account".to_string(),
             StoredValue::Contract(_) => "Contract".to_string(),
+            StoredValue::ContractPackage(_) => "ContractPackage".to_string(),
         }
     }
 }
@@ -94,6 +106,20 @@ impl TryFrom<StoredValue> for Contract {
     }
 }
 
+impl TryFrom<StoredValue> for ContractPackage {
+    type Error = TypeMismatch;
+
+    fn try_from(stored_value: StoredValue) -> Result<Self, Self::Error> {
+        match stored_value {
+            StoredValue::ContractPackage(contract_package) => Ok(contract_package),
+            _ => Err(TypeMismatch::new(
+                "ContractPackage".to_string(),
+                stored_value.type_name(),
+            )),
+        }
+    }
+}
+
 impl ToBytes for StoredValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
@@ -101,6 +127,9 @@ impl ToBytes for StoredValue {
             StoredValue::CLValue(cl_value) => (Tag::CLValue, cl_value.to_bytes()?),
             StoredValue::Account(account) => (Tag::Account, account.to_bytes()?),
             StoredValue::Contract(contract) => (Tag::Contract, contract.to_bytes()?),
+            StoredValue::ContractPackage(contract_package) => {
+                (Tag::ContractPackage, contract_package.to_bytes()?)
+            }
         };
         result.push(tag as u8);
         result.append(&mut serialized_data);
@@ -113,6 +142,9 @@ impl ToBytes for StoredValue {
                 StoredValue::CLValue(cl_value) => cl_value.serialized_length(),
                 StoredValue::Account(account) => account.serialized_length(),
                 StoredValue::Contract(contract) => contract.serialized_length(),
+                StoredValue::ContractPackage(contract_package) => {
+                    contract_package.serialized_length()
+                }
             }
     }
 }
@@ -127,6 +159,11 @@ impl FromBytes for StoredValue {
                 .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::Contract as u8 => Contract::from_bytes(remainder)
                 .map(|(contract, remainder)| (StoredValue::Contract(contract), remainder)),
+            tag if tag == Tag::ContractPackage as u8 => {
+                ContractPackage::from_bytes(remainder).map(|(contract_package, remainder)| {
+                    (StoredValue::ContractPackage(contract_package), remainder)
+                })
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -138,13 +175,17 @@ pub mod gens {
     use types::gens::cl_value_arb;
 
     use super::StoredValue;
-    use crate::{account::gens::account_arb, contract::gens::contract_arb};
+    use crate::{
+        account::gens::account_arb, contract::gens::contract_arb,
+        contract_package::gens::contract_package_arb,
+    };
 
     pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
         prop_oneof![
             cl_value_arb().prop_map(StoredValue::CLValue),
             account_arb().prop_map(StoredValue::Account),
             contract_arb().prop_map(StoredValue::Contract),
+            contract_package_arb().prop_map(StoredValue::ContractPackage),
         ]
     }
 }
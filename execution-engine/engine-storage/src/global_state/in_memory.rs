@@ -10,7 +10,7 @@ use types::{Key, ProtocolVersion};
 
 use crate::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{commit, delete_keys, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
     store::Store,
@@ -21,7 +21,10 @@ use crate::{
     trie::{operations::create_hashed_empty_trie, Trie},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        operations::{
+            self, diff, keys_with_prefix, read, read_with_proof, DiffResult, ReadResult,
+            ReadWithProofResult, WriteResult,
+        },
     },
 };
 
@@ -137,6 +140,28 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = keys_with_prefix::<Key, StoredValue, InMemoryReadTransaction, InMemoryTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+            start_after,
+        )
+        .take(limit)
+        .collect::<Result<Vec<Key>, _>>()?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -157,6 +182,48 @@ impl StateProvider for InMemoryGlobalState {
         Ok(maybe_state)
     }
 
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<ReadWithProofResult<Key, StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = read_with_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &prestate_hash,
+            key,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        left_state_hash: Blake2bHash,
+        right_state_hash: Blake2bHash,
+    ) -> Result<DiffResult<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = diff::<Key, StoredValue, InMemoryReadTransaction, InMemoryTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &left_state_hash,
+            &right_state_hash,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn commit(
         &self,
         correlation_id: CorrelationId,
@@ -173,6 +240,22 @@ impl StateProvider for InMemoryGlobalState {
         Ok(commit_result)
     }
 
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        keys: &[Key],
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = delete_keys::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            keys,
+        )?;
+        Ok(commit_result)
+    }
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -201,7 +284,9 @@ impl StateProvider for InMemoryGlobalState {
 
 #[cfg(test)]
 mod tests {
-    use types::{account::PublicKey, CLValue};
+    use types::{account::PublicKey, bytesrepr::ToBytes, CLValue};
+
+    use crate::trie_store::operations::TrieDiff;
 
     use super::*;
 
@@ -263,6 +348,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_with_prefix_from_a_checkout_return_expected_keys() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let test_keys: Vec<Key> = create_test_pairs()
+            .iter()
+            .map(|TestPair { key, .. }| *key)
+            .collect();
+
+        let account_tag = &test_keys[0].to_bytes().unwrap()[..1];
+        assert_eq!(
+            checkout
+                .keys_with_prefix(correlation_id, account_tag, None, usize::max_value())
+                .unwrap(),
+            test_keys
+        );
+
+        let second_key_bytes = test_keys[1].to_bytes().unwrap();
+        assert_eq!(
+            checkout
+                .keys_with_prefix(correlation_id, &second_key_bytes, None, usize::max_value())
+                .unwrap(),
+            vec![test_keys[1]]
+        );
+
+        let hash_key_bytes = Key::Hash([1u8; 32]).to_bytes().unwrap();
+        assert!(checkout
+            .keys_with_prefix(
+                correlation_id,
+                &hash_key_bytes[..1],
+                None,
+                usize::max_value()
+            )
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn keys_with_prefix_from_a_checkout_return_expected_page() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let test_keys: Vec<Key> = create_test_pairs()
+            .iter()
+            .map(|TestPair { key, .. }| *key)
+            .collect();
+
+        let account_tag = &test_keys[0].to_bytes().unwrap()[..1];
+        assert_eq!(
+            checkout
+                .keys_with_prefix(correlation_id, account_tag, None, 1)
+                .unwrap(),
+            vec![test_keys[0]]
+        );
+
+        let first_key_bytes = test_keys[0].to_bytes().unwrap();
+        assert_eq!(
+            checkout
+                .keys_with_prefix(correlation_id, account_tag, Some(&first_key_bytes), 1)
+                .unwrap(),
+            vec![test_keys[1]]
+        );
+
+        let second_key_bytes = test_keys[1].to_bytes().unwrap();
+        assert!(checkout
+            .keys_with_prefix(correlation_id, account_tag, Some(&second_key_bytes), 1)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -271,6 +427,121 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn reads_with_proof_verify_against_the_state_root() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            match state
+                .read_with_proof(correlation_id, root_hash, &key)
+                .unwrap()
+            {
+                ReadWithProofResult::Found(proof) => {
+                    assert_eq!(proof.verify(&root_hash), Ok(Some(&value)))
+                }
+                _ => panic!("should find a proof of inclusion"),
+            }
+        }
+
+        let missing_key = Key::Account(PublicKey::ed25519_from([4u8; 32]));
+        match state
+            .read_with_proof(correlation_id, root_hash, &missing_key)
+            .unwrap()
+        {
+            ReadWithProofResult::NotFound(proof) => assert_eq!(proof.verify(&root_hash), Ok(None)),
+            _ => panic!("should find a proof of exclusion"),
+        }
+    }
+
+    #[test]
+    fn delete_keys_returns_state_without_the_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated[2..]
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let missing_key = Key::Account(PublicKey::ed25519_from([4u8; 32]));
+        let keys = [test_pairs_updated[2].key, missing_key];
+        let deleted_hash = match state
+            .delete_keys(correlation_id, updated_hash, &keys)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("delete failed"),
+        };
+        assert_eq!(deleted_hash, root_hash);
+
+        let deleted_checkout = state.checkout(deleted_hash).unwrap().unwrap();
+        assert_eq!(
+            deleted_checkout
+                .read(correlation_id, &test_pairs_updated[2].key)
+                .unwrap(),
+            None
+        );
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        match state.delete_keys(correlation_id, fake_hash, &keys).unwrap() {
+            CommitResult::RootNotFound => (),
+            _ => panic!("should not find root"),
+        }
+    }
+
+    #[test]
+    fn diff_returns_added_removed_and_changed_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated[1..]
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let expected = TrieDiff {
+            added: vec![test_pairs_updated[2].key],
+            removed: vec![],
+            changed: vec![test_pairs[1].key],
+        };
+        assert_eq!(
+            state.diff(correlation_id, root_hash, updated_hash).unwrap(),
+            DiffResult::Found(expected)
+        );
+
+        let expected = TrieDiff {
+            added: vec![],
+            removed: vec![test_pairs_updated[2].key],
+            changed: vec![test_pairs[1].key],
+        };
+        assert_eq!(
+            state.diff(correlation_id, updated_hash, root_hash).unwrap(),
+            DiffResult::Found(expected)
+        );
+
+        assert_eq!(
+            state.diff(correlation_id, root_hash, root_hash).unwrap(),
+            DiffResult::Found(TrieDiff::default())
+        );
+    }
+
     #[test]
     fn commit_updates_state() {
         let correlation_id = CorrelationId::new();
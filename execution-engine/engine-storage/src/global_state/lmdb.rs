@@ -1,38 +1,61 @@
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Instant};
+
+use lmdb::Cursor;
 
 use engine_shared::{
     additive_map::AdditiveMap,
+    logging::{log_duration, log_metric},
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{commit, delete_keys, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
-    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource, Writable},
     trie::{operations::create_hashed_empty_trie, Trie},
     trie_store::{
+        cache::{CachedTrieStore, TrieCache},
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{
+            diff, keys_with_prefix, reachable, read, read_with_proof, DiffResult, ReachableResult,
+            ReadResult, ReadWithProofResult,
+        },
     },
+    GAUGE_METRIC_KEY,
 };
 
+const GLOBAL_STATE_PRUNE_DURATION: &str = "global_state_prune_duration";
+const GLOBAL_STATE_PRUNE_DELETES: &str = "global_state_prune_deletes";
+const PRUNE: &str = "prune";
+
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
+    pub trie_cache: Arc<TrieCache<Key, StoredValue>>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
+/// Represents the result of pruning the trie store of a global state.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PruneResult {
+    /// One of the state roots to keep does not exist, so nothing was deleted.
+    RootNotFound(Blake2bHash),
+    /// The trie objects reachable from the state roots to keep were kept, and all others deleted.
+    Success { kept: usize, deleted: usize },
+}
+
 /// Represents a "view" of global state at a particular root hash.
 pub struct LmdbGlobalStateView {
     pub environment: Arc<LmdbEnvironment>,
     pub store: Arc<LmdbTrieStore>,
+    pub cache: Arc<TrieCache<Key, StoredValue>>,
     pub root_hash: Blake2bHash,
 }
 
@@ -69,10 +92,126 @@ impl LmdbGlobalState {
         LmdbGlobalState {
             environment,
             trie_store,
+            trie_cache: Arc::new(TrieCache::default()),
             protocol_data_store,
             empty_root_hash,
         }
     }
+
+    /// Returns the trie store, serving reads from the trie cache of this state where possible.
+    fn cached_trie_store(&self) -> CachedTrieStore<Key, StoredValue, LmdbTrieStore> {
+        CachedTrieStore::new(self.trie_store.deref(), self.trie_cache.deref())
+    }
+
+    /// Returns the trie store for a read-write transaction, staging its changes to the trie cache
+    /// of this state until they are applied after a successful commit.
+    fn read_write_trie_store(&self) -> CachedTrieStore<Key, StoredValue, LmdbTrieStore> {
+        CachedTrieStore::read_write(self.trie_store.deref(), self.trie_cache.deref())
+    }
+
+    /// Deletes all trie objects which are not reachable from any of the given state roots, or from
+    /// the empty root of this state.
+    ///
+    /// The reachable objects are collected in a single read transaction, then the store is swept
+    /// in separate read-write transactions each visiting at most `batch_size` stored objects, so
+    /// that the sweep never holds one long write transaction however many objects are kept.  As
+    /// only unreachable objects are deleted, an interrupted prune leaves every kept state
+    /// intact and can simply be run again.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        roots_to_keep: &[Blake2bHash],
+        batch_size: usize,
+    ) -> Result<PruneResult, error::Error> {
+        assert!(batch_size > 0, "batch size must be positive");
+
+        let start = Instant::now();
+
+        let reachable_hashes = {
+            let mut roots = roots_to_keep.to_vec();
+            roots.push(self.empty_root_hash);
+            let txn = self.environment.create_read_txn()?;
+            let result = reachable::<
+                Key,
+                StoredValue,
+                lmdb::RoTransaction,
+                LmdbTrieStore,
+                error::Error,
+            >(correlation_id, &txn, self.trie_store.deref(), &roots)?;
+            txn.commit()?;
+            match result {
+                ReachableResult::Found(reachable_hashes) => reachable_hashes,
+                ReachableResult::RootNotFound(root) => return Ok(PruneResult::RootNotFound(root)),
+            }
+        };
+
+        let db = self.trie_store.db();
+        let mut deleted: usize = 0;
+        let mut maybe_start_key: Option<Vec<u8>> = None;
+
+        loop {
+            let mut txn = self.environment.create_read_write_txn()?;
+
+            let (unreachable, maybe_next_key) = {
+                let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, db)?;
+                let iter = match maybe_start_key {
+                    Some(ref start_key) => cursor.iter_from(start_key),
+                    None => cursor.iter_start(),
+                };
+                let mut unreachable: Vec<Vec<u8>> = Vec::new();
+                let mut maybe_next_key: Option<Vec<u8>> = None;
+                for (visited, (key_bytes, _)) in iter.enumerate() {
+                    if visited == batch_size {
+                        maybe_next_key = Some(key_bytes.to_vec());
+                        break;
+                    }
+                    let hash: Blake2bHash = bytesrepr::deserialize(key_bytes.to_vec())?;
+                    if !reachable_hashes.contains(&hash) {
+                        unreachable.push(key_bytes.to_vec());
+                    }
+                }
+                (unreachable, maybe_next_key)
+            };
+
+            for key_bytes in &unreachable {
+                txn.delete(db, key_bytes)?;
+            }
+            txn.commit()?;
+            self.trie_cache.clear();
+            deleted += unreachable.len();
+
+            match maybe_next_key {
+                Some(next_key) => maybe_start_key = Some(next_key),
+                None => break,
+            }
+        }
+
+        log_metric(
+            correlation_id,
+            GLOBAL_STATE_PRUNE_DELETES,
+            PRUNE,
+            GAUGE_METRIC_KEY,
+            deleted as f64,
+        );
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_PRUNE_DURATION,
+            PRUNE,
+            start.elapsed(),
+        );
+
+        Ok(PruneResult::Success {
+            kept: reachable_hashes.len(),
+            deleted,
+        })
+    }
+}
+
+impl LmdbGlobalStateView {
+    /// Returns the trie store, serving reads from the trie cache of this view where possible.
+    fn cached_trie_store(&self) -> CachedTrieStore<Key, StoredValue, LmdbTrieStore> {
+        CachedTrieStore::new(self.store.deref(), self.cache.deref())
+    }
 }
 
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
@@ -84,10 +223,10 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, _, Self::Error>(
             correlation_id,
             &txn,
-            self.store.deref(),
+            &self.cached_trie_store(),
             &self.root_hash,
             key,
         )? {
@@ -96,6 +235,29 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
             ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
         };
         txn.commit()?;
+        self.cache.log_metrics(correlation_id);
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = keys_with_prefix::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+            start_after,
+        )
+        .take(limit)
+        .collect::<Result<Vec<Key>, _>>()?;
+        txn.commit()?;
         Ok(ret)
     }
 }
@@ -107,29 +269,93 @@ impl StateProvider for LmdbGlobalState {
 
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_root: Option<Trie<Key, StoredValue>> =
+            self.cached_trie_store().get(&txn, &state_hash)?;
         let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
             environment: Arc::clone(&self.environment),
             store: Arc::clone(&self.trie_store),
+            cache: Arc::clone(&self.trie_cache),
             root_hash: state_hash,
         });
         txn.commit()?;
         Ok(maybe_state)
     }
 
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<ReadWithProofResult<Key, StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = read_with_proof::<Key, StoredValue, lmdb::RoTransaction, _, Self::Error>(
+            correlation_id,
+            &txn,
+            &self.cached_trie_store(),
+            &state_hash,
+            key,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        left_state_hash: Blake2bHash,
+        right_state_hash: Blake2bHash,
+    ) -> Result<DiffResult<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = diff::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &left_state_hash,
+            &right_state_hash,
+        )?;
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn commit(
         &self,
         correlation_id: CorrelationId,
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+        let store = self.read_write_trie_store();
+        let commit_result = commit::<LmdbEnvironment, _, _, Self::Error>(
             &self.environment,
-            &self.trie_store,
+            &store,
             correlation_id,
             prestate_hash,
             effects,
         )?;
+        if let CommitResult::Success { .. } = commit_result {
+            store.apply_staged();
+        }
+        self.trie_cache.log_metrics(correlation_id);
+        Ok(commit_result)
+    }
+
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        keys: &[Key],
+    ) -> Result<CommitResult, Self::Error> {
+        let store = self.read_write_trie_store();
+        let commit_result = delete_keys::<LmdbEnvironment, _, Self::Error>(
+            &self.environment,
+            &store,
+            correlation_id,
+            prestate_hash,
+            keys,
+        )?;
+        if let CommitResult::Success { .. } = commit_result {
+            store.apply_staged();
+        }
+        self.trie_cache.log_metrics(correlation_id);
         Ok(commit_result)
     }
 
@@ -167,6 +393,7 @@ mod tests {
     use types::{account::PublicKey, CLValue};
 
     use crate::{
+        global_state::snapshot::{ExportSnapshotResult, SnapshotError},
         trie_store::operations::{write, WriteResult},
         TEST_MAP_SIZE,
     };
@@ -209,8 +436,7 @@ mod tests {
         ]
     }
 
-    fn create_test_state() -> (LmdbGlobalState, Blake2bHash) {
-        let correlation_id = CorrelationId::new();
+    fn create_empty_state() -> LmdbGlobalState {
         let _temp_dir = tempdir().unwrap();
         let environment = Arc::new(
             LmdbEnvironment::new(&_temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap(),
@@ -220,7 +446,12 @@ mod tests {
         let protocol_data_store = Arc::new(
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
         );
-        let ret = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap()
+    }
+
+    fn create_test_state() -> (LmdbGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let ret = create_empty_state();
         let mut current_root = ret.empty_root_hash;
         {
             let mut txn = ret.environment.create_read_write_txn().unwrap();
@@ -259,6 +490,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn repeated_reads_from_a_checkout_are_served_from_the_trie_cache() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        let misses = state.trie_cache.misses();
+        let hits = state.trie_cache.hits();
+        assert!(!state.trie_cache.is_empty());
+
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(state.trie_cache.misses(), misses);
+        assert!(state.trie_cache.hits() > hits);
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -339,4 +589,205 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn delete_keys_returns_state_without_the_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated[2..]
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let missing_key = Key::Account(PublicKey::ed25519_from([4u8; 32]));
+        let keys = [test_pairs_updated[2].key, missing_key];
+        let deleted_hash = match state
+            .delete_keys(correlation_id, updated_hash, &keys)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("delete failed"),
+        };
+        assert_eq!(deleted_hash, root_hash);
+
+        let deleted_checkout = state.checkout(deleted_hash).unwrap().unwrap();
+        assert_eq!(
+            deleted_checkout
+                .read(correlation_id, &test_pairs_updated[2].key)
+                .unwrap(),
+            None
+        );
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        match state.delete_keys(correlation_id, fake_hash, &keys).unwrap() {
+            CommitResult::RootNotFound => (),
+            _ => panic!("should not find root"),
+        }
+    }
+
+    #[test]
+    fn prune_deletes_unreachable_states_only() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        match state.prune(correlation_id, &[updated_hash], 1).unwrap() {
+            PruneResult::Success { deleted, .. } => assert!(deleted > 0),
+            PruneResult::RootNotFound(root) => panic!("root not found: {}", root),
+        }
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        // Pruning again finds nothing left to delete
+        match state.prune(correlation_id, &[updated_hash], 1).unwrap() {
+            PruneResult::Success { deleted, .. } => assert_eq!(deleted, 0),
+            PruneResult::RootNotFound(root) => panic!("root not found: {}", root),
+        }
+    }
+
+    #[test]
+    fn prune_fails_if_unknown_hash_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+
+        let result = state
+            .prune(correlation_id, &[root_hash, fake_hash], 1)
+            .unwrap();
+
+        assert_eq!(result, PruneResult::RootNotFound(fake_hash));
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    fn export_test_snapshot(state: &LmdbGlobalState, root_hash: Blake2bHash) -> Vec<u8> {
+        let correlation_id = CorrelationId::new();
+        let mut snapshot = Vec::new();
+        let result = state
+            .export_snapshot(correlation_id, root_hash, 1, &mut snapshot)
+            .unwrap();
+        assert_eq!(result, ExportSnapshotResult::Success { leaves: 2 });
+        snapshot
+    }
+
+    #[test]
+    fn imported_snapshot_has_same_state_and_protocol_data() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let protocol_data = ProtocolData::default();
+        state
+            .put_protocol_data(protocol_version, &protocol_data)
+            .unwrap();
+
+        let snapshot = export_test_snapshot(&state, root_hash);
+
+        let imported_state = create_empty_state();
+        let imported_root = imported_state
+            .import_snapshot(correlation_id, &mut snapshot.as_slice())
+            .unwrap();
+        assert_eq!(imported_root, root_hash);
+
+        // No intermediate trie objects are left behind
+        match imported_state
+            .prune(correlation_id, &[root_hash], 1)
+            .unwrap()
+        {
+            PruneResult::Success { deleted, .. } => assert_eq!(deleted, 0),
+            PruneResult::RootNotFound(root) => panic!("root not found: {}", root),
+        }
+
+        let checkout = imported_state.checkout(root_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+        assert_eq!(
+            Some(protocol_data),
+            imported_state.get_protocol_data(protocol_version).unwrap()
+        );
+    }
+
+    #[test]
+    fn export_snapshot_fails_if_unknown_hash_is_given() {
+        let correlation_id = CorrelationId::new();
+        let (state, _) = create_test_state();
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        let mut snapshot = Vec::new();
+
+        let result = state
+            .export_snapshot(correlation_id, fake_hash, 1, &mut snapshot)
+            .unwrap();
+
+        assert_eq!(result, ExportSnapshotResult::RootNotFound);
+        assert!(snapshot.is_empty());
+    }
+
+    #[test]
+    fn import_snapshot_fails_on_corrupted_chunk() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let mut snapshot = export_test_snapshot(&state, root_hash);
+        let last_payload_byte = snapshot.len() - 1 - 4 - 32 - 32;
+        snapshot[last_payload_byte] ^= 0xff;
+
+        let imported_state = create_empty_state();
+        let result = imported_state.import_snapshot(correlation_id, &mut snapshot.as_slice());
+
+        match result {
+            Err(SnapshotError::ChecksumMismatch(2)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // The leaf of the first chunk was rolled back, so the state is still empty
+        let snapshot = export_test_snapshot(&state, root_hash);
+        let imported_root = imported_state
+            .import_snapshot(correlation_id, &mut snapshot.as_slice())
+            .unwrap();
+        assert_eq!(imported_root, root_hash);
+    }
+
+    #[test]
+    fn import_snapshot_fails_if_state_is_not_empty() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let snapshot = export_test_snapshot(&state, root_hash);
+
+        let result = state.import_snapshot(correlation_id, &mut snapshot.as_slice());
+
+        match result {
+            Err(SnapshotError::NotEmpty) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
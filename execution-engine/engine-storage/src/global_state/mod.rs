@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod snapshot;
 
 use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
 
@@ -13,12 +14,13 @@ use engine_shared::{
 };
 use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
 
+pub use crate::trie_store::operations::{DiffResult, ReadWithProofResult, TrieDiff};
 use crate::{
     protocol_data::ProtocolData,
     transaction_source::{Transaction, TransactionSource},
     trie::Trie,
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{delete, read, write, DeleteResult, ReadResult, WriteResult},
         TrieStore,
     },
     GAUGE_METRIC_KEY,
@@ -29,7 +31,10 @@ const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
+const GLOBAL_STATE_DELETE_KEYS_DELETES: &str = "global_state_delete_keys_deletes";
+const GLOBAL_STATE_DELETE_KEYS_DURATION: &str = "global_state_delete_keys_duration";
 const COMMIT: &str = "commit";
+const DELETE_KEYS: &str = "delete_keys";
 
 /// A reader of state
 pub trait StateReader<K, V> {
@@ -38,6 +43,17 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns up to `limit` keys whose serialized form starts with the given prefix, ordered by
+    /// their serialized forms.  If `start_after` is given, only keys whose serialized form sorts
+    /// after it are returned, and the keys before it are not visited.
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<K>, Self::Error>;
 }
 
 #[derive(Debug)]
@@ -91,6 +107,24 @@ pub trait StateProvider {
     /// Checkouts to the post state of a specific block.
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error>;
 
+    /// Returns a Merkle proof of the value at the corresponding key, or of its absence, in the
+    /// post state of a specific block.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: &Key,
+    ) -> Result<ReadWithProofResult<Key, StoredValue>, Self::Error>;
+
+    /// Returns the keys which were added, removed or changed between the post states of two
+    /// blocks.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        left_state_hash: Blake2bHash,
+        right_state_hash: Blake2bHash,
+    ) -> Result<DiffResult<Key>, Self::Error>;
+
     /// Applies changes and returns a new post state hash.
     /// block_hash is used for computing a deterministic and unique keys.
     fn commit(
@@ -100,6 +134,14 @@ pub trait StateProvider {
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error>;
 
+    /// Deletes the given keys, skipping any which are absent, and returns a new post state hash.
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        keys: &[Key],
+    ) -> Result<CommitResult, Self::Error>;
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -217,3 +259,64 @@ where
         bonded_validators,
     })
 }
+
+pub fn delete_keys<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    prestate_hash: Blake2bHash,
+    keys: &[Key],
+) -> Result<CommitResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let mut state_root = prestate_hash;
+
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+
+    if maybe_root.is_none() {
+        return Ok(CommitResult::RootNotFound);
+    };
+
+    let start = Instant::now();
+    let mut deletes: i32 = 0;
+
+    for key in keys {
+        match delete::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, key)? {
+            DeleteResult::Deleted(root_hash) => {
+                state_root = root_hash;
+                deletes += 1;
+            }
+            DeleteResult::DoesNotExist => (),
+            _x @ DeleteResult::RootNotFound => panic!(stringify!(_x)),
+        }
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_DELETE_KEYS_DURATION,
+        DELETE_KEYS,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_DELETE_KEYS_DELETES,
+        DELETE_KEYS,
+        GAUGE_METRIC_KEY,
+        f64::from(deletes),
+    );
+
+    let bonded_validators = Default::default();
+
+    Ok(CommitResult::Success {
+        state_root,
+        bonded_validators,
+    })
+}
@@ -0,0 +1,412 @@
+//! Portable snapshots of a global state.
+//!
+//! A snapshot holds every key-value pair of the trie under a given state root, together with the
+//! protocol data of the global state it was exported from.  It allows a new node to bootstrap
+//! from a recent state instead of replaying every block since genesis.
+//!
+//! A snapshot is a sequence of frames.  Each frame is the little-endian `u32` length of its
+//! payload, the payload itself, and the [`Blake2bHash`] of the payload as a checksum.  The first
+//! frame holds the [`SnapshotHeader`].  It is followed by any number of chunks, each holding the
+//! bytesrepr encoding of a `Vec` of [`Trie::Leaf`]s.  An empty frame marks the end of the snapshot.
+use std::{
+    io::{self, Read, Write},
+    ops::Deref,
+    time::Instant,
+};
+
+use failure::Fail;
+use lmdb::Cursor;
+
+use engine_shared::{
+    logging::{log_duration, log_metric},
+    newtypes::{Blake2bHash, CorrelationId, BLAKE2B_DIGEST_LENGTH},
+    stored_value::StoredValue,
+};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    Key, ProtocolVersion,
+};
+
+use crate::{
+    error,
+    global_state::lmdb::LmdbGlobalState,
+    protocol_data::ProtocolData,
+    store::Store,
+    transaction_source::{Transaction, TransactionSource, Writable},
+    trie::Trie,
+    trie_store::{
+        lmdb::LmdbTrieStore,
+        operations::{keys, reachable, read, write, ReachableResult, ReadResult, WriteResult},
+    },
+    GAUGE_METRIC_KEY,
+};
+
+/// Identifies a file as a global state snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"CLSNAPSH";
+
+/// The version of the snapshot format written by [`LmdbGlobalState::export_snapshot`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const GLOBAL_STATE_SNAPSHOT_LEAVES: &str = "global_state_snapshot_leaves";
+const GLOBAL_STATE_EXPORT_SNAPSHOT_DURATION: &str = "global_state_export_snapshot_duration";
+const GLOBAL_STATE_IMPORT_SNAPSHOT_DURATION: &str = "global_state_import_snapshot_duration";
+const EXPORT_SNAPSHOT: &str = "export_snapshot";
+const IMPORT_SNAPSHOT: &str = "import_snapshot";
+
+#[derive(Debug, Fail)]
+pub enum SnapshotError {
+    #[fail(display = "{}", _0)]
+    Io(#[fail(cause)] io::Error),
+
+    #[fail(display = "{}", _0)]
+    Storage(#[fail(cause)] error::Error),
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+
+    #[fail(display = "Not a global state snapshot")]
+    InvalidMagic,
+
+    #[fail(display = "Unsupported snapshot format version: {}", _0)]
+    UnsupportedVersion(u32),
+
+    #[fail(display = "Checksum mismatch in snapshot frame {}", _0)]
+    ChecksumMismatch(usize),
+
+    #[fail(
+        display = "Snapshot frame {} holds something other than trie leaves",
+        _0
+    )]
+    InvalidChunk(usize),
+
+    #[fail(display = "Snapshots can only be imported into an empty global state")]
+    NotEmpty,
+
+    #[fail(
+        display = "Imported state root {} does not match snapshot state root {}",
+        actual, expected
+    )]
+    RootMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error)
+    }
+}
+
+impl From<error::Error> for SnapshotError {
+    fn from(error: error::Error) -> Self {
+        SnapshotError::Storage(error)
+    }
+}
+
+impl From<lmdb::Error> for SnapshotError {
+    fn from(error: lmdb::Error) -> Self {
+        SnapshotError::Storage(error.into())
+    }
+}
+
+impl From<bytesrepr::Error> for SnapshotError {
+    fn from(error: bytesrepr::Error) -> Self {
+        SnapshotError::BytesRepr(error)
+    }
+}
+
+/// The first frame of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub state_root: Blake2bHash,
+    pub protocol_data: Vec<(ProtocolVersion, ProtocolData)>,
+}
+
+impl ToBytes for SnapshotHeader {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.extend_from_slice(&SNAPSHOT_MAGIC);
+        ret.append(&mut SNAPSHOT_FORMAT_VERSION.to_bytes()?);
+        ret.append(&mut self.state_root.to_bytes()?);
+        ret.append(&mut self.protocol_data.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        SNAPSHOT_MAGIC.len()
+            + U32_SERIALIZED_LENGTH
+            + self.state_root.serialized_length()
+            + self.protocol_data.serialized_length()
+    }
+}
+
+impl SnapshotHeader {
+    fn parse(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        if bytes.len() < SNAPSHOT_MAGIC.len() || bytes[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let (version, rem) = u32::from_bytes(&bytes[SNAPSHOT_MAGIC.len()..])?;
+        if version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let (state_root, rem) = Blake2bHash::from_bytes(rem)?;
+        let (protocol_data, rem) = Vec::<(ProtocolVersion, ProtocolData)>::from_bytes(rem)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes.into());
+        }
+        Ok(SnapshotHeader {
+            state_root,
+            protocol_data,
+        })
+    }
+}
+
+/// Represents the result of exporting a snapshot.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExportSnapshotResult {
+    /// The given state root does not exist, so nothing was written.
+    RootNotFound,
+    /// All leaves under the state root were written.
+    Success { leaves: usize },
+}
+
+fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> Result<(), SnapshotError> {
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(payload)?;
+    writer.write_all(&Blake2bHash::new(payload).value())?;
+    Ok(())
+}
+
+/// Reads the next frame, returning its payload once its checksum is verified.  `index` is only
+/// used to report which frame is corrupted.
+fn read_frame<R: Read>(reader: &mut R, index: usize) -> Result<Vec<u8>, SnapshotError> {
+    let mut length_bytes = [0u8; U32_SERIALIZED_LENGTH];
+    reader.read_exact(&mut length_bytes)?;
+    let length = u32::from_le_bytes(length_bytes) as u64;
+
+    // Reading through `take` avoids allocating a buffer of a corrupted length up front.
+    let mut payload = Vec::new();
+    reader.by_ref().take(length).read_to_end(&mut payload)?;
+    if payload.len() as u64 != length {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    let mut checksum = [0u8; BLAKE2B_DIGEST_LENGTH];
+    reader.read_exact(&mut checksum)?;
+    if Blake2bHash::new(&payload) != Blake2bHash::from(checksum) {
+        return Err(SnapshotError::ChecksumMismatch(index));
+    }
+    Ok(payload)
+}
+
+impl LmdbGlobalState {
+    /// Writes a snapshot of the state under `state_root`, along with all protocol data of this
+    /// global state, to `writer`.
+    ///
+    /// The leaves of the trie are written in chunks of at most `chunk_size` leaves each.
+    pub fn export_snapshot<W: Write>(
+        &self,
+        correlation_id: CorrelationId,
+        state_root: Blake2bHash,
+        chunk_size: usize,
+        writer: &mut W,
+    ) -> Result<ExportSnapshotResult, SnapshotError> {
+        assert!(chunk_size > 0, "chunk size must be positive");
+
+        let start = Instant::now();
+        let txn = self.environment.create_read_txn()?;
+
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_root)?;
+        if maybe_root.is_none() {
+            return Ok(ExportSnapshotResult::RootNotFound);
+        }
+
+        let protocol_data = {
+            let mut cursor =
+                lmdb::Transaction::open_ro_cursor(&txn, self.protocol_data_store.handle())?;
+            let mut protocol_data = Vec::new();
+            for (key_bytes, value_bytes) in cursor.iter_start() {
+                let protocol_version: ProtocolVersion = bytesrepr::deserialize(key_bytes.to_vec())?;
+                let data = ProtocolData::from_stored_bytes(value_bytes.to_vec())?;
+                protocol_data.push((protocol_version, data));
+            }
+            protocol_data
+        };
+
+        let header = SnapshotHeader {
+            state_root,
+            protocol_data,
+        };
+        write_frame(writer, &header.to_bytes()?)?;
+
+        let mut leaves = 0;
+        let mut chunk: Vec<Trie<Key, StoredValue>> = Vec::with_capacity(chunk_size);
+        for maybe_key in keys::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &state_root,
+        ) {
+            let key = maybe_key?;
+            let value = match read::<_, _, _, LmdbTrieStore, error::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                &state_root,
+                &key,
+            )? {
+                ReadResult::Found(value) => value,
+                ReadResult::NotFound | ReadResult::RootNotFound => {
+                    panic!("snapshot key {:?} vanished during export", key)
+                }
+            };
+            chunk.push(Trie::leaf(key, value));
+            if chunk.len() == chunk_size {
+                leaves += chunk.len();
+                write_frame(writer, &chunk.to_bytes()?)?;
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            leaves += chunk.len();
+            write_frame(writer, &chunk.to_bytes()?)?;
+        }
+        write_frame(writer, &[])?;
+        writer.flush()?;
+
+        txn.commit()?;
+
+        log_metric(
+            correlation_id,
+            GLOBAL_STATE_SNAPSHOT_LEAVES,
+            EXPORT_SNAPSHOT,
+            GAUGE_METRIC_KEY,
+            leaves as f64,
+        );
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_EXPORT_SNAPSHOT_DURATION,
+            EXPORT_SNAPSHOT,
+            start.elapsed(),
+        );
+
+        Ok(ExportSnapshotResult::Success { leaves })
+    }
+
+    /// Reads a snapshot from `reader` into this global state, which must be empty, and returns its
+    /// state root.
+    ///
+    /// The whole snapshot is imported in a single read-write transaction, which is only committed
+    /// once the state root computed from the imported leaves matches the one recorded in the
+    /// snapshot.  On any error the transaction is aborted, leaving this global state empty.  The
+    /// intermediate trie objects written along the way which are not part of the final state are
+    /// deleted before committing.
+    pub fn import_snapshot<R: Read>(
+        &self,
+        correlation_id: CorrelationId,
+        reader: &mut R,
+    ) -> Result<Blake2bHash, SnapshotError> {
+        let start = Instant::now();
+        let db = self.trie_store.db();
+        let mut txn = self.environment.create_read_write_txn()?;
+
+        let is_empty = {
+            let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, db)?;
+            cursor
+                .iter_start()
+                .all(|(key_bytes, _)| key_bytes == &self.empty_root_hash.value()[..])
+        };
+        if !is_empty {
+            return Err(SnapshotError::NotEmpty);
+        }
+
+        let header = SnapshotHeader::parse(&read_frame(reader, 0)?)?;
+
+        let mut current_root = self.empty_root_hash;
+        let mut leaves = 0;
+        for index in 1.. {
+            let payload = read_frame(reader, index)?;
+            if payload.is_empty() {
+                break;
+            }
+            let chunk: Vec<Trie<Key, StoredValue>> = bytesrepr::deserialize(payload)?;
+
+            for trie in chunk {
+                let (key, value) = match trie {
+                    Trie::Leaf { key, value } => (key, value),
+                    Trie::Node { .. } | Trie::Extension { .. } => {
+                        return Err(SnapshotError::InvalidChunk(index))
+                    }
+                };
+                match write::<_, _, _, LmdbTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    self.trie_store.deref(),
+                    &current_root,
+                    &key,
+                    &value,
+                )? {
+                    WriteResult::Written(root_hash) => current_root = root_hash,
+                    WriteResult::AlreadyExists => (),
+                    WriteResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+                }
+                leaves += 1;
+            }
+        }
+
+        if current_root != header.state_root {
+            return Err(SnapshotError::RootMismatch {
+                expected: header.state_root,
+                actual: current_root,
+            });
+        }
+
+        let reachable_hashes =
+            match reachable::<Key, StoredValue, lmdb::RwTransaction, LmdbTrieStore, error::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                &[current_root, self.empty_root_hash],
+            )? {
+                ReachableResult::Found(reachable_hashes) => reachable_hashes,
+                ReachableResult::RootNotFound(root) => panic!("imported root {} vanished", root),
+            };
+        let unreachable: Vec<Vec<u8>> = {
+            let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, db)?;
+            let mut unreachable = Vec::new();
+            for (key_bytes, _) in cursor.iter_start() {
+                let hash: Blake2bHash = bytesrepr::deserialize(key_bytes.to_vec())?;
+                if !reachable_hashes.contains(&hash) {
+                    unreachable.push(key_bytes.to_vec());
+                }
+            }
+            unreachable
+        };
+        for key_bytes in &unreachable {
+            txn.delete(db, key_bytes)?;
+        }
+
+        for (protocol_version, protocol_data) in &header.protocol_data {
+            self.protocol_data_store
+                .put(&mut txn, protocol_version, protocol_data)?;
+        }
+        txn.commit()?;
+
+        log_metric(
+            correlation_id,
+            GLOBAL_STATE_SNAPSHOT_LEAVES,
+            IMPORT_SNAPSHOT,
+            GAUGE_METRIC_KEY,
+            leaves as f64,
+        );
+        log_duration(
+            correlation_id,
+            GLOBAL_STATE_IMPORT_SNAPSHOT_DURATION,
+            IMPORT_SNAPSHOT,
+            start.elapsed(),
+        );
+
+        Ok(current_root)
+    }
+}
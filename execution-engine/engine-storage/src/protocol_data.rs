@@ -1,10 +1,31 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_wasm_prep::{
+    host_function_costs::{HostFunctionCosts, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH},
+    storage_costs::{StorageCosts, STORAGE_COSTS_SERIALIZED_LENGTH},
+    wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH},
+    wasm_limits::{WasmLimits, WASM_LIMITS_SERIALIZED_LENGTH},
+};
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
+/// The default period in milliseconds of block time after its execution during which a deploy
+/// hash is rejected: one day, the default max TTL of a deploy.
+pub const DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// The version of the layout [`ProtocolData`] is serialized in, which prefixes the serialized
+/// fields.
+const PROTOCOL_DATA_VERSION: u8 = 1;
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = U8_SERIALIZED_LENGTH
+    + WASM_COSTS_SERIALIZED_LENGTH
+    + HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    + STORAGE_COSTS_SERIALIZED_LENGTH
+    + WASM_LIMITS_SERIALIZED_LENGTH
+    + U64_SERIALIZED_LENGTH
+    + 3 * UREF_SERIALIZED_LENGTH;
+/// The length of protocol data serialized before the layout was versioned, which holds only the
+/// Wasm costs and the system contracts' URefs.  It is shorter than any versioned layout.
+const LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
     WASM_COSTS_SERIALIZED_LENGTH + 3 * UREF_SERIALIZED_LENGTH;
 const DEFAULT_UREF_ADDRESS: [u8; 32] = [0; 32];
 
@@ -12,6 +33,10 @@ const DEFAULT_UREF_ADDRESS: [u8; 32] = [0; 32];
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
+    host_function_costs: HostFunctionCosts,
+    storage_costs: StorageCosts,
+    wasm_limits: WasmLimits,
+    deploy_hash_expiry_millis: u64,
     mint: URef,
     proof_of_stake: URef,
     standard_payment: URef,
@@ -25,6 +50,10 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_costs: WasmCosts::default(),
+            host_function_costs: HostFunctionCosts::default(),
+            storage_costs: StorageCosts::default(),
+            wasm_limits: WasmLimits::default(),
+            deploy_hash_expiry_millis: DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
             mint: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             proof_of_stake: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             standard_payment: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
@@ -33,26 +62,49 @@ impl Default for ProtocolData {
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
+    /// Creates a new [`ProtocolData`] value from given [`WasmCosts`], [`HostFunctionCosts`],
+    /// [`StorageCosts`] and [`WasmLimits`] values and deploy hash expiry.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         wasm_costs: WasmCosts,
+        host_function_costs: HostFunctionCosts,
+        storage_costs: StorageCosts,
+        wasm_limits: WasmLimits,
+        deploy_hash_expiry_millis: u64,
         mint: URef,
         proof_of_stake: URef,
         standard_payment: URef,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            deploy_hash_expiry_millis,
             mint,
             proof_of_stake,
             standard_payment,
         }
     }
 
-    /// Creates a new, partially-valid [`ProtocolData`] value where only the mint URef is known.
+    /// Creates a new, partially-valid [`ProtocolData`] value where only the [`WasmLimits`] are
+    /// known.
+    ///
+    /// Used during `commit_genesis` before any system contracts' URefs are known.
+    pub fn partial_with_wasm_limits(wasm_limits: WasmLimits) -> Self {
+        ProtocolData {
+            wasm_limits,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new, partially-valid [`ProtocolData`] value where only the [`WasmLimits`] and the
+    /// mint URef are known.
     ///
     /// Used during `commit_genesis` before all system contracts' URefs are known.
-    pub fn partial_with_mint(mint: URef) -> Self {
+    pub fn partial_with_mint(wasm_limits: WasmLimits, mint: URef) -> Self {
         ProtocolData {
+            wasm_limits,
             mint,
             ..Default::default()
         }
@@ -64,11 +116,17 @@ impl ProtocolData {
     /// Used during `commit_genesis` before all system contracts' URefs are known.
     pub fn partial_without_standard_payment(
         wasm_costs: WasmCosts,
+        host_function_costs: HostFunctionCosts,
+        storage_costs: StorageCosts,
+        wasm_limits: WasmLimits,
         mint: URef,
         proof_of_stake: URef,
     ) -> Self {
         ProtocolData {
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
             mint,
             proof_of_stake,
             ..Default::default()
@@ -80,6 +138,27 @@ impl ProtocolData {
         &self.wasm_costs
     }
 
+    /// Gets the [`HostFunctionCosts`] value from a given [`ProtocolData`] value.
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        &self.host_function_costs
+    }
+
+    /// Gets the [`StorageCosts`] value from a given [`ProtocolData`] value.
+    pub fn storage_costs(&self) -> &StorageCosts {
+        &self.storage_costs
+    }
+
+    /// Gets the [`WasmLimits`] value from a given [`ProtocolData`] value.
+    pub fn wasm_limits(&self) -> &WasmLimits {
+        &self.wasm_limits
+    }
+
+    /// Gets the period in milliseconds of block time after its execution during which a deploy
+    /// hash is rejected as a replay.  Zero disables the rejection.
+    pub fn deploy_hash_expiry_millis(&self) -> u64 {
+        self.deploy_hash_expiry_millis
+    }
+
     pub fn mint(&self) -> URef {
         self.mint
     }
@@ -92,6 +171,26 @@ impl ProtocolData {
         self.standard_payment
     }
 
+    /// Deserializes protocol data stored on its own, either in the current layout or in the
+    /// unversioned layout stored by earlier versions of the engine.  The parameters missing from
+    /// the latter take their default values.
+    pub fn from_stored_bytes(bytes: Vec<u8>) -> Result<Self, bytesrepr::Error> {
+        if bytes.len() != LEGACY_PROTOCOL_DATA_SERIALIZED_LENGTH {
+            return bytesrepr::deserialize(bytes);
+        }
+        let (wasm_costs, rem) = WasmCosts::from_bytes(&bytes)?;
+        let (mint, rem) = URef::from_bytes(rem)?;
+        let (proof_of_stake, rem) = URef::from_bytes(rem)?;
+        let (standard_payment, _) = URef::from_bytes(rem)?;
+        Ok(ProtocolData {
+            wasm_costs,
+            mint,
+            proof_of_stake,
+            standard_payment,
+            ..Default::default()
+        })
+    }
+
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<URef> {
         let mut vec = Vec::with_capacity(3);
@@ -111,7 +210,12 @@ impl ProtocolData {
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut PROTOCOL_DATA_VERSION.to_bytes()?);
         ret.append(&mut self.wasm_costs.to_bytes()?);
+        ret.append(&mut self.host_function_costs.to_bytes()?);
+        ret.append(&mut self.storage_costs.to_bytes()?);
+        ret.append(&mut self.wasm_limits.to_bytes()?);
+        ret.append(&mut self.deploy_hash_expiry_millis.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
@@ -125,13 +229,25 @@ impl ToBytes for ProtocolData {
 
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem) = WasmCosts::from_bytes(bytes)?;
+        let (version, rem) = u8::from_bytes(bytes)?;
+        if version != PROTOCOL_DATA_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (wasm_costs, rem) = WasmCosts::from_bytes(rem)?;
+        let (host_function_costs, rem) = HostFunctionCosts::from_bytes(rem)?;
+        let (storage_costs, rem) = StorageCosts::from_bytes(rem)?;
+        let (wasm_limits, rem) = WasmLimits::from_bytes(rem)?;
+        let (deploy_hash_expiry_millis, rem) = u64::from_bytes(rem)?;
         let (mint, rem) = URef::from_bytes(rem)?;
         let (proof_of_stake, rem) = URef::from_bytes(rem)?;
         let (standard_payment, rem) = URef::from_bytes(rem)?;
         Ok((
             ProtocolData {
                 wasm_costs,
+                host_function_costs,
+                storage_costs,
+                wasm_limits,
+                deploy_hash_expiry_millis,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -143,9 +259,13 @@ impl FromBytes for ProtocolData {
 
 #[cfg(test)]
 pub(crate) mod gens {
-    use proptest::prop_compose;
+    use proptest::{num, prop_compose};
 
-    use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
+    use engine_wasm_prep::{
+        host_function_costs::gens as host_function_costs_gens,
+        storage_costs::gens as storage_costs_gens, wasm_costs::gens as wasm_costs_gens,
+        wasm_limits::gens as wasm_limits_gens,
+    };
     use types::gens;
 
     use super::ProtocolData;
@@ -153,12 +273,20 @@ pub(crate) mod gens {
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
+            host_function_costs in host_function_costs_gens::host_function_costs_arb(),
+            storage_costs in storage_costs_gens::storage_costs_arb(),
+            wasm_limits in wasm_limits_gens::wasm_limits_arb(),
+            deploy_hash_expiry_millis in num::u64::ANY,
             mint in gens::uref_arb(),
             proof_of_stake in gens::uref_arb(),
             standard_payment in gens::uref_arb(),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
+                host_function_costs,
+                storage_costs,
+                wasm_limits,
+                deploy_hash_expiry_millis,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -171,10 +299,20 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, AccessRights, URef};
+    use engine_wasm_prep::{
+        host_function_costs::{HostFunctionCost, HostFunctionCosts},
+        storage_costs::StorageCosts,
+        wasm_costs::WasmCosts,
+        wasm_limits::WasmLimits,
+    };
+    use types::{
+        bytesrepr::{self, ToBytes},
+        AccessRights, URef,
+    };
+
+    use super::{gens, ProtocolData, DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS};
 
-    use super::{gens, ProtocolData};
+    const DEPLOY_HASH_EXPIRY_MILLIS: u64 = 60 * 60 * 1000;
 
     fn wasm_costs_mock() -> WasmCosts {
         WasmCosts {
@@ -191,6 +329,15 @@ mod tests {
         }
     }
 
+    fn host_function_costs_mock() -> HostFunctionCosts {
+        HostFunctionCosts {
+            write: HostFunctionCost::new(1_000, 10),
+            new_uref: HostFunctionCost::new(1_000, 10),
+            call_contract: HostFunctionCost::new(5_000, 1),
+            ..Default::default()
+        }
+    }
+
     fn wasm_costs_free() -> WasmCosts {
         WasmCosts {
             regular: 0,
@@ -210,11 +357,16 @@ mod tests {
     fn should_serialize_and_deserialize() {
         let mock = {
             let costs = wasm_costs_mock();
+            let host_function_costs = host_function_costs_mock();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
             let standard_payment_reference = URef::new([2u8; 32], AccessRights::READ_ADD_WRITE);
             ProtocolData::new(
                 costs,
+                host_function_costs,
+                StorageCosts::new(1),
+                WasmLimits::default(),
+                DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -222,11 +374,16 @@ mod tests {
         };
         let free = {
             let costs = wasm_costs_free();
+            let host_function_costs = HostFunctionCosts::default();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
             let standard_payment_reference = URef::new([2u8; 32], AccessRights::READ_ADD_WRITE);
             ProtocolData::new(
                 costs,
+                host_function_costs,
+                StorageCosts::default(),
+                WasmLimits::default(),
+                DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -236,6 +393,55 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_deserialize_stored_protocol_data() {
+        let protocol_data = ProtocolData::new(
+            wasm_costs_mock(),
+            host_function_costs_mock(),
+            StorageCosts::new(1),
+            WasmLimits::new(128, 16, 1024),
+            DEPLOY_HASH_EXPIRY_MILLIS,
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([1u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([2u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let bytes = protocol_data.to_bytes().expect("should serialize");
+        assert_eq!(ProtocolData::from_stored_bytes(bytes), Ok(protocol_data));
+    }
+
+    #[test]
+    fn should_deserialize_legacy_protocol_data() {
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let standard_payment_reference = URef::new([2u8; 32], AccessRights::READ_ADD_WRITE);
+        let legacy_bytes = {
+            let mut bytes = wasm_costs_mock().to_bytes().expect("should serialize");
+            bytes.append(&mut mint_reference.to_bytes().expect("should serialize"));
+            bytes.append(
+                &mut proof_of_stake_reference
+                    .to_bytes()
+                    .expect("should serialize"),
+            );
+            bytes.append(
+                &mut standard_payment_reference
+                    .to_bytes()
+                    .expect("should serialize"),
+            );
+            bytes
+        };
+        let expected = ProtocolData::new(
+            wasm_costs_mock(),
+            HostFunctionCosts::default(),
+            StorageCosts::default(),
+            WasmLimits::default(),
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
+            mint_reference,
+            proof_of_stake_reference,
+            standard_payment_reference,
+        );
+        assert_eq!(ProtocolData::from_stored_bytes(legacy_bytes), Ok(expected));
+    }
+
     #[test]
     fn should_return_all_system_contracts() {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
@@ -243,8 +449,13 @@ mod tests {
         let standard_payment_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = wasm_costs_mock();
+            let host_function_costs = host_function_costs_mock();
             ProtocolData::new(
                 costs,
+                host_function_costs,
+                StorageCosts::new(1),
+                WasmLimits::default(),
+                DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -272,8 +483,13 @@ mod tests {
         let standard_payment_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = wasm_costs_mock();
+            let host_function_costs = host_function_costs_mock();
             ProtocolData::new(
                 costs,
+                host_function_costs,
+                StorageCosts::new(1),
+                WasmLimits::default(),
+                DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
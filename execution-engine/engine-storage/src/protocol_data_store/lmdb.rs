@@ -1,12 +1,12 @@
 use lmdb::{Database, DatabaseFlags};
-use types::ProtocolVersion;
+use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use crate::{
     error,
     protocol_data::ProtocolData,
     protocol_data_store::{self, ProtocolDataStore},
     store::Store,
-    transaction_source::lmdb::LmdbEnvironment,
+    transaction_source::{lmdb::LmdbEnvironment, Readable},
 };
 
 /// An LMDB-backed protocol data store.
@@ -49,6 +49,19 @@ impl Store<ProtocolVersion, ProtocolData> for LmdbProtocolDataStore {
     fn handle(&self) -> Self::Handle {
         self.db
     }
+
+    /// Gets the protocol data at the given protocol version, which may have been stored before its
+    /// layout was versioned.
+    fn get<T>(&self, txn: &T, key: &ProtocolVersion) -> Result<Option<ProtocolData>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Self::Error: From<T::Error>,
+    {
+        match txn.read(self.handle(), &key.to_bytes()?)? {
+            None => Ok(None),
+            Some(value_bytes) => Ok(Some(ProtocolData::from_stored_bytes(value_bytes)?)),
+        }
+    }
 }
 
 impl ProtocolDataStore for LmdbProtocolDataStore {}
@@ -42,4 +42,14 @@ pub trait Store<K, V> {
         txn.write(handle, &key.to_bytes()?, &value.to_bytes()?)
             .map_err(Into::into)
     }
+
+    fn delete<T>(&self, txn: &mut T, key: &K) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        K: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        let handle = self.handle();
+        txn.delete(handle, &key.to_bytes()?).map_err(Into::into)
+    }
 }
@@ -0,0 +1,127 @@
+//! Merkle proofs of inclusion or exclusion of a key in a Merkle Trie
+
+use failure::Fail;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, ToBytes};
+
+use crate::trie::Trie;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum TrieMerkleProofError {
+    #[fail(display = "Proof has no steps")]
+    Empty,
+
+    #[fail(display = "Proof step {} does not match the hash pointing to it", _0)]
+    HashMismatch(usize),
+
+    #[fail(display = "Proof ends before the lookup of the key terminates")]
+    Incomplete,
+
+    #[fail(display = "Proof continues after the lookup of the key terminates")]
+    TrailingSteps,
+
+    #[fail(display = "{}", _0)]
+    BytesRepr(#[fail(cause)] bytesrepr::Error),
+}
+
+impl From<bytesrepr::Error> for TrieMerkleProofError {
+    fn from(error: bytesrepr::Error) -> Self {
+        TrieMerkleProofError::BytesRepr(error)
+    }
+}
+
+/// A proof that a key is present in, or absent from, the trie under a given root.
+///
+/// The proof consists of the trie objects visited when looking up the key, ordered from the root
+/// down to the object where the lookup terminates. The key is present if the last object is a
+/// leaf holding the key. Otherwise the last object shows the key to be absent: it is either a
+/// leaf holding a different key, a node without a pointer at the key's next index, or an
+/// extension whose affix does not match the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    steps: Vec<Trie<K, V>>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    pub fn new(key: K, steps: Vec<Trie<K, V>>) -> Self {
+        TrieMerkleProof { key, steps }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn steps(&self) -> &[Trie<K, V>] {
+        &self.steps
+    }
+
+    pub fn take_steps(self) -> Vec<Trie<K, V>> {
+        self.steps
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Eq,
+    V: ToBytes,
+{
+    /// Checks the proof against the given root by recomputing the hash of each step.
+    ///
+    /// Returns the value stored under the key if the proof shows its inclusion, or `None` if the
+    /// proof shows its absence.
+    pub fn verify(&self, root: &Blake2bHash) -> Result<Option<&V>, TrieMerkleProofError> {
+        if self.steps.is_empty() {
+            return Err(TrieMerkleProofError::Empty);
+        }
+
+        let path: Vec<u8> = self.key.to_bytes()?;
+        let last_index = self.steps.len() - 1;
+        let mut expected_hash: Blake2bHash = *root;
+        let mut depth: usize = 0;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let step_bytes = step.to_bytes()?;
+            if Blake2bHash::new(&step_bytes) != expected_hash {
+                return Err(TrieMerkleProofError::HashMismatch(index));
+            }
+
+            let next_pointer = match step {
+                Trie::Leaf { key, value } => {
+                    if index != last_index {
+                        return Err(TrieMerkleProofError::TrailingSteps);
+                    }
+                    // Paths are compressed, so the leaf under the key's path may hold a
+                    // different key, in which case the key is absent.
+                    return Ok(if *key == self.key { Some(value) } else { None });
+                }
+                Trie::Node { pointer_block } => {
+                    let maybe_pointer = path
+                        .get(depth)
+                        .and_then(|path_index| pointer_block[usize::from(*path_index)]);
+                    depth += 1;
+                    maybe_pointer
+                }
+                Trie::Extension { affix, pointer } => {
+                    let sub_path = path.get(depth..depth + affix.len());
+                    depth += affix.len();
+                    if sub_path == Some(affix.as_slice()) {
+                        Some(*pointer)
+                    } else {
+                        None
+                    }
+                }
+            };
+
+            match next_pointer {
+                Some(pointer) if index != last_index => expected_hash = *pointer.hash(),
+                Some(_) => return Err(TrieMerkleProofError::Incomplete),
+                None if index != last_index => return Err(TrieMerkleProofError::TrailingSteps),
+                None => return Ok(None),
+            }
+        }
+
+        unreachable!("the last step should terminate the lookup")
+    }
+}
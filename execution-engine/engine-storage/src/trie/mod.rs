@@ -3,6 +3,8 @@
 use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
 use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
 
+pub mod merkle_proof;
+
 #[cfg(test)]
 pub mod gens;
 
@@ -0,0 +1,370 @@
+//! A size-bounded, least-recently-used cache of deserialized [`Trie`] values.
+//!
+//! Reading a trie object from a [`TrieStore`] deserializes it with `FromBytes` every time, which
+//! dominates the cost of reads for the upper levels of a trie, as they are visited by almost every
+//! read and write.  [`CachedTrieStore`] wraps a [`TrieStore`] and serves its `get`s from a shared
+//! [`TrieCache`] where possible.
+//!
+//! As trie objects are stored at the hash of their serialized form, a cached value can never be
+//! stale.  Deleting a trie object through a [`CachedTrieStore`] evicts it from the cache; trie
+//! objects deleted by other means should be followed by a call to [`TrieCache::clear`].
+//!
+//! A read-write transaction may still be aborted, so a store created with
+//! [`CachedTrieStore::read_write`] only stages the values it reads, writes and deletes.  They reach
+//! the cache through [`CachedTrieStore::apply_staged`] once the transaction has been committed.
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    mem,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use parking_lot::Mutex;
+
+use engine_shared::{
+    logging::log_metric,
+    newtypes::{Blake2bHash, CorrelationId},
+};
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::{
+    store::Store,
+    transaction_source::{Readable, Writable},
+    trie::{PointerBlock, Trie},
+    trie_store::TrieStore,
+    GAUGE_METRIC_KEY,
+};
+
+/// The default maximum size in bytes of a [`TrieCache`].
+pub const DEFAULT_TRIE_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+const TRIE_CACHE_HITS: &str = "trie_cache_hits";
+const TRIE_CACHE_MISSES: &str = "trie_cache_misses";
+const TRIE_CACHE_SIZE: &str = "trie_cache_size";
+const TRIE_CACHE: &str = "trie_cache";
+
+struct Entry<K, V> {
+    trie: Trie<K, V>,
+    size: usize,
+    last_used: u64,
+}
+
+struct Entries<K, V> {
+    entries: HashMap<Blake2bHash, Entry<K, V>>,
+    recency: BTreeMap<u64, Blake2bHash>,
+    size: usize,
+    tick: u64,
+}
+
+impl<K, V> Entries<K, V> {
+    fn new() -> Self {
+        Entries {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            size: 0,
+            tick: 0,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, hash: &Blake2bHash) {
+        if let Some(entry) = self.entries.remove(hash) {
+            self.recency.remove(&entry.last_used);
+            self.size -= entry.size;
+        }
+    }
+}
+
+/// A thread-safe cache of deserialized [`Trie`] values keyed by their hashes.
+///
+/// The cache holds at most `max_size` bytes, as estimated by [`TrieCache::estimate_size`], and
+/// evicts the least recently used values first.
+pub struct TrieCache<K, V> {
+    max_size: usize,
+    entries: Mutex<Entries<K, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K, V> TrieCache<K, V> {
+    pub fn new(max_size: usize) -> Self {
+        TrieCache {
+            max_size,
+            entries: Mutex::new(Entries::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the estimated size in bytes of the cached values.
+    pub fn size(&self) -> usize {
+        self.entries.lock().size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which were served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups which were not served from the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Estimates the memory used by a deserialized trie value from the length of its serialized
+    /// form.  Nodes always allocate a full pointer block, however sparse their serialized form.
+    pub fn estimate_size(trie: &Trie<K, V>, serialized_length: usize) -> usize {
+        let heap_size = match trie {
+            Trie::Node { .. } => mem::size_of::<PointerBlock>(),
+            Trie::Leaf { .. } | Trie::Extension { .. } => serialized_length,
+        };
+        mem::size_of::<Blake2bHash>() + mem::size_of::<Entry<K, V>>() + heap_size
+    }
+
+    /// Inserts a value of the given estimated size, evicting the least recently used values as
+    /// needed.  Values larger than the cache are not inserted.
+    pub fn insert(&self, hash: Blake2bHash, trie: Trie<K, V>, size: usize) {
+        if size > self.max_size {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        entries.remove(&hash);
+        while entries.size + size > self.max_size {
+            let oldest = match entries.recency.values().next() {
+                Some(oldest) => *oldest,
+                None => break,
+            };
+            entries.remove(&oldest);
+        }
+        let last_used = entries.next_tick();
+        entries.recency.insert(last_used, hash);
+        entries.size += size;
+        entries.entries.insert(
+            hash,
+            Entry {
+                trie,
+                size,
+                last_used,
+            },
+        );
+    }
+
+    pub fn remove(&self, hash: &Blake2bHash) {
+        self.entries.lock().remove(hash)
+    }
+
+    pub fn clear(&self) {
+        *self.entries.lock() = Entries::new();
+    }
+
+    /// Logs the cumulative hit and miss counts, and the current size of the cache.
+    pub fn log_metrics(&self, correlation_id: CorrelationId) {
+        log_metric(
+            correlation_id,
+            TRIE_CACHE_HITS,
+            TRIE_CACHE,
+            GAUGE_METRIC_KEY,
+            self.hits() as f64,
+        );
+        log_metric(
+            correlation_id,
+            TRIE_CACHE_MISSES,
+            TRIE_CACHE,
+            GAUGE_METRIC_KEY,
+            self.misses() as f64,
+        );
+        log_metric(
+            correlation_id,
+            TRIE_CACHE_SIZE,
+            TRIE_CACHE,
+            GAUGE_METRIC_KEY,
+            self.size() as f64,
+        );
+    }
+}
+
+impl<K: Clone, V: Clone> TrieCache<K, V> {
+    /// Returns a copy of the cached value, marking it as the most recently used.
+    pub fn get(&self, hash: &Blake2bHash) -> Option<Trie<K, V>> {
+        let mut entries = self.entries.lock();
+        let last_used = entries.next_tick();
+        let maybe_trie = match entries.entries.get_mut(hash) {
+            Some(entry) => {
+                let previously_used = mem::replace(&mut entry.last_used, last_used);
+                Some((previously_used, entry.trie.clone()))
+            }
+            None => None,
+        };
+        match maybe_trie {
+            Some((previously_used, trie)) => {
+                entries.recency.remove(&previously_used);
+                entries.recency.insert(last_used, *hash);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(trie)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+}
+
+impl<K, V> Default for TrieCache<K, V> {
+    fn default() -> Self {
+        TrieCache::new(DEFAULT_TRIE_CACHE_SIZE)
+    }
+}
+
+/// A change to a [`TrieCache`] made within a read-write transaction.
+enum StagedChange<K, V> {
+    Insert(Blake2bHash, Trie<K, V>, usize),
+    Remove(Blake2bHash),
+}
+
+/// A [`TrieStore`] which serves reads from a [`TrieCache`] before falling back to the wrapped
+/// store.
+pub struct CachedTrieStore<'a, K, V, S> {
+    store: &'a S,
+    cache: &'a TrieCache<K, V>,
+    staged: Option<RefCell<Vec<StagedChange<K, V>>>>,
+}
+
+impl<'a, K, V, S> CachedTrieStore<'a, K, V, S> {
+    /// Creates a store for use with read transactions, whose reads populate the cache directly.
+    pub fn new(store: &'a S, cache: &'a TrieCache<K, V>) -> Self {
+        CachedTrieStore {
+            store,
+            cache,
+            staged: None,
+        }
+    }
+
+    /// Creates a store for use with a read-write transaction, whose changes to the cache are
+    /// staged until [`CachedTrieStore::apply_staged`] is called.
+    pub fn read_write(store: &'a S, cache: &'a TrieCache<K, V>) -> Self {
+        CachedTrieStore {
+            store,
+            cache,
+            staged: Some(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Applies the staged changes to the cache.  This must only be called once the read-write
+    /// transaction they were made in has been committed.
+    pub fn apply_staged(self) {
+        let staged = match self.staged {
+            Some(staged) => staged.into_inner(),
+            None => return,
+        };
+        for change in staged {
+            match change {
+                StagedChange::Insert(hash, trie, size) => self.cache.insert(hash, trie, size),
+                StagedChange::Remove(hash) => self.cache.remove(&hash),
+            }
+        }
+    }
+
+    fn insert(&self, hash: Blake2bHash, trie: Trie<K, V>, size: usize) {
+        match &self.staged {
+            Some(staged) => staged
+                .borrow_mut()
+                .push(StagedChange::Insert(hash, trie, size)),
+            None => self.cache.insert(hash, trie, size),
+        }
+    }
+
+    fn remove(&self, hash: &Blake2bHash) {
+        match &self.staged {
+            Some(staged) => staged.borrow_mut().push(StagedChange::Remove(*hash)),
+            None => self.cache.remove(hash),
+        }
+    }
+}
+
+impl<'a, K, V, S> Store<Blake2bHash, Trie<K, V>> for CachedTrieStore<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Store<Blake2bHash, Trie<K, V>>,
+{
+    type Error = S::Error;
+
+    type Handle = S::Handle;
+
+    fn handle(&self) -> Self::Handle {
+        self.store.handle()
+    }
+
+    fn get<T>(&self, txn: &T, key: &Blake2bHash) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(trie) = self.cache.get(key) {
+            return Ok(Some(trie));
+        }
+        let handle = self.handle();
+        match txn.read(handle, &key.to_bytes()?)? {
+            None => Ok(None),
+            Some(trie_bytes) => {
+                let serialized_length = trie_bytes.len();
+                let trie: Trie<K, V> = bytesrepr::deserialize(trie_bytes)?;
+                let size = TrieCache::estimate_size(&trie, serialized_length);
+                self.insert(*key, trie.clone(), size);
+                Ok(Some(trie))
+            }
+        }
+    }
+
+    fn put<T>(&self, txn: &mut T, key: &Blake2bHash, value: &Trie<K, V>) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.store.put(txn, key, value)?;
+        if self.staged.is_some() {
+            let size = TrieCache::estimate_size(value, value.serialized_length());
+            self.insert(*key, value.clone(), size);
+        }
+        Ok(())
+    }
+
+    fn delete<T>(&self, txn: &mut T, key: &Blake2bHash) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.remove(key);
+        self.store.delete(txn, key)
+    }
+}
+
+impl<'a, K, V, S> TrieStore<K, V> for CachedTrieStore<'a, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: TrieStore<K, V>,
+{
+}
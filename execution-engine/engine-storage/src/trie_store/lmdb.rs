@@ -141,6 +141,10 @@ impl LmdbTrieStore {
         Ok(LmdbTrieStore { db })
     }
 
+    pub fn db(&self) -> Database {
+        self.db
+    }
+
     fn name(maybe_name: Option<&str>) -> String {
         maybe_name
             .map(|name| format!("{}-{}", trie_store::NAME, name))
@@ -2,6 +2,7 @@
 //!
 //! See the [in_memory](in_memory/index.html#usage) and
 //! [lmdb](lmdb/index.html#usage) modules for usage examples.
+pub mod cache;
 pub mod in_memory;
 pub mod lmdb;
 pub(crate) mod operations;
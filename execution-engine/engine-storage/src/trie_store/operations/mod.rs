@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests;
 
-use std::{mem, time::Instant};
+use std::{cmp, collections::HashSet, mem, time::Instant};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
@@ -11,21 +11,32 @@ use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    trie::{self, merkle_proof::TrieMerkleProof, Parents, Pointer, Trie, RADIX},
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
 
+const TRIE_STORE_DIFF_DURATION: &str = "trie_store_diff_duration";
+const TRIE_STORE_DIFF_GETS: &str = "trie_store_diff_gets";
 const TRIE_STORE_READ_DURATION: &str = "trie_store_read_duration";
 const TRIE_STORE_READ_GETS: &str = "trie_store_read_gets";
+const TRIE_STORE_READ_WITH_PROOF_DURATION: &str = "trie_store_read_with_proof_duration";
+const TRIE_STORE_REACHABLE_DURATION: &str = "trie_store_reachable_duration";
+const TRIE_STORE_REACHABLE_GETS: &str = "trie_store_reachable_gets";
 const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DELETE_PUTS: &str = "trie_store_delete_puts";
+const DIFF: &str = "diff";
 const READ: &str = "read";
+const READ_WITH_PROOF: &str = "read_with_proof";
+const REACHABLE: &str = "reachable";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
+const DELETE: &str = "delete";
 const PUT: &str = "put";
 
 #[derive(Debug, PartialEq, Eq)]
@@ -199,6 +210,94 @@ where
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReadWithProofResult<K, V> {
+    /// The key is present, and the proof shows its inclusion.
+    Found(TrieMerkleProof<K, V>),
+    /// The key is absent, and the proof shows its exclusion.
+    NotFound(TrieMerkleProof<K, V>),
+    RootNotFound,
+}
+
+/// Returns a Merkle proof of the value at the corresponding key, or of its absence, at a given
+/// root in a given store
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadWithProofResult<K, V>, E>
+where
+    K: ToBytes + FromBytes + Eq + Clone + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadWithProofResult::RootNotFound),
+    };
+
+    let start = Instant::now();
+    let mut depth: usize = 0;
+    let mut steps: Vec<Trie<K, V>> = Vec::new();
+
+    let found = loop {
+        let maybe_pointer: Option<Pointer> = match &current {
+            // Keys may not match in the case of a compressed path from a Node directly to a Leaf
+            Trie::Leaf { key: leaf_key, .. } => break *key == *leaf_key,
+            Trie::Node { pointer_block } => {
+                let maybe_pointer = path
+                    .get(depth)
+                    .and_then(|index| pointer_block[usize::from(*index)]);
+                depth += 1;
+                maybe_pointer
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = path.get(depth..depth + affix.len());
+                depth += affix.len();
+                if sub_path == Some(affix.as_slice()) {
+                    Some(*pointer)
+                } else {
+                    None
+                }
+            }
+        };
+        let pointer = match maybe_pointer {
+            Some(pointer) => pointer,
+            None => break false,
+        };
+        match store.get(txn, pointer.hash())? {
+            Some(next) => steps.push(mem::replace(&mut current, next)),
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?})",
+                pointer.hash(),
+                key
+            ),
+        }
+    };
+    steps.push(current);
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_READ_WITH_PROOF_DURATION,
+        READ_WITH_PROOF,
+        start.elapsed(),
+    );
+
+    let proof = TrieMerkleProof::new(key.clone(), steps);
+    if found {
+        Ok(ReadWithProofResult::Found(proof))
+    } else {
+        Ok(ReadWithProofResult::NotFound(proof))
+    }
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -731,6 +830,141 @@ where
     }
 }
 
+/// Pops the last of the parents if it is an extension, returning its affix.
+fn pop_extension_affix<K, V>(parents: &mut Parents<K, V>) -> Option<Vec<u8>> {
+    match parents.last() {
+        Some((_, Trie::Extension { .. })) => (),
+        _ => return None,
+    }
+    match parents.pop() {
+        Some((_, Trie::Extension { affix, .. })) => Some(affix),
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Deletes the leaf with the given key from the trie at the given root.
+///
+/// The resulting trie is the same as if the leaf had never been written: a node other than the
+/// root which is left with a single child is replaced by that child, and extensions which end up
+/// adjacent to each other are merged.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut put_counter: i32 = 0;
+
+    let current_root = match store.get(txn, root)? {
+        Some(current_root) => current_root,
+        None => return Ok(DeleteResult::RootNotFound),
+    };
+    let path: Vec<u8> = key.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+    match tip {
+        Trie::Leaf {
+            key: ref leaf_key, ..
+        } if key == leaf_key => (),
+        _ => return Ok(DeleteResult::DoesNotExist),
+    }
+
+    // Remove the pointer to the leaf from its parent node.
+    let mut pointer_block = match parents.pop() {
+        Some((index, Trie::Node { mut pointer_block })) => {
+            pointer_block[usize::from(index)] = None;
+            pointer_block
+        }
+        _ => panic!("A leaf should have a node for its parent"),
+    };
+    let remaining_children: Vec<(usize, Pointer)> = pointer_block[..]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, maybe_pointer)| maybe_pointer.map(|pointer| (index, pointer)))
+        .collect();
+
+    let new_tip: Trie<K, V> = if parents.is_empty() || remaining_children.len() > 1 {
+        // The root is a node however many children it has.
+        Trie::Node { pointer_block }
+    } else {
+        let (child_index, child_pointer) = match remaining_children.as_slice() {
+            [child] => *child,
+            _ => panic!("A node other than the root should have at least two children"),
+        };
+        match child_pointer {
+            // The remaining leaf replaces the node along with any extension above it.
+            Pointer::LeafPointer(leaf_hash) => {
+                pop_extension_affix(&mut parents);
+                match store.get(txn, &leaf_hash)? {
+                    Some(leaf) => leaf,
+                    None => panic!("No trie value at key: {:?}", leaf_hash),
+                }
+            }
+            // The node becomes an extension to the remaining node, merged with any extension
+            // above or below it.
+            Pointer::NodePointer(child_hash) => {
+                let child: Trie<K, V> = match store.get(txn, &child_hash)? {
+                    Some(child) => child,
+                    None => panic!("No trie value at key: {:?}", child_hash),
+                };
+                let mut affix = pop_extension_affix(&mut parents).unwrap_or_default();
+                affix.push(child_index as u8);
+                let pointer = match child {
+                    Trie::Extension {
+                        affix: child_affix,
+                        pointer,
+                    } => {
+                        affix.extend(child_affix);
+                        pointer
+                    }
+                    Trie::Node { .. } => child_pointer,
+                    Trie::Leaf { .. } => panic!("A node pointer should not point to a leaf"),
+                };
+                Trie::extension(affix, pointer)
+            }
+        }
+    };
+
+    let new_elements = rehash(new_tip, parents)?;
+    let mut root_hash = root.to_owned();
+    for (hash, element) in new_elements.iter() {
+        put_counter += 1;
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DELETE_PUTS,
+        PUT,
+        GAUGE_METRIC_KEY,
+        f64::from(put_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
@@ -746,6 +980,31 @@ pub struct KeysIterator<'a, 'b, K, V, T, S: TrieStore<K, V>> {
     store: &'a S,
     txn: &'b T,
     state: KeysIteratorState<K, V, S>,
+    prefix: Vec<u8>,
+    start_after: Option<Vec<u8>>,
+}
+
+impl<'a, 'b, K, V, T, S: TrieStore<K, V>> KeysIterator<'a, 'b, K, V, T, S> {
+    /// Returns the byte of `start_after` at the depth of `path` if `path` leads to `start_after`,
+    /// i.e. if the children of `path` before that byte hold only keys at or before `start_after`.
+    fn start_after_byte(&self, path: &[u8]) -> Option<u8> {
+        let start_after = self.start_after.as_ref()?;
+        if start_after.starts_with(path) {
+            start_after.get(path.len()).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if every key under the given extension sorts at or before `start_after`.
+    fn is_before_start(&self, path: &[u8], affix: &[u8]) -> bool {
+        let start_after = match self.start_after.as_ref() {
+            Some(start_after) if start_after.starts_with(path) => &start_after[path.len()..],
+            _ => return false,
+        };
+        let len = cmp::min(affix.len(), start_after.len());
+        affix[..len] < start_after[..len]
+    }
 }
 
 impl<'a, 'b, K, V, T, S> Iterator for KeysIterator<'a, 'b, K, V, T, S>
@@ -774,21 +1033,37 @@ where
 
             match trie {
                 Trie::Leaf { key, .. } => {
-                    debug_assert!({
-                        let key_bytes = match key.to_bytes() {
-                            Ok(bytes) => bytes,
-                            Err(e) => {
-                                self.state = KeysIteratorState::Failed;
-                                return Some(Err(e.into()));
-                            }
-                        };
-                        key_bytes.starts_with(&path)
-                    });
-                    return Some(Ok(key));
+                    let key_bytes = match key.to_bytes() {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            self.state = KeysIteratorState::Failed;
+                            return Some(Err(e.into()));
+                        }
+                    };
+                    debug_assert!(key_bytes.starts_with(&path));
+                    // A leaf may be found above the depth of the prefix
+                    let is_after_start = match self.start_after {
+                        Some(ref start_after) => key_bytes > *start_after,
+                        None => true,
+                    };
+                    if key_bytes.starts_with(&self.prefix) && is_after_start {
+                        return Some(Ok(key));
+                    }
                 }
                 Trie::Node { ref pointer_block } => {
-                    let mut index: usize = maybe_index.unwrap_or_default();
-                    while index < RADIX {
+                    // Below the prefix, every child is visited.  Above it, only the child on the
+                    // path of the prefix is.
+                    let (start, end) = match self.prefix.get(path.len()) {
+                        Some(prefix_byte) => (*prefix_byte as usize, *prefix_byte as usize + 1),
+                        None => (0, RADIX),
+                    };
+                    // On the path to the key to start after, the children before it are skipped.
+                    let start = match self.start_after_byte(&path) {
+                        Some(start_after_byte) => cmp::max(start, start_after_byte as usize),
+                        None => start,
+                    };
+                    let mut index: usize = maybe_index.unwrap_or(start);
+                    while index < end {
                         if let Some(ref pointer) = pointer_block[index] {
                             maybe_next_trie = match self.store.get(self.txn, pointer.hash()) {
                                 Ok(trie) => trie,
@@ -806,6 +1081,13 @@ where
                     }
                 }
                 Trie::Extension { affix, pointer } => {
+                    let matches_prefix = affix
+                        .iter()
+                        .zip(self.prefix.iter().skip(path.len()))
+                        .all(|(affix_byte, prefix_byte)| affix_byte == prefix_byte);
+                    if !matches_prefix || self.is_before_start(&path, &affix) {
+                        continue;
+                    }
                     maybe_next_trie = match self.store.get(self.txn, pointer.hash()) {
                         Ok(trie) => trie,
                         Err(e) => {
@@ -836,12 +1118,36 @@ where
 /// Notes:
 /// * The root doesn't necessarily need to be the apex of the trie. It can be the "root" of a
 ///   sub-trie.
-#[allow(dead_code)]
 pub fn keys<'a, 'b, K, V, T, S>(
+    correlation_id: CorrelationId,
+    txn: &'b T,
+    store: &'a S,
+    root: &Blake2bHash,
+) -> KeysIterator<'a, 'b, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+{
+    keys_with_prefix(correlation_id, txn, store, root, &[], None)
+}
+
+/// Returns the iterator over the keys at a given root hash whose serialized form starts with a
+/// given prefix.
+///
+/// Only the branch of the trie matching the prefix is descended, and keys are returned in the
+/// order of their serialized forms.  If `start_after` is given, the iteration seeks past it
+/// without visiting the keys before it, and only keys whose serialized form sorts after it are
+/// returned.
+pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
     store: &'a S,
     root: &Blake2bHash,
+    prefix: &[u8],
+    start_after: Option<&[u8]>,
 ) -> KeysIterator<'a, 'b, K, V, T, S>
 where
     K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
@@ -863,5 +1169,281 @@ where
         store,
         txn,
         state: init_state,
+        prefix: prefix.to_vec(),
+        start_after: start_after.map(<[u8]>::to_vec),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReachableResult {
+    Found(HashSet<Blake2bHash>),
+    RootNotFound(Blake2bHash),
+}
+
+/// Returns the hashes of all trie objects reachable from any of the given roots in a given store,
+/// including the roots themselves.
+///
+/// Leaves are not read from the store, as they point to no further objects.
+pub fn reachable<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    roots: &[Blake2bHash],
+) -> Result<ReachableResult, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut reachable: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<Blake2bHash> = roots.to_vec();
+
+    while let Some(hash) = pending.pop() {
+        if reachable.contains(&hash) {
+            continue;
+        }
+        let trie: Trie<K, V> = match store.get(txn, &hash)? {
+            Some(trie) => trie,
+            None if roots.contains(&hash) => return Ok(ReachableResult::RootNotFound(hash)),
+            None => panic!("No trie value at key: {:?}", hash),
+        };
+        get_counter += 1;
+        reachable.insert(hash);
+
+        let pointers: Vec<Pointer> = match trie {
+            Trie::Leaf { .. } => vec![],
+            Trie::Node { pointer_block } => pointer_block[..].iter().flatten().cloned().collect(),
+            Trie::Extension { pointer, .. } => vec![pointer],
+        };
+        for pointer in pointers {
+            match pointer {
+                Pointer::LeafPointer(leaf_hash) => {
+                    reachable.insert(leaf_hash);
+                }
+                Pointer::NodePointer(node_hash) => pending.push(node_hash),
+            }
+        }
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_REACHABLE_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_REACHABLE_DURATION,
+        REACHABLE,
+        start.elapsed(),
+    );
+
+    Ok(ReachableResult::Found(reachable))
+}
+
+/// The keys whose values differ between two tries.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TrieDiff<K> {
+    /// Keys which only exist in the right trie.
+    pub added: Vec<K>,
+    /// Keys which only exist in the left trie.
+    pub removed: Vec<K>,
+    /// Keys which exist in both tries, with different values.
+    pub changed: Vec<K>,
+}
+
+impl<K> Default for TrieDiff<K> {
+    fn default() -> Self {
+        TrieDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult<K> {
+    Found(TrieDiff<K>),
+    RootNotFound(Blake2bHash),
+}
+
+/// A subtrie at some depth of a trie being diffed.
+enum Subtrie<K, V> {
+    /// A trie object in the store.
+    Stored(Blake2bHash),
+    /// A trie object which is not in the store, being the remainder of an extension whose affix
+    /// has been partially descended, or an already loaded leaf.
+    Loaded(Trie<K, V>),
+}
+
+/// The depth of, and the subtries on either side of, a part of the tries being diffed.
+type PendingDiff<K, V> = (usize, Option<Subtrie<K, V>>, Option<Subtrie<K, V>>);
+
+fn load_subtrie<K, V, T, S>(
+    txn: &T,
+    store: &S,
+    maybe_subtrie: Option<Subtrie<K, V>>,
+    get_counter: &mut i32,
+) -> Result<Option<Trie<K, V>>, S::Error>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+{
+    match maybe_subtrie {
+        Some(Subtrie::Stored(hash)) => {
+            *get_counter += 1;
+            match store.get(txn, &hash)? {
+                Some(trie) => Ok(Some(trie)),
+                None => panic!("No trie value at key: {:?}", hash),
+            }
+        }
+        Some(Subtrie::Loaded(trie)) => Ok(Some(trie)),
+        None => Ok(None),
     }
 }
+
+/// Returns the subtries below each possible next key byte of a trie found at a given depth.
+fn diff_children<K, V>(
+    trie: Trie<K, V>,
+    depth: usize,
+) -> Result<Vec<Option<Subtrie<K, V>>>, bytesrepr::Error>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    let mut children: Vec<Option<Subtrie<K, V>>> = (0..RADIX).map(|_| None).collect();
+    match trie {
+        Trie::Leaf { ref key, .. } => {
+            let index = key.to_bytes()?[depth] as usize;
+            children[index] = Some(Subtrie::Loaded(trie));
+        }
+        Trie::Node { pointer_block } => {
+            for (index, maybe_pointer) in pointer_block[..].iter().enumerate() {
+                if let Some(pointer) = maybe_pointer {
+                    children[index] = Some(Subtrie::Stored(*pointer.hash()));
+                }
+            }
+        }
+        Trie::Extension { affix, pointer } => {
+            let child = if affix.len() == 1 {
+                Subtrie::Stored(*pointer.hash())
+            } else {
+                Subtrie::Loaded(Trie::extension(affix[1..].to_vec(), pointer))
+            };
+            children[affix[0] as usize] = Some(child);
+        }
+    }
+    Ok(children)
+}
+
+/// Returns the keys which were added, removed or changed between the trie under `left_root` and
+/// the trie under `right_root` in a given store.
+///
+/// Both tries are descended side by side, one key byte at a time, and subtries whose hashes are
+/// equal on both sides are skipped without being read.
+pub fn diff<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    left_root: &Blake2bHash,
+    right_root: &Blake2bHash,
+) -> Result<DiffResult<K>, E>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let mut trie_diff = TrieDiff::default();
+    let mut pending: Vec<PendingDiff<K, V>> = Vec::new();
+
+    for root in &[left_root, right_root] {
+        if store.get(txn, root)?.is_none() {
+            return Ok(DiffResult::RootNotFound(**root));
+        }
+    }
+    pending.push((
+        0,
+        Some(Subtrie::Stored(*left_root)),
+        Some(Subtrie::Stored(*right_root)),
+    ));
+
+    while let Some((depth, left, right)) = pending.pop() {
+        if let (Some(Subtrie::Stored(left_hash)), Some(Subtrie::Stored(right_hash))) =
+            (&left, &right)
+        {
+            if left_hash == right_hash {
+                continue;
+            }
+        }
+
+        let left = load_subtrie(txn, store, left, &mut get_counter)?;
+        let right = load_subtrie(txn, store, right, &mut get_counter)?;
+
+        if left == right {
+            continue;
+        }
+
+        match (left, right) {
+            (Some(Trie::Leaf { key: left_key, .. }), Some(Trie::Leaf { key: right_key, .. })) => {
+                if left_key == right_key {
+                    trie_diff.changed.push(left_key);
+                } else {
+                    trie_diff.removed.push(left_key);
+                    trie_diff.added.push(right_key);
+                }
+            }
+            (Some(Trie::Leaf { key, .. }), None) => trie_diff.removed.push(key),
+            (None, Some(Trie::Leaf { key, .. })) => trie_diff.added.push(key),
+            (left, right) => {
+                let left_children = match left {
+                    Some(trie) => diff_children(trie, depth)?,
+                    None => (0..RADIX).map(|_| None).collect(),
+                };
+                let right_children = match right {
+                    Some(trie) => diff_children(trie, depth)?,
+                    None => (0..RADIX).map(|_| None).collect(),
+                };
+                // Pushed in reverse so that subtries are diffed in key order
+                for (left_child, right_child) in left_children.into_iter().zip(right_children).rev()
+                {
+                    if left_child.is_some() || right_child.is_some() {
+                        pending.push((depth + 1, left_child, right_child));
+                    }
+                }
+            }
+        }
+    }
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DIFF_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DIFF_DURATION,
+        DIFF,
+        start.elapsed(),
+    );
+
+    Ok(DiffResult::Found(trie_diff))
+}
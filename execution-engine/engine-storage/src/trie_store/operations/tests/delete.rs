@@ -0,0 +1,257 @@
+use super::*;
+use crate::trie_store::operations::{delete, DeleteResult};
+
+fn delete_keys<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    keys: &[K],
+) -> Result<Vec<DeleteResult>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut results = Vec::new();
+    let mut root_hash = root_hash.to_owned();
+    let mut txn = environment.create_read_write_txn()?;
+
+    for key in keys.iter() {
+        let delete_result =
+            delete::<K, V, _, _, E>(correlation_id, &mut txn, store, &root_hash, key)?;
+        if let DeleteResult::Deleted(hash) = delete_result {
+            root_hash = hash;
+        }
+        results.push(delete_result);
+    }
+    txn.commit()?;
+    Ok(results)
+}
+
+/// Writes the given leaves to the empty trie and returns the resulting root hash.
+fn write_to_empty_trie<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    leaves: &[TestTrie],
+) -> Result<Blake2bHash, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let results =
+        write_leaves::<_, _, _, _, E>(correlation_id, environment, store, empty_root_hash, leaves)?;
+    let root_hash = results
+        .into_iter()
+        .filter_map(|result| match result {
+            WriteResult::Written(root_hash) => Some(root_hash),
+            _ => None,
+        })
+        .last()
+        .unwrap_or(*empty_root_hash);
+    Ok(root_hash)
+}
+
+fn deleting_each_leaf_had_expected_results<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    test_leaves: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let root_hash = write_to_empty_trie::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        test_leaves,
+    )?;
+    let keys: Vec<TestKey> = test_leaves.iter().filter_map(Trie::key).cloned().collect();
+
+    // Deleting a leaf results in the same trie as never writing it
+    for (index, deleted_leaf) in test_leaves.iter().enumerate() {
+        let results = delete_keys::<_, _, _, _, E>(
+            correlation_id,
+            environment,
+            store,
+            &root_hash,
+            &keys[index..=index],
+        )?;
+        let deleted_root_hash = match results.as_slice() {
+            [DeleteResult::Deleted(root_hash)] => *root_hash,
+            _ => panic!("expected the leaf to be deleted"),
+        };
+
+        let remaining_leaves: Vec<TestTrie> = test_leaves
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, leaf)| leaf.to_owned())
+            .collect();
+        let expected_root_hash = write_to_empty_trie::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            empty_root_hash,
+            &remaining_leaves,
+        )?;
+        assert_eq!(deleted_root_hash, expected_root_hash);
+
+        check_leaves::<_, _, _, _, E>(
+            correlation_id,
+            environment,
+            store,
+            &deleted_root_hash,
+            &remaining_leaves,
+            &[deleted_leaf.to_owned()],
+        )?;
+    }
+
+    // Deleting every leaf results in the empty trie
+    let results =
+        delete_keys::<_, _, _, _, E>(correlation_id, environment, store, &root_hash, &keys)?;
+    assert!(results.iter().all(|result| match result {
+        DeleteResult::Deleted(_) => true,
+        _ => false,
+    }));
+    assert_eq!(
+        results.last(),
+        Some(&DeleteResult::Deleted(*empty_root_hash))
+    );
+
+    Ok(())
+}
+
+fn all_test_leaves() -> Vec<TestTrie> {
+    TEST_LEAVES
+        .iter()
+        .chain(TEST_LEAVES_ADJACENTS.iter())
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn lmdb_deleting_each_leaf_of_n_leaf_trie_had_expected_results() {
+    for num_leaves in 1..=TEST_LEAVES_LENGTH {
+        let correlation_id = CorrelationId::new();
+        let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        deleting_each_leaf_had_expected_results::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &empty_root_hash,
+            &TEST_LEAVES[..num_leaves],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_deleting_each_leaf_of_n_leaf_trie_had_expected_results() {
+    for num_leaves in 1..=TEST_LEAVES_LENGTH {
+        let correlation_id = CorrelationId::new();
+        let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        deleting_each_leaf_had_expected_results::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &empty_root_hash,
+            &TEST_LEAVES[..num_leaves],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn lmdb_deleting_each_leaf_of_trie_with_adjacent_leaves_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    deleting_each_leaf_had_expected_results::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        &all_test_leaves(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_each_leaf_of_trie_with_adjacent_leaves_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    deleting_each_leaf_had_expected_results::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        &all_test_leaves(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_missing_key_does_not_exist() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_LEAVES_LENGTH]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_keys: Vec<TestKey> = TEST_LEAVES_ADJACENTS
+        .iter()
+        .filter_map(Trie::key)
+        .cloned()
+        .collect();
+
+    let results = delete_keys::<_, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+        &missing_keys,
+    )
+    .unwrap();
+
+    assert!(results
+        .iter()
+        .all(|result| *result == DeleteResult::DoesNotExist));
+}
+
+#[test]
+fn in_memory_deleting_from_missing_root_is_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root_hash = Blake2bHash::new(b"missing root");
+    let key = TEST_LEAVES[0].key().cloned().unwrap();
+
+    let results = delete_keys::<_, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &missing_root_hash,
+        &[key],
+    )
+    .unwrap();
+
+    assert_eq!(results, vec![DeleteResult::RootNotFound]);
+}
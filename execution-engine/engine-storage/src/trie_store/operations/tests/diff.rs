@@ -0,0 +1,174 @@
+//! This module contains tests for [`diff`].
+//!
+//! Diffs are taken between the partial tries used in the tests for [`read`], and between the full
+//! trie and an updated version of it.
+
+use super::*;
+use crate::trie_store::operations::{diff, DiffResult, TrieDiff};
+
+fn sorted_keys(leaves: &[TestTrie]) -> Vec<TestKey> {
+    let mut ret: Vec<TestKey> = leaves.iter().filter_map(Trie::key).cloned().collect();
+    ret.sort();
+    ret
+}
+
+fn sorted_diff(result: DiffResult<TestKey>) -> TrieDiff<TestKey> {
+    match result {
+        DiffResult::Found(mut trie_diff) => {
+            trie_diff.added.sort();
+            trie_diff.removed.sort();
+            trie_diff.changed.sort();
+            trie_diff
+        }
+        DiffResult::RootNotFound(root) => panic!("root not found: {:?}", root),
+    }
+}
+
+fn check_diffs<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    roots: &[Blake2bHash],
+    updated_root: &Blake2bHash,
+    num_updated: usize,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let full_root = &roots[TEST_LEAVES_LENGTH];
+
+    for (num_leaves, root) in roots.iter().enumerate() {
+        let missing = sorted_keys(&TEST_LEAVES[num_leaves..]);
+
+        let added = sorted_diff(diff::<_, _, _, _, E>(
+            correlation_id,
+            &txn,
+            store,
+            root,
+            full_root,
+        )?);
+        assert_eq!(
+            added,
+            TrieDiff {
+                added: missing.clone(),
+                ..Default::default()
+            }
+        );
+
+        let removed = sorted_diff(diff::<_, _, _, _, E>(
+            correlation_id,
+            &txn,
+            store,
+            full_root,
+            root,
+        )?);
+        assert_eq!(
+            removed,
+            TrieDiff {
+                removed: missing,
+                ..Default::default()
+            }
+        );
+    }
+
+    let changed = sorted_diff(diff::<_, _, _, _, E>(
+        correlation_id,
+        &txn,
+        store,
+        full_root,
+        updated_root,
+    )?);
+    assert_eq!(
+        changed,
+        TrieDiff {
+            changed: sorted_keys(&TEST_LEAVES_UPDATED[..num_updated]),
+            ..Default::default()
+        }
+    );
+
+    let fake_root: Blake2bHash = [1u8; 32].into();
+    let result = diff::<_, _, _, _, E>(correlation_id, &txn, store, full_root, &fake_root)?;
+    assert_eq!(result, DiffResult::RootNotFound(fake_root));
+
+    txn.commit()?;
+    Ok(())
+}
+
+fn create_all_partial_tries() -> (Vec<Blake2bHash>, Vec<HashedTestTrie>) {
+    let mut roots = Vec::new();
+    let mut tries = Vec::new();
+    for generator in TEST_TRIE_GENERATORS.iter() {
+        let (root_hash, mut generated) = generator().unwrap();
+        roots.push(root_hash);
+        tries.append(&mut generated);
+    }
+    (roots, tries)
+}
+
+#[test]
+fn lmdb_diffs_between_n_leaf_partial_tries_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let num_updated = 3;
+    let (roots, tries) = create_all_partial_tries();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    let updated_root = match write_leaves::<_, _, _, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &roots[TEST_LEAVES_LENGTH],
+        &TEST_LEAVES_UPDATED[..num_updated],
+    )
+    .unwrap()
+    .last()
+    {
+        Some(WriteResult::Written(root_hash)) => *root_hash,
+        _ => panic!("should have written updated leaves"),
+    };
+
+    check_diffs::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &roots,
+        &updated_root,
+        num_updated,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diffs_between_n_leaf_partial_tries_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let num_updated = 3;
+    let (roots, tries) = create_all_partial_tries();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let updated_root = match write_leaves::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &roots[TEST_LEAVES_LENGTH],
+        &TEST_LEAVES_UPDATED[..num_updated],
+    )
+    .unwrap()
+    .last()
+    {
+        Some(WriteResult::Written(root_hash)) => *root_hash,
+        _ => panic!("should have written updated leaves"),
+    };
+
+    check_diffs::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &roots,
+        &updated_root,
+        num_updated,
+    )
+    .unwrap();
+}
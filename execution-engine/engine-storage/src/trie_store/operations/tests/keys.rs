@@ -86,6 +86,159 @@ mod partial_tries {
     }
 }
 
+mod prefixed_partial_tries {
+    use engine_shared::newtypes::CorrelationId;
+    use types::bytesrepr::ToBytes;
+
+    use crate::{
+        transaction_source::{Transaction, TransactionSource},
+        trie::Trie,
+        trie_store::operations::{
+            self,
+            tests::{
+                InMemoryTestContext, LmdbTestContext, TestKey, TestValue, TEST_LEAVES,
+                TEST_TRIE_GENERATORS,
+            },
+        },
+    };
+
+    const TEST_PREFIXES: [&[u8]; 6] = [&[], &[0], &[0, 0, 0], &[0, 0, 2], &[0, 1], &[1]];
+    const TEST_START_AFTERS: [&[u8]; 6] = [
+        &[0],
+        &[0, 0, 0, 0, 0, 0, 0],
+        &[0, 0, 0, 0, 0, 0, 1],
+        &[0, 0, 0, 1],
+        &[0, 0, 2, 0, 0, 0, 0],
+        &[0, 1, 0, 0, 0, 0, 0],
+    ];
+
+    fn expected_keys(num_leaves: usize, prefix: &[u8]) -> Vec<TestKey> {
+        let test_leaves = TEST_LEAVES;
+        let (used, _) = test_leaves.split_at(num_leaves);
+        let mut ret = used
+            .iter()
+            .filter_map(Trie::key)
+            .filter(|key| key.to_bytes().unwrap().starts_with(prefix))
+            .cloned()
+            .collect::<Vec<TestKey>>();
+        ret.sort();
+        ret
+    }
+
+    #[test]
+    fn lmdb_keys_with_prefix_from_n_leaf_partial_trie_had_expected_results() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+
+            for prefix in TEST_PREFIXES.iter() {
+                let txn = context.environment.create_read_txn().unwrap();
+                let actual = operations::keys_with_prefix::<TestKey, TestValue, _, _>(
+                    correlation_id,
+                    &txn,
+                    &context.store,
+                    &root_hash,
+                    prefix,
+                    None,
+                )
+                .collect::<Result<Vec<TestKey>, _>>()
+                .unwrap();
+                txn.commit().unwrap();
+                // Keys are returned in order
+                assert_eq!(actual, expected_keys(num_leaves, prefix));
+            }
+        }
+    }
+
+    fn expected_keys_after(num_leaves: usize, prefix: &[u8], start_after: &[u8]) -> Vec<TestKey> {
+        expected_keys(num_leaves, prefix)
+            .into_iter()
+            .filter(|key| key.to_bytes().unwrap().as_slice() > start_after)
+            .collect()
+    }
+
+    #[test]
+    fn lmdb_keys_with_prefix_after_key_from_n_leaf_partial_trie_had_expected_results() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+
+            for prefix in TEST_PREFIXES.iter() {
+                for start_after in TEST_START_AFTERS.iter() {
+                    let txn = context.environment.create_read_txn().unwrap();
+                    let actual = operations::keys_with_prefix::<TestKey, TestValue, _, _>(
+                        correlation_id,
+                        &txn,
+                        &context.store,
+                        &root_hash,
+                        prefix,
+                        Some(start_after),
+                    )
+                    .collect::<Result<Vec<TestKey>, _>>()
+                    .unwrap();
+                    txn.commit().unwrap();
+                    assert_eq!(actual, expected_keys_after(num_leaves, prefix, start_after));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn in_memory_keys_with_prefix_after_key_from_n_leaf_partial_trie_had_expected_results() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+
+            for prefix in TEST_PREFIXES.iter() {
+                for start_after in TEST_START_AFTERS.iter() {
+                    let txn = context.environment.create_read_txn().unwrap();
+                    let actual = operations::keys_with_prefix::<TestKey, TestValue, _, _>(
+                        correlation_id,
+                        &txn,
+                        &context.store,
+                        &root_hash,
+                        prefix,
+                        Some(start_after),
+                    )
+                    .collect::<Result<Vec<TestKey>, _>>()
+                    .unwrap();
+                    txn.commit().unwrap();
+                    assert_eq!(actual, expected_keys_after(num_leaves, prefix, start_after));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn in_memory_keys_with_prefix_from_n_leaf_partial_trie_had_expected_results() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = generator().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+
+            for prefix in TEST_PREFIXES.iter() {
+                let txn = context.environment.create_read_txn().unwrap();
+                let actual = operations::keys_with_prefix::<TestKey, TestValue, _, _>(
+                    correlation_id,
+                    &txn,
+                    &context.store,
+                    &root_hash,
+                    prefix,
+                    None,
+                )
+                .collect::<Result<Vec<TestKey>, _>>()
+                .unwrap();
+                txn.commit().unwrap();
+                // Keys are returned in order
+                assert_eq!(actual, expected_keys(num_leaves, prefix));
+            }
+        }
+    }
+}
+
 mod full_tries {
     use engine_shared::newtypes::{Blake2bHash, CorrelationId};
 
@@ -1,7 +1,10 @@
+mod delete;
+mod diff;
 mod ee_699;
 mod keys;
 mod proptests;
 mod read;
+mod read_with_proof;
 mod scan;
 mod write;
 
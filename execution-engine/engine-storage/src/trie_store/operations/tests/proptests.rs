@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use std::{collections::BTreeMap, ops::RangeInclusive};
 
 use proptest::{
     array,
@@ -76,6 +76,67 @@ fn in_memory_roundtrip_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
     .unwrap()
 }
 
+/// Writes `pairs`, then deletes every other key, and checks that the result is the same trie as
+/// writing only the remaining keys.
+fn in_memory_delete_matches_never_writing(pairs: &[(TestKey, TestValue)]) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let final_pairs: BTreeMap<TestKey, TestValue> = pairs.iter().cloned().collect();
+    let (deleted_keys, remaining_pairs): (Vec<_>, Vec<_>) = final_pairs
+        .into_iter()
+        .enumerate()
+        .partition(|(index, _)| index % 2 == 0);
+    let deleted_keys: Vec<TestKey> = deleted_keys.into_iter().map(|(_, (key, _))| key).collect();
+    let remaining_pairs: Vec<(TestKey, TestValue)> =
+        remaining_pairs.into_iter().map(|(_, pair)| pair).collect();
+
+    let written_root_hash = write_pairs::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        pairs,
+    )
+    .unwrap()
+    .last()
+    .cloned()
+    .unwrap_or(empty_root_hash);
+
+    let mut root_hash = written_root_hash;
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    for key in deleted_keys.iter() {
+        match operations::delete::<_, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &root_hash,
+            key,
+        )
+        .unwrap()
+        {
+            operations::DeleteResult::Deleted(hash) => root_hash = hash,
+            _ => return false,
+        }
+    }
+    txn.commit().unwrap();
+
+    let expected_root_hash = write_pairs::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        &remaining_pairs,
+    )
+    .unwrap()
+    .last()
+    .cloned()
+    .unwrap_or(empty_root_hash);
+
+    root_hash == expected_root_hash
+}
+
 fn test_key_arb() -> impl Strategy<Value = TestKey> {
     array::uniform7(any::<u8>()).prop_map(TestKey)
 }
@@ -90,6 +151,11 @@ proptest! {
         assert!(in_memory_roundtrip_succeeds(&inputs));
     }
 
+    #[test]
+    fn prop_in_memory_delete_matches_never_writing(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
+        assert!(in_memory_delete_matches_never_writing(&inputs));
+    }
+
     #[test]
     fn prop_lmdb_roundtrip_succeeds(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
         assert!(lmdb_roundtrip_succeeds(&inputs));
@@ -0,0 +1,180 @@
+//! This module contains tests for [`read_with_proof`] and [`TrieMerkleProof::verify`].
+//!
+//! Proofs are read from the same partial tries as in the tests for [`read`], and checked against
+//! the roots of those tries.
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofError},
+    trie_store::operations::{read_with_proof, ReadWithProofResult},
+};
+
+fn check_proofs<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    present: &[TestTrie],
+    absent: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+
+    for leaf in present {
+        if let Trie::Leaf { key, value } = leaf {
+            match read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+                ReadWithProofResult::Found(proof) => {
+                    assert_eq!(proof.verify(root), Ok(Some(value)))
+                }
+                _ => panic!("expected a proof of inclusion for {:?}", key),
+            }
+        } else {
+            panic!("leaves should only contain leaves")
+        }
+    }
+
+    for leaf in absent {
+        if let Trie::Leaf { key, .. } = leaf {
+            match read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+                ReadWithProofResult::NotFound(proof) => assert_eq!(proof.verify(root), Ok(None)),
+                _ => panic!("expected a proof of exclusion for {:?}", key),
+            }
+        } else {
+            panic!("leaves should only contain leaves")
+        }
+    }
+
+    txn.commit()?;
+    Ok(())
+}
+
+fn read_proof_of_first_leaf() -> (Blake2bHash, TrieMerkleProof<TestKey, TestValue>) {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let txn = context.environment.create_read_txn().unwrap();
+    let key = TEST_LEAVES[0].key().unwrap();
+    let result = read_with_proof::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        key,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    match result {
+        ReadWithProofResult::Found(proof) => (root_hash, proof),
+        _ => panic!("expected a proof of inclusion"),
+    }
+}
+
+#[test]
+fn lmdb_proofs_from_n_leaf_partial_trie_verify() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_proofs_from_n_leaf_partial_trie_verify() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn read_with_proof_from_missing_root() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, _) = create_1_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    let txn = context.environment.create_read_txn().unwrap();
+    let key = TEST_LEAVES[0].key().unwrap();
+    let result = read_with_proof::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        key,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    assert_eq!(result, ReadWithProofResult::RootNotFound);
+}
+
+#[test]
+fn proof_should_not_verify_against_other_root() {
+    let (_, proof) = read_proof_of_first_leaf();
+    let (other_root_hash, _) = create_5_leaf_trie().unwrap();
+    assert_eq!(
+        proof.verify(&other_root_hash),
+        Err(TrieMerkleProofError::HashMismatch(0))
+    );
+}
+
+#[test]
+fn tampered_proof_should_not_verify() {
+    let (root_hash, proof) = read_proof_of_first_leaf();
+    let key = *proof.key();
+    let mut steps = proof.take_steps();
+    let last_index = steps.len() - 1;
+    steps[last_index] = Trie::leaf(key, TestValue(*b"forged"));
+    let tampered_proof = TrieMerkleProof::new(key, steps);
+    assert_eq!(
+        tampered_proof.verify(&root_hash),
+        Err(TrieMerkleProofError::HashMismatch(last_index))
+    );
+}
+
+#[test]
+fn truncated_proof_should_not_verify() {
+    let (root_hash, proof) = read_proof_of_first_leaf();
+    let key = *proof.key();
+    let mut steps = proof.take_steps();
+    steps.pop();
+    let truncated_proof = TrieMerkleProof::new(key, steps);
+    assert_eq!(
+        truncated_proof.verify(&root_hash),
+        Err(TrieMerkleProofError::Incomplete)
+    );
+
+    let empty_proof = TrieMerkleProof::<TestKey, TestValue>::new(key, Vec::new());
+    assert_eq!(
+        empty_proof.verify(&root_hash),
+        Err(TrieMerkleProofError::Empty)
+    );
+}
@@ -0,0 +1,183 @@
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::ToBytes;
+
+use super::TestData;
+use crate::{
+    store::{Store, StoreExt},
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::{
+        cache::{CachedTrieStore, TrieCache},
+        lmdb::LmdbTrieStore,
+    },
+    TEST_MAP_SIZE,
+};
+
+type TestTrie = Trie<Vec<u8>, Vec<u8>>;
+
+fn leaf_sizes(data: &[TestData<Vec<u8>, Vec<u8>>]) -> Vec<usize> {
+    data.iter()
+        .map(|TestData(_, trie)| TrieCache::estimate_size(trie, trie.to_bytes().unwrap().len()))
+        .collect()
+}
+
+#[test]
+fn lmdb_cached_get_is_served_from_cache() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let cache = TrieCache::default();
+    let store = CachedTrieStore::new(&lmdb_store, &cache);
+    let data = super::create_data();
+
+    let expected: Vec<Option<TestTrie>> = data
+        .iter()
+        .map(|TestData(_, trie)| Some(trie.to_owned()))
+        .collect();
+    let keys: Vec<Blake2bHash> = data.iter().map(|TestData(hash, _)| *hash).collect();
+
+    {
+        let mut txn = env.create_read_write_txn().unwrap();
+        store
+            .put_many(&mut txn, data.iter().map(Into::into))
+            .unwrap();
+        txn.commit().unwrap();
+    }
+    assert!(cache.is_empty());
+
+    for _ in 0..2 {
+        let txn = env.create_read_txn().unwrap();
+        let actual = store.get_many(&txn, keys.iter()).unwrap();
+        txn.commit().unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    assert_eq!(cache.len(), data.len());
+    assert_eq!(cache.misses(), data.len() as u64);
+    assert_eq!(cache.hits(), data.len() as u64);
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn lmdb_cached_delete_evicts_from_cache() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let cache = TrieCache::default();
+    let store = CachedTrieStore::new(&lmdb_store, &cache);
+    let data = &super::create_data()[0..1];
+    let TestData(hash, trie) = &data[0];
+
+    let mut txn = env.create_read_write_txn().unwrap();
+    store.put(&mut txn, hash, trie).unwrap();
+    assert_eq!(store.get(&txn, hash).unwrap(), Some(trie.to_owned()));
+    assert_eq!(cache.len(), 1);
+
+    Store::<Blake2bHash, TestTrie>::delete(&store, &mut txn, hash).unwrap();
+    assert!(cache.is_empty());
+    let maybe_trie: Option<TestTrie> = store.get(&txn, hash).unwrap();
+    assert_eq!(maybe_trie, None);
+    txn.commit().unwrap();
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn lmdb_read_write_changes_reach_cache_only_once_applied() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let cache = TrieCache::default();
+    let data = &super::create_data()[0..2];
+    let TestData(hash_1, trie_1) = &data[0];
+    let TestData(hash_2, trie_2) = &data[1];
+
+    {
+        let store = CachedTrieStore::read_write(&lmdb_store, &cache);
+        let mut txn = env.create_read_write_txn().unwrap();
+        store.put(&mut txn, hash_1, trie_1).unwrap();
+        store.put(&mut txn, hash_2, trie_2).unwrap();
+        assert_eq!(store.get(&txn, hash_1).unwrap(), Some(trie_1.to_owned()));
+        Store::<Blake2bHash, TestTrie>::delete(&store, &mut txn, hash_2).unwrap();
+        assert!(cache.is_empty());
+
+        txn.commit().unwrap();
+        assert!(cache.is_empty());
+        store.apply_staged();
+    }
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(hash_1), Some(trie_1.to_owned()));
+    assert_eq!(cache.get(hash_2), None);
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn lmdb_aborted_read_write_txn_leaves_cache_untouched() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let lmdb_store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let cache = TrieCache::default();
+    let data = &super::create_data()[0..1];
+    let TestData(hash, trie) = &data[0];
+
+    {
+        let store = CachedTrieStore::read_write(&lmdb_store, &cache);
+        let mut txn = env.create_read_write_txn().unwrap();
+        store.put(&mut txn, hash, trie).unwrap();
+        assert_eq!(store.get(&txn, hash).unwrap(), Some(trie.to_owned()));
+        // Dropping the transaction aborts it, and the staged changes are never applied.
+    }
+    assert!(cache.is_empty());
+
+    let store = CachedTrieStore::new(&lmdb_store, &cache);
+    let txn = env.create_read_txn().unwrap();
+    let maybe_trie: Option<TestTrie> = store.get(&txn, hash).unwrap();
+    txn.commit().unwrap();
+    assert_eq!(maybe_trie, None);
+    assert!(cache.is_empty());
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn cache_evicts_least_recently_used() {
+    let data = &super::create_data()[0..3];
+    let sizes = leaf_sizes(data);
+    let cache = TrieCache::new(sizes[0] + sizes[1] + sizes[2] - 1);
+
+    let TestData(hash_1, leaf_1) = &data[0];
+    let TestData(hash_2, leaf_2) = &data[1];
+    let TestData(hash_3, leaf_3) = &data[2];
+
+    cache.insert(*hash_1, leaf_1.to_owned(), sizes[0]);
+    cache.insert(*hash_2, leaf_2.to_owned(), sizes[1]);
+    assert_eq!(cache.get(hash_1), Some(leaf_1.to_owned()));
+
+    cache.insert(*hash_3, leaf_3.to_owned(), sizes[2]);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.size() <= cache.max_size());
+    assert_eq!(cache.get(hash_2), None);
+    assert_eq!(cache.get(hash_1), Some(leaf_1.to_owned()));
+    assert_eq!(cache.get(hash_3), Some(leaf_3.to_owned()));
+}
+
+#[test]
+fn cache_does_not_insert_values_larger_than_itself() {
+    let data = &super::create_data()[0..1];
+    let sizes = leaf_sizes(data);
+    let cache = TrieCache::new(sizes[0] - 1);
+    let TestData(hash, leaf) = &data[0];
+
+    cache.insert(*hash, leaf.to_owned(), sizes[0]);
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.size(), 0);
+    assert_eq!(cache.get(hash), None);
+}
@@ -1,3 +1,4 @@
+mod cache;
 mod concurrent;
 mod proptests;
 mod simple;
@@ -160,6 +160,70 @@ fn lmdb_put_get_many_succeeds() {
     tmp_dir.close().unwrap();
 }
 
+fn put_delete_succeeds<'a, K, V, S, X, E>(
+    store: &S,
+    transaction_source: &'a X,
+    items: &[TestData<K, V>],
+) -> Result<Vec<Option<Trie<K, V>>>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    S: TrieStore<K, V>,
+    X: TransactionSource<'a, Handle = S::Handle>,
+    S::Error: From<X::Error>,
+    E: From<S::Error> + From<X::Error>,
+{
+    let items = items.iter().map(Into::into);
+    {
+        let mut txn: X::ReadWriteTransaction = transaction_source.create_read_write_txn()?;
+        store.put_many(&mut txn, items.clone())?;
+        txn.commit()?;
+    }
+    {
+        let mut txn: X::ReadWriteTransaction = transaction_source.create_read_write_txn()?;
+        for (key, _) in items.clone() {
+            store.delete(&mut txn, key)?;
+        }
+        txn.commit()?;
+    }
+    let txn: X::ReadTransaction = transaction_source.create_read_txn()?;
+    let keys = items.map(|(k, _)| k);
+    let ret = store.get_many(&txn, keys)?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+#[test]
+fn in_memory_put_delete_succeeds() {
+    let env = InMemoryEnvironment::new();
+    let store = InMemoryTrieStore::new(&env, None);
+    let data = super::create_data();
+
+    assert!(
+        put_delete_succeeds::<_, _, _, _, in_memory::Error>(&store, &env, &data)
+            .expect("put_delete failed")
+            .into_iter()
+            .all(|maybe_trie| maybe_trie.is_none())
+    )
+}
+
+#[test]
+fn lmdb_put_delete_succeeds() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+
+    assert!(
+        put_delete_succeeds::<_, _, _, _, error::Error>(&store, &env, &data)
+            .expect("put_delete failed")
+            .into_iter()
+            .all(|maybe_trie| maybe_trie.is_none())
+    );
+
+    tmp_dir.close().unwrap();
+}
+
 fn uncommitted_read_write_txn_does_not_persist<'a, K, V, S, X, E>(
     store: &S,
     transaction_source: &'a X,
@@ -2,7 +2,10 @@ use std::{collections::BTreeSet, path::Path};
 
 use contract::args_parser::ArgsParser;
 use engine_core::{
-    engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
+    engine_state::{
+        deploy_item::DeployItem,
+        executable_deploy_item::{ExecutableDeployItem, DEFAULT_ENTRY_POINT_NAME},
+    },
     DeployHash,
 };
 use types::{account::PublicKey, bytesrepr::ToBytes, URef};
@@ -51,8 +54,11 @@ impl DeployItemBuilder {
 
     pub fn with_stored_payment_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.payment_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
+            args,
+        });
         self
     }
 
@@ -64,6 +70,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref_addr,
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
             args,
         });
         self
@@ -73,6 +80,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
             args,
         });
         self
@@ -82,6 +90,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
             args,
         });
         self
@@ -101,8 +110,11 @@ impl DeployItemBuilder {
 
     pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.session_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
+            args,
+        });
         self
     }
 
@@ -114,6 +126,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref_addr,
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
             args,
         });
         self
@@ -123,6 +136,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
             args,
         });
         self
@@ -132,6 +146,22 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
+            entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
+            args,
+        });
+        self
+    }
+
+    pub fn with_stored_session_named_key_entry_point(
+        mut self,
+        uref_name: &str,
+        entry_point: &str,
+        args: impl ArgsParser,
+    ) -> Self {
+        let args = Self::serialize_args(args);
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByName {
+            name: uref_name.to_owned(),
+            entry_point: entry_point.to_owned(),
             args,
         });
         self
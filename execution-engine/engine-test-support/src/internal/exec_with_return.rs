@@ -6,7 +6,7 @@ use engine_core::{
         executable_deploy_item::ExecutableDeployItem, execution_effect::ExecutionEffect,
         EngineConfig, EngineState,
     },
-    execution::{self, AddressGenerator},
+    execution::{self, AddressGenerator, RandomGenerator},
     runtime::{self, Runtime},
     runtime_context::RuntimeContext,
 };
@@ -19,7 +19,10 @@ use types::{
     ProtocolVersion, URef, U512,
 };
 
-use crate::internal::{utils, WasmTestBuilder, DEFAULT_WASM_COSTS};
+use crate::internal::{
+    utils, WasmTestBuilder, DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS, DEFAULT_HOST_FUNCTION_COSTS,
+    DEFAULT_STORAGE_COSTS, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS,
+};
 
 const INIT_FN_STORE_ID: u32 = 0;
 
@@ -39,7 +42,7 @@ pub fn exec<S, T>(
     extra_urefs: Vec<URef>,
 ) -> Option<(T, Vec<URef>, ExecutionEffect)>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     S::Error: Into<execution::Error>,
     EngineState<S>: ExecutionEngineService,
     T: FromBytes + CLTyped,
@@ -62,6 +65,10 @@ where
         let address_generator = AddressGenerator::new(&deploy_hash, phase);
         Rc::new(RefCell::new(address_generator))
     };
+    let random_generator = {
+        let random_generator = RandomGenerator::new(&deploy_hash, phase);
+        Rc::new(RefCell::new(random_generator))
+    };
     let gas_counter = Gas::default();
     let fn_store_id = INIT_FN_STORE_ID;
     let gas_limit = Gas::new(U512::from(std::u64::MAX));
@@ -85,7 +92,16 @@ where
         let mint = builder.get_mint_contract_uref();
         let pos = builder.get_mint_contract_uref();
         let standard_payment = builder.get_standard_payment_contract_uref();
-        ProtocolData::new(*DEFAULT_WASM_COSTS, mint, pos, standard_payment)
+        ProtocolData::new(
+            *DEFAULT_WASM_COSTS,
+            *DEFAULT_HOST_FUNCTION_COSTS,
+            *DEFAULT_STORAGE_COSTS,
+            *DEFAULT_WASM_LIMITS,
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
+            mint,
+            pos,
+            standard_payment,
+        )
     };
 
     let context = RuntimeContext::new(
@@ -102,6 +118,7 @@ where
         gas_counter,
         fn_store_id,
         address_generator,
+        random_generator,
         protocol_version,
         correlation_id,
         phase,
@@ -116,7 +133,9 @@ where
 
     let wasm_costs = *DEFAULT_WASM_COSTS;
 
-    let preprocessor = Preprocessor::new(wasm_costs);
+    let wasm_limits = *DEFAULT_WASM_LIMITS;
+
+    let preprocessor = Preprocessor::new(wasm_costs, wasm_limits);
     let parity_module = builder
         .get_engine_state()
         .get_module(
@@ -129,10 +148,21 @@ where
         )
         .expect("should get wasm module");
 
-    let (instance, memory) = runtime::instance_and_memory(parity_module.clone(), protocol_version)
-        .expect("should be able to make wasm instance from module");
-
-    let mut runtime = Runtime::new(config, Default::default(), memory, parity_module, context);
+    let (instance, memory) = runtime::instance_and_memory(
+        parity_module.clone(),
+        protocol_version,
+        wasm_limits.max_memory_pages,
+    )
+    .expect("should be able to make wasm instance from module");
+
+    let mut runtime = Runtime::new(
+        config,
+        Default::default(),
+        Default::default(),
+        memory,
+        parity_module,
+        context,
+    );
 
     match instance.invoke_export("call", &[], &mut runtime) {
         Ok(_) => None,
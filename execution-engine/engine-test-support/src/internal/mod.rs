@@ -11,7 +11,10 @@ use num_traits::identities::Zero;
 
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
 use engine_shared::{motes::Motes, test_utils};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+    wasm_limits::WasmLimits,
+};
 use types::{account::PublicKey, ProtocolVersion, U512};
 
 use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
@@ -31,6 +34,8 @@ pub const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
 pub const DEFAULT_CHAIN_NAME: &str = "gerald";
 pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
 pub const DEFAULT_BLOCK_TIME: u64 = 0;
+pub const DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS: u64 =
+    engine_storage::protocol_data::DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS;
 pub const MOCKED_ACCOUNT_ADDRESS: PublicKey = PublicKey::ed25519_from([48u8; 32]);
 
 pub const DEFAULT_ACCOUNT_KEY: PublicKey = DEFAULT_ACCOUNT_ADDR;
@@ -49,6 +54,9 @@ lazy_static! {
     pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
     pub static ref DEFAULT_PAYMENT: U512 = 100_000_000.into();
     pub static ref DEFAULT_WASM_COSTS: WasmCosts = test_utils::wasm_costs_mock();
+    pub static ref DEFAULT_HOST_FUNCTION_COSTS: HostFunctionCosts = HostFunctionCosts::default();
+    pub static ref DEFAULT_STORAGE_COSTS: StorageCosts = StorageCosts::default();
+    pub static ref DEFAULT_WASM_LIMITS: WasmLimits = WasmLimits::default();
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
         let mint_installer_bytes;
         let pos_installer_bytes;
@@ -73,6 +81,10 @@ lazy_static! {
             standard_payment_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            *DEFAULT_HOST_FUNCTION_COSTS,
+            *DEFAULT_STORAGE_COSTS,
+            *DEFAULT_WASM_LIMITS,
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
         )
     };
 }
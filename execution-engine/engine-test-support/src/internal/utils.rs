@@ -7,6 +7,7 @@ use std::{
 use lazy_static::lazy_static;
 
 use engine_core::engine_state::{
+    deploy_item::DeployItem,
     execution_result::ExecutionResult,
     genesis::{GenesisAccount, GenesisConfig},
 };
@@ -14,13 +15,17 @@ use engine_shared::{
     account::Account, additive_map::AdditiveMap, gas::Gas, stored_value::StoredValue,
     transform::Transform,
 };
-use types::Key;
+use types::{account::PublicKey, Key, U512};
 
 use crate::internal::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
-    MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+    DeployItemBuilder, DEFAULT_CHAIN_NAME, DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
+    DEFAULT_GENESIS_TIMESTAMP, DEFAULT_HOST_FUNCTION_COSTS, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_STORAGE_COSTS, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS, MINT_INSTALL_CONTRACT,
+    POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+
 lazy_static! {
     // The location of compiled Wasm files if compiled from the Rust sources within the CasperLabs
     // repo, i.e. 'CasperLabs/execution-engine/target/wasm32-unknown-unknown/release/'.
@@ -109,6 +114,10 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
     let standard_payment_installer_bytes = read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
     let protocol_version = *DEFAULT_PROTOCOL_VERSION;
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+    let storage_costs = *DEFAULT_STORAGE_COSTS;
+    let wasm_limits = *DEFAULT_WASM_LIMITS;
+    let deploy_hash_expiry_millis = DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS;
     GenesisConfig::new(
         name,
         timestamp,
@@ -118,9 +127,31 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        host_function_costs,
+        storage_costs,
+        wasm_limits,
+        deploy_hash_expiry_millis,
     )
 }
 
+/// Returns a deploy transferring `amount` from the main purse of `from` to the account `to`, which
+/// pays `payment` through the standard payment contract.
+pub fn transfer_deploy_item(
+    from: PublicKey,
+    to: PublicKey,
+    amount: U512,
+    payment: U512,
+    deploy_hash: [u8; 32],
+) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(from)
+        .with_session_code(CONTRACT_TRANSFER_PURSE_TO_ACCOUNT, (to, amount))
+        .with_empty_payment_bytes((payment,))
+        .with_authorization_keys(&[from])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
 pub fn get_exec_costs<T: AsRef<ExecutionResult>, I: IntoIterator<Item = T>>(
     exec_response: I,
 ) -> Vec<Gas> {
@@ -14,18 +14,29 @@ use log::LevelFilter;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult, genesis::GenesisConfig,
+        self,
+        bid_state::{BidState, BidStateRequest, BidStateResult},
+        deploy_item::DeployItem,
+        distribute_rewards::{DistributeRewardsRequest, DistributeRewardsResult},
+        estimate::{EstimateRequest, EstimateResult},
+        executable_deploy_item::ExecutableDeployItem,
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        genesis::GenesisConfig,
+        slash::{SlashRequest, SlashResult},
+        unbond_payout::{UnbondPayoutRequest, UnbondPayoutResult},
         EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
     execution,
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        CommitRequest, CommitResponse, DiffRequest, GenesisResponse, ListKeysRequest, QueryRequest,
+        QueryWithProofRequest, UpgradeRequest, UpgradeResponse, ViewRequest,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
+    state,
     transforms::TransformEntry,
 };
 use engine_shared::{
@@ -40,9 +51,13 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
+    global_state::{
+        in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, CommitResult, StateProvider,
+        TrieDiff,
+    },
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     transaction_source::lmdb::LmdbEnvironment,
+    trie::merkle_proof::TrieMerkleProof,
     trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
@@ -51,7 +66,7 @@ use types::{
     CLValue, Key, URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, DEFAULT_PROTOCOL_VERSION};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -287,7 +302,7 @@ impl LmdbWasmTestBuilder {
 
 impl<S> WasmTestBuilder<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     S::Error: Into<execution::Error>,
     EngineState<S>: ExecutionEngineService,
 {
@@ -383,6 +398,149 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    pub fn query_with_proof(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+        key: Key,
+    ) -> Result<TrieMerkleProof<Key, StoredValue>, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut query_request = QueryWithProofRequest::new();
+        query_request.set_state_hash(post_state);
+        query_request.set_key(key.into());
+
+        let mut query_response = self
+            .engine_state
+            .query_with_proof(RequestOptions::new(), query_request)
+            .wait_drop_metadata()
+            .expect("should get query with proof response");
+
+        if query_response.has_failure() {
+            return Err(query_response.take_failure());
+        }
+
+        let mut pb_proof = query_response.take_success();
+        let key = bytesrepr::deserialize(pb_proof.take_key()).map_err(|err| format!("{}", err))?;
+        let steps = pb_proof
+            .take_steps()
+            .into_iter()
+            .map(bytesrepr::deserialize)
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("{}", err))?;
+        Ok(TrieMerkleProof::new(key, steps))
+    }
+
+    /// Returns the keys added, removed or changed between two post state hashes.
+    pub fn diff(
+        &self,
+        left_state_hash: Vec<u8>,
+        right_state_hash: Vec<u8>,
+    ) -> Result<TrieDiff<Key>, String> {
+        let mut diff_request = DiffRequest::new();
+        diff_request.set_left_state_hash(left_state_hash);
+        diff_request.set_right_state_hash(right_state_hash);
+
+        let mut diff_response = self
+            .engine_state
+            .diff(RequestOptions::new(), diff_request)
+            .wait_drop_metadata()
+            .expect("should get diff response");
+
+        if diff_response.has_failure() {
+            return Err(diff_response.take_failure());
+        }
+
+        let mut pb_state_diff = diff_response.take_success();
+        let parse_keys = |pb_keys: Vec<state::Key>| {
+            pb_keys
+                .into_iter()
+                .map(Key::try_from)
+                .collect::<Result<Vec<Key>, _>>()
+                .map_err(|err| format!("{:?}", err))
+        };
+        Ok(TrieDiff {
+            added: parse_keys(pb_state_diff.take_added().into_vec())?,
+            removed: parse_keys(pb_state_diff.take_removed().into_vec())?,
+            changed: parse_keys(pb_state_diff.take_changed().into_vec())?,
+        })
+    }
+
+    /// Returns a page of the keys under the given post state hash whose serialized form starts
+    /// with `prefix`, along with the page token for the next page if there are more keys.
+    pub fn list_keys(
+        &self,
+        state_hash: Vec<u8>,
+        prefix: &[u8],
+        page_size: u32,
+        maybe_page_token: Option<Key>,
+    ) -> Result<(Vec<Key>, Option<Key>), String> {
+        let mut list_keys_request = ListKeysRequest::new();
+        list_keys_request.set_state_hash(state_hash);
+        list_keys_request.set_prefix(prefix.to_vec());
+        list_keys_request.set_page_size(page_size);
+        if let Some(page_token) = maybe_page_token {
+            list_keys_request.set_page_token(page_token.into());
+        }
+
+        let mut list_keys_response = self
+            .engine_state
+            .list_keys(RequestOptions::new(), list_keys_request)
+            .wait_drop_metadata()
+            .expect("should get list keys response");
+
+        if list_keys_response.has_failure() {
+            return Err(list_keys_response.take_failure());
+        }
+
+        let mut pb_list_keys_result = list_keys_response.take_success();
+        let keys = pb_list_keys_result
+            .take_keys()
+            .into_iter()
+            .map(Key::try_from)
+            .collect::<Result<Vec<Key>, _>>()
+            .map_err(|err| format!("{:?}", err))?;
+        let maybe_next_page_token = if pb_list_keys_result.has_next_page_token() {
+            let next_page_token = Key::try_from(pb_list_keys_result.take_next_page_token())
+                .map_err(|err| format!("{:?}", err))?;
+            Some(next_page_token)
+        } else {
+            None
+        };
+        Ok((keys, maybe_next_page_token))
+    }
+
+    /// Calls a stored contract in a read-only context against the latest post state hash, and
+    /// returns the value it passes to `runtime::ret`.
+    pub fn view(
+        &self,
+        address: PublicKey,
+        contract: ExecutableDeployItem,
+    ) -> Result<CLValue, String> {
+        let state_hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        let mut view_request = ViewRequest::new();
+        view_request.set_state_hash(state_hash);
+        view_request.set_address(address.as_bytes().to_vec());
+        view_request.set_contract(contract.into());
+        view_request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        let mut view_response = self
+            .engine_state
+            .view(RequestOptions::new(), view_request)
+            .wait_drop_metadata()
+            .expect("should get view response");
+
+        if view_response.has_failure() {
+            return Err(view_response.take_failure());
+        }
+
+        bytesrepr::deserialize(view_response.take_success()).map_err(|err| format!("{:?}", err))
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let exec_request = {
             let hash = self
@@ -410,6 +568,71 @@ where
         self
     }
 
+    /// Executes the deploys of the request in order, each seeing the effects of the previous ones,
+    /// and commits their combined effects.  Expects the commit to succeed, and overwrites the
+    /// cached post state hash with the resulting one.
+    pub fn exec_block(&mut self, mut exec_request: ExecuteRequest) -> &mut Self
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        let exec_request = {
+            let hash = self
+                .post_state_hash
+                .clone()
+                .expect("expected post_state_hash");
+            exec_request.parent_state_hash =
+                hash.as_slice().try_into().expect("expected a valid hash");
+            exec_request
+        };
+        let (execution_results, commit_result) = self
+            .engine_state
+            .run_execute_block(CorrelationId::new(), exec_request)
+            .expect("should execute block")
+            .take();
+        match commit_result {
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => {
+                self.post_state_hash = Some(state_root.to_vec());
+                self.bonded_validators.push(bonded_validators);
+            }
+            commit_result => panic!(
+                "Expected commit success but received a failure instead: {:?}",
+                commit_result
+            ),
+        }
+        self.transforms.extend(
+            execution_results
+                .iter()
+                .map(|res| res.effect().transforms.clone()),
+        );
+        self.exec_responses
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self
+    }
+
+    /// Estimates the gas consumed by a deploy executed against the latest post state hash, without
+    /// committing its effects.
+    pub fn estimate(&self, deploy_item: DeployItem) -> EstimateResult {
+        let state_hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        let estimate_request = EstimateRequest::new(
+            state_hash
+                .as_slice()
+                .try_into()
+                .expect("expected a valid hash"),
+            0,
+            deploy_item,
+            *DEFAULT_PROTOCOL_VERSION,
+        );
+        self.engine_state
+            .run_estimate(CorrelationId::new(), estimate_request)
+            .expect("should estimate")
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self
@@ -489,6 +712,128 @@ where
         self
     }
 
+    /// Slashes the given validators at the current post state hash. On success, the post state
+    /// hash and bonded validators are updated.
+    pub fn commit_slash(&mut self, slashes: Vec<(PublicKey, U512)>) -> SlashResult
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should parse post state hash");
+        let slash_request = SlashRequest::new(pre_state_hash, *DEFAULT_PROTOCOL_VERSION, slashes);
+
+        let slash_result = self
+            .engine_state
+            .commit_slash(CorrelationId::new(), slash_request)
+            .expect("should slash");
+
+        if let SlashResult::Success {
+            post_state_hash,
+            bonded_validators,
+        } = &slash_result
+        {
+            self.post_state_hash = Some(post_state_hash.to_vec());
+            self.bonded_validators.push(bonded_validators.clone());
+        }
+
+        slash_result
+    }
+
+    /// Distributes the given rewards at the current post state hash. On success, the post state
+    /// hash and bonded validators are updated.
+    pub fn commit_distribute_rewards(
+        &mut self,
+        rewards: Vec<(PublicKey, U512)>,
+    ) -> DistributeRewardsResult
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should parse post state hash");
+        let distribute_rewards_request =
+            DistributeRewardsRequest::new(pre_state_hash, *DEFAULT_PROTOCOL_VERSION, rewards);
+
+        let distribute_rewards_result = self
+            .engine_state
+            .commit_distribute_rewards(CorrelationId::new(), distribute_rewards_request)
+            .expect("should distribute rewards");
+
+        if let DistributeRewardsResult::Success {
+            post_state_hash,
+            bonded_validators,
+        } = &distribute_rewards_result
+        {
+            self.post_state_hash = Some(post_state_hash.to_vec());
+            self.bonded_validators.push(bonded_validators.clone());
+        }
+
+        distribute_rewards_result
+    }
+
+    /// Pays out the unbonding requests matured by `era_height` at the current post state hash. On
+    /// success, the post state hash and bonded validators are updated.
+    pub fn commit_unbond_payout(&mut self, era_height: u64) -> UnbondPayoutResult
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should parse post state hash");
+        let unbond_payout_request =
+            UnbondPayoutRequest::new(pre_state_hash, *DEFAULT_PROTOCOL_VERSION, era_height);
+
+        let unbond_payout_result = self
+            .engine_state
+            .commit_unbond_payout(CorrelationId::new(), unbond_payout_request)
+            .expect("should pay out unbonds");
+
+        if let UnbondPayoutResult::Success {
+            post_state_hash,
+            bonded_validators,
+        } = &unbond_payout_result
+        {
+            self.post_state_hash = Some(post_state_hash.to_vec());
+            self.bonded_validators.push(bonded_validators.clone());
+        }
+
+        unbond_payout_result
+    }
+
+    /// Returns the bid state of the Proof of Stake contract at the current post state hash.
+    pub fn get_bid_state(&self) -> BidState {
+        let pre_state_hash: Blake2bHash = self
+            .post_state_hash
+            .as_ref()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("should parse post state hash");
+        let bid_state_request = BidStateRequest::new(pre_state_hash, *DEFAULT_PROTOCOL_VERSION);
+
+        match self
+            .engine_state
+            .get_bid_state(CorrelationId::new(), bid_state_request)
+            .expect("should get bid state")
+        {
+            BidStateResult::Success(bid_state) => bid_state,
+            BidStateResult::RootNotFound => panic!("post state hash should exist"),
+        }
+    }
+
     /// Expects a successful run and caches transformations
     pub fn expect_success(&mut self) -> &mut Self {
         // Check first result, as only first result is interesting for a simple test
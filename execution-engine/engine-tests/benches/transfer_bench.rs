@@ -1,4 +1,8 @@
-use std::{path::Path, time::Duration};
+use std::{
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 use criterion::{
     criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, Throughput,
@@ -25,10 +29,13 @@ const TRANSFER_BATCH_SIZE: u64 = 3;
 const PER_RUN_FUNDING: u64 = 10_000_000;
 const TARGET_ADDR: PublicKey = PublicKey::ed25519_from([127; 32]);
 
-/// Converts an integer into an array of type [u8; 32] by converting integer
-/// into its big endian representation and embedding it at the end of the
-/// range.
-fn make_deploy_hash(i: u64) -> [u8; 32] {
+static DEPLOY_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a deploy hash which hasn't been returned before, as committing a deploy whose hash has
+/// already been executed would be rejected.  Embeds the big endian representation of a counter at
+/// the end of the range.
+fn make_deploy_hash() -> [u8; 32] {
+    let i = DEPLOY_COUNT.fetch_add(1, Ordering::SeqCst);
     let mut result = [128; 32];
     result[32 - 8..].copy_from_slice(&i.to_be_bytes());
     result
@@ -130,7 +137,7 @@ fn transfer_to_account_multiple_deploys(
 ) {
     let mut exec_builder = ExecuteRequestBuilder::new();
 
-    for i in 0..TRANSFER_BATCH_SIZE {
+    for _ in 0..TRANSFER_BATCH_SIZE {
         let deploy = DeployItemBuilder::default()
             .with_address(DEFAULT_ACCOUNT_ADDR)
             .with_empty_payment_bytes((U512::from(PER_RUN_FUNDING),))
@@ -139,7 +146,7 @@ fn transfer_to_account_multiple_deploys(
                 (account, U512::one()),
             )
             .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
-            .with_deploy_hash(make_deploy_hash(i)) // deploy_hash
+            .with_deploy_hash(make_deploy_hash()) // deploy_hash
             .build();
         exec_builder = exec_builder.push_deploy(deploy);
     }
@@ -184,13 +191,13 @@ fn transfer_to_purse_multiple_deploys(
 ) {
     let mut exec_builder = ExecuteRequestBuilder::new();
 
-    for i in 0..TRANSFER_BATCH_SIZE {
+    for _ in 0..TRANSFER_BATCH_SIZE {
         let deploy = DeployItemBuilder::default()
             .with_address(TARGET_ADDR)
             .with_empty_payment_bytes((U512::from(PER_RUN_FUNDING),))
             .with_session_code(CONTRACT_TRANSFER_TO_PURSE, (purse, U512::one()))
             .with_authorization_keys(&[TARGET_ADDR])
-            .with_deploy_hash(make_deploy_hash(i)) // deploy_hash
+            .with_deploy_hash(make_deploy_hash()) // deploy_hash
             .build();
         exec_builder = exec_builder.push_deploy(deploy);
     }
@@ -0,0 +1,186 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::ApiError;
+
+const CONTRACT_PACKAGE: &str = "contract_package.wasm";
+const CREATE: &str = "create";
+const CALL_LATEST: &str = "call-latest";
+const CALL_VERSION: &str = "call-version";
+const DISABLE: &str = "disable";
+const ENABLE: &str = "enable";
+const DISABLE_READ_ONLY: &str = "disable-read-only";
+const VERSION_ONE: u32 = 1;
+const VERSION_TWO: u32 = 2;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_PACKAGE, (CREATE,)).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_latest(
+    builder: &mut InMemoryWasmTestBuilder,
+    expected: u32,
+) -> &mut InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PACKAGE,
+        (CALL_LATEST, expected),
+    )
+    .build();
+    builder.exec(exec_request)
+}
+
+fn call_version(
+    builder: &mut InMemoryWasmTestBuilder,
+    version: u32,
+    expected: u32,
+) -> &mut InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PACKAGE,
+        (CALL_VERSION, version, expected),
+    )
+    .build();
+    builder.exec(exec_request)
+}
+
+fn set_version(
+    builder: &mut InMemoryWasmTestBuilder,
+    method_name: &str,
+    version: u32,
+) -> &mut InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PACKAGE,
+        (method_name, version),
+    )
+    .build();
+    builder.exec(exec_request)
+}
+
+#[ignore]
+#[test]
+fn should_call_latest_version() {
+    let mut builder = setup();
+    call_latest(&mut builder, VERSION_TWO)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_call_pinned_version() {
+    let mut builder = setup();
+    call_version(&mut builder, VERSION_ONE, VERSION_ONE)
+        .expect_success()
+        .commit();
+    call_version(&mut builder, VERSION_TWO, VERSION_TWO)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_fall_back_to_previous_version_when_latest_is_disabled() {
+    let mut builder = setup();
+    set_version(&mut builder, DISABLE, VERSION_TWO)
+        .expect_success()
+        .commit();
+
+    call_latest(&mut builder, VERSION_ONE)
+        .expect_success()
+        .commit();
+
+    call_version(&mut builder, VERSION_TWO, VERSION_TWO).commit();
+    let error_message = builder
+        .exec_error_message(3)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("DisabledContractVersion({})", VERSION_TWO)),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_call_reenabled_version() {
+    let mut builder = setup();
+    set_version(&mut builder, DISABLE, VERSION_TWO)
+        .expect_success()
+        .commit();
+    set_version(&mut builder, ENABLE, VERSION_TWO)
+        .expect_success()
+        .commit();
+
+    call_latest(&mut builder, VERSION_TWO)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_package_with_no_enabled_versions() {
+    let mut builder = setup();
+    set_version(&mut builder, DISABLE, VERSION_ONE)
+        .expect_success()
+        .commit();
+    set_version(&mut builder, DISABLE, VERSION_TWO)
+        .expect_success()
+        .commit();
+
+    call_latest(&mut builder, VERSION_TWO).commit();
+    let error_message = builder
+        .exec_error_message(3)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("NoEnabledContractVersion"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_disable_missing_version() {
+    let mut builder = setup();
+    set_version(&mut builder, DISABLE, 3).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!(
+            "Revert({})",
+            u32::from(ApiError::InvalidContractVersion)
+        )),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_disable_version_without_write_access() {
+    let mut builder = setup();
+    set_version(&mut builder, DISABLE_READ_ONLY, VERSION_TWO).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(error_message.contains("InvalidAccess"), "{}", error_message);
+
+    call_latest(&mut builder, VERSION_TWO)
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,169 @@
+use ed25519_dalek::{ExpandedSecretKey, PublicKey as Ed25519PublicKey, SecretKey};
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, ED25519_LENGTH, ED25519_SIGNATURE_LENGTH},
+    ApiError,
+};
+
+const CONTRACT_CRYPTO: &str = "crypto.wasm";
+const BLAKE2B: &str = "blake2b";
+const BLAKE2B_OVERSIZED: &str = "blake2b-oversized";
+const BLAKE2B_UNDERSIZED: &str = "blake2b-undersized";
+const VERIFY_SIGNATURE: &str = "verify-signature";
+const SECRET_KEY: [u8; ED25519_LENGTH] = [42u8; ED25519_LENGTH];
+const MESSAGE: &[u8] = b"Hello, world!";
+
+fn sign(message: &[u8]) -> ([u8; ED25519_SIGNATURE_LENGTH], PublicKey) {
+    let secret_key = SecretKey::from_bytes(&SECRET_KEY).expect("should create secret key");
+    let public_key = Ed25519PublicKey::from(&secret_key);
+    let signature = ExpandedSecretKey::from(&secret_key).sign(message, &public_key);
+    (
+        signature.to_bytes(),
+        PublicKey::ed25519_from(public_key.to_bytes()),
+    )
+}
+
+#[ignore]
+#[test]
+fn should_hash_with_blake2b() {
+    let expected_hash = Blake2bHash::new(MESSAGE).value();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (BLAKE2B, MESSAGE.to_vec(), expected_hash),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_hash_into_oversized_buffer() {
+    let expected_hash = Blake2bHash::new(MESSAGE).value();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (BLAKE2B_OVERSIZED, MESSAGE.to_vec(), expected_hash),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_undersized_hash_buffer() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (BLAKE2B_UNDERSIZED, MESSAGE.to_vec()),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("Revert({})", u32::from(ApiError::BufferTooSmall))),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_verify_valid_signature() {
+    let (signature, public_key) = sign(MESSAGE);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (
+            VERIFY_SIGNATURE,
+            MESSAGE.to_vec(),
+            signature,
+            public_key,
+            true,
+        ),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_signature_of_other_message() {
+    let (signature, public_key) = sign(MESSAGE);
+    let other_message = b"Goodbye, world!".to_vec();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (
+            VERIFY_SIGNATURE,
+            other_message,
+            signature,
+            public_key,
+            false,
+        ),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_reject_signature_by_other_key() {
+    let (signature, _public_key) = sign(MESSAGE);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CRYPTO,
+        (
+            VERIFY_SIGNATURE,
+            MESSAGE.to_vec(),
+            signature,
+            DEFAULT_ACCOUNT_ADDR,
+            false,
+        ),
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
@@ -0,0 +1,83 @@
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{CLValue, Key};
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const EMIT_FROM_SESSION: &str = "emit-from-session";
+const EMIT_VIA_SUBCALL: &str = "emit-via-subcall";
+const EMIT_AND_REVERT: &str = "emit-and-revert";
+const SESSION_EVENT_NAME: &str = "session_event";
+const SESSION_EVENT_VALUE: u64 = 1;
+const SUBCALL_EVENT_NAME: &str = "subcall_event";
+const SUBCALL_EVENT_VALUE: &str = "Hello, world!";
+
+fn run_emit_event(method_name: &str) -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_EMIT_EVENT, (method_name,))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_return_events_emitted_by_session_code() {
+    let builder = run_emit_event(EMIT_FROM_SESSION);
+
+    let response = builder.get_exec_response(0).expect("should have response");
+    let result = utils::get_success_result(response);
+    assert!(result.is_success(), "expected success");
+
+    let events = result.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].source(), Key::Account(DEFAULT_ACCOUNT_ADDR));
+    assert_eq!(events[0].name(), SESSION_EVENT_NAME);
+    assert_eq!(
+        *events[0].value(),
+        CLValue::from_t(SESSION_EVENT_VALUE).expect("should create CLValue")
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_events_emitted_by_subcalls_in_order() {
+    let builder = run_emit_event(EMIT_VIA_SUBCALL);
+
+    let response = builder.get_exec_response(0).expect("should have response");
+    let result = utils::get_success_result(response);
+    assert!(result.is_success(), "expected success");
+
+    let events = result.events();
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].name(), SUBCALL_EVENT_NAME);
+    assert_eq!(
+        *events[0].value(),
+        CLValue::from_t(String::from(SUBCALL_EVENT_VALUE)).expect("should create CLValue")
+    );
+    match events[0].source() {
+        Key::Hash(_) => {}
+        other => panic!("expected event from stored contract, got {:?}", other),
+    }
+
+    assert_eq!(events[1].name(), SESSION_EVENT_NAME);
+    assert_eq!(events[1].source(), Key::Account(DEFAULT_ACCOUNT_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_discard_events_on_revert() {
+    let builder = run_emit_event(EMIT_AND_REVERT);
+
+    let response = builder.get_exec_response(0).expect("should have response");
+    let result = utils::get_success_result(response);
+    assert!(result.is_failure(), "expected failure");
+    assert!(result.events().is_empty(), "events should be discarded");
+}
@@ -0,0 +1,116 @@
+use engine_core::engine_state::executable_deploy_item::DEFAULT_ENTRY_POINT_NAME;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_ENTRY_POINTS: &str = "entry_points.wasm";
+const CONTRACT_NAME: &str = "entry_points";
+const STORE: &str = "store";
+const CALL_GREET: &str = "call-greet";
+const CALL_MISSING: &str = "call-missing";
+const SET_GREETING_ENTRY_POINT: &str = "set_greeting";
+const MISSING_ENTRY_POINT: &str = "missing";
+const GREETING_KEY: &str = "greeting";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENTRY_POINTS, (STORE,))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn stored_entry_point_request(entry_point: &str) -> ExecuteRequestBuilder {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_named_key_entry_point(CONTRACT_NAME, entry_point, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy)
+}
+
+#[ignore]
+#[test]
+fn should_call_named_entry_point_from_contract() {
+    let mut builder = setup();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENTRY_POINTS, (CALL_GREET,))
+            .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_call_missing_entry_point_from_contract() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ENTRY_POINTS,
+        (CALL_MISSING,),
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("NoSuchEntryPoint(\"{}\")", MISSING_ENTRY_POINT)),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_invoke_named_entry_point_from_deploy() {
+    let mut builder = setup();
+
+    let exec_request = stored_entry_point_request(SET_GREETING_ENTRY_POINT).build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.named_keys().contains_key(GREETING_KEY));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_invoke_missing_entry_point_from_deploy() {
+    let mut builder = setup();
+
+    // The contract was stored with named entry points only, so it has no `call` export.
+    let exec_request = stored_entry_point_request(DEFAULT_ENTRY_POINT_NAME).build();
+
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!(
+            "NoSuchEntryPoint(\"{}\")",
+            DEFAULT_ENTRY_POINT_NAME
+        )),
+        "{}",
+        error_message
+    );
+}
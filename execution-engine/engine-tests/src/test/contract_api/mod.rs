@@ -1,5 +1,9 @@
 mod account;
+mod contract_package;
 mod create_purse;
+mod crypto;
+mod emit_event;
+mod entry_points;
 mod get_arg;
 mod get_blocktime;
 mod get_caller;
@@ -8,6 +12,8 @@ mod list_named_keys;
 mod local_state;
 mod main_purse;
 mod mint_purse;
+mod module_cache;
+mod random_bytes;
 mod revert;
 mod subcall;
 mod transfer;
@@ -0,0 +1,72 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_ENTRY_POINTS: &str = "entry_points.wasm";
+const CONTRACT_NAME: &str = "entry_points";
+const STORE: &str = "store";
+const CALL_GREET: &str = "call-greet";
+const SET_GREETING_ENTRY_POINT: &str = "set_greeting";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENTRY_POINTS, (STORE,))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_greet(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ENTRY_POINTS, (CALL_GREET,))
+            .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn module_cache_counts(builder: &InMemoryWasmTestBuilder) -> (u64, u64) {
+    let module_cache = builder.get_engine_state().module_cache();
+    (module_cache.hits(), module_cache.misses())
+}
+
+#[ignore]
+#[test]
+fn should_reuse_cached_module_across_subcalls() {
+    let mut builder = setup();
+    assert_eq!(module_cache_counts(&builder), (0, 0));
+
+    call_greet(&mut builder);
+    assert_eq!(module_cache_counts(&builder), (0, 1));
+
+    call_greet(&mut builder);
+    assert_eq!(module_cache_counts(&builder), (1, 1));
+}
+
+#[ignore]
+#[test]
+fn should_share_cached_module_with_stored_session_code() {
+    let mut builder = setup();
+    call_greet(&mut builder);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_named_key_entry_point(CONTRACT_NAME, SET_GREETING_ENTRY_POINT, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).expect_success().commit();
+    assert_eq!(module_cache_counts(&builder), (1, 1));
+}
@@ -0,0 +1,133 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{bytesrepr::FromBytes, ApiError, CLTyped, Key, URef};
+
+const CONTRACT_RANDOM_BYTES: &str = "random_bytes.wasm";
+const DRAW: &str = "draw";
+const DRAW_VIA_SUBCALL: &str = "draw-via-subcall";
+const NEW_UREF: &str = "new-uref";
+const DRAW_AND_NEW_UREF: &str = "draw-and-new-uref";
+const DRAW_OVERSIZED: &str = "draw-oversized";
+const DRAW_UNDERSIZED: &str = "draw-undersized";
+const FIRST_DRAW_KEY: &str = "first_draw";
+const SECOND_DRAW_KEY: &str = "second_draw";
+const UREF_KEY: &str = "uref";
+const DEPLOY_HASH_1: [u8; 32] = [1; 32];
+const DEPLOY_HASH_2: [u8; 32] = [2; 32];
+
+type RandomBytes = [u8; 32];
+
+fn random_bytes_request(method_name: &str, deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_RANDOM_BYTES, (method_name,))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn run_random_bytes(method_name: &str, deploy_hash: [u8; 32]) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(random_bytes_request(method_name, deploy_hash))
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn query_named_value<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should query named key")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should convert CLValue")
+}
+
+fn get_draws(builder: &InMemoryWasmTestBuilder) -> (RandomBytes, RandomBytes) {
+    (
+        query_named_value(builder, FIRST_DRAW_KEY),
+        query_named_value(builder, SECOND_DRAW_KEY),
+    )
+}
+
+fn get_uref(builder: &InMemoryWasmTestBuilder) -> URef {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    match account.named_keys()[UREF_KEY] {
+        Key::URef(uref) => uref,
+        other => panic!("Key {:?} is not an URef", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_draw_same_bytes_for_same_deploy_hash() {
+    let draws_a = get_draws(&run_random_bytes(DRAW, DEPLOY_HASH_1));
+    let draws_b = get_draws(&run_random_bytes(DRAW, DEPLOY_HASH_1));
+    assert_eq!(draws_a, draws_b);
+
+    let draws_c = get_draws(&run_random_bytes(DRAW, DEPLOY_HASH_2));
+    assert_ne!(draws_a, draws_c);
+}
+
+#[ignore]
+#[test]
+fn should_draw_different_bytes_on_each_call() {
+    let (first, second) = get_draws(&run_random_bytes(DRAW, DEPLOY_HASH_1));
+    assert_ne!(first, second);
+}
+
+#[ignore]
+#[test]
+fn should_continue_random_stream_in_subcall() {
+    let (first, second) = get_draws(&run_random_bytes(DRAW_VIA_SUBCALL, DEPLOY_HASH_1));
+    assert_ne!(first, second);
+}
+
+#[ignore]
+#[test]
+fn should_not_affect_uref_addresses() {
+    let uref_a = get_uref(&run_random_bytes(NEW_UREF, DEPLOY_HASH_1));
+    let uref_b = get_uref(&run_random_bytes(DRAW_AND_NEW_UREF, DEPLOY_HASH_1));
+    assert_eq!(uref_a.addr(), uref_b.addr());
+}
+
+#[ignore]
+#[test]
+fn should_fill_prefix_of_oversized_buffer() {
+    let draws = get_draws(&run_random_bytes(DRAW, DEPLOY_HASH_1));
+    let oversized_draws = get_draws(&run_random_bytes(DRAW_OVERSIZED, DEPLOY_HASH_1));
+    assert_eq!(oversized_draws, draws);
+}
+
+#[ignore]
+#[test]
+fn should_reject_undersized_buffer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(random_bytes_request(DRAW_UNDERSIZED, DEPLOY_HASH_1))
+        .commit();
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("Revert({})", u32::from(ApiError::BufferTooSmall))),
+        "{}",
+        error_message
+    );
+}
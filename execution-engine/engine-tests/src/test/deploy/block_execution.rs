@@ -0,0 +1,101 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, CLValue, TransferResult, TransferredTo, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_2_FUNDING_AMOUNT: u64 = 1_000_000;
+// Covers account 1's payment for its own deploy on top of the amount it transfers.
+const ACCOUNT_1_FUNDING_AMOUNT: u64 = 100_000_000 + ACCOUNT_2_FUNDING_AMOUNT;
+
+/// A request whose second deploy is sent from the account created by its first deploy.
+fn dependent_transfers_request() -> ExecuteRequest {
+    ExecuteRequestBuilder::new()
+        .push_deploy(utils::transfer_deploy_item(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_1_ADDR,
+            U512::from(ACCOUNT_1_FUNDING_AMOUNT),
+            *DEFAULT_PAYMENT,
+            [1; 32],
+        ))
+        .push_deploy(utils::transfer_deploy_item(
+            ACCOUNT_1_ADDR,
+            ACCOUNT_2_ADDR,
+            U512::from(ACCOUNT_2_FUNDING_AMOUNT),
+            *DEFAULT_PAYMENT,
+            [2; 32],
+        ))
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploys_of_a_block_against_the_effects_of_the_preceding_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let genesis_hash = builder.get_post_state_hash();
+
+    builder.exec_block(dependent_transfers_request());
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.is_success()));
+    assert_ne!(builder.get_post_state_hash(), genesis_hash);
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    assert!(
+        builder.get_purse_balance(account_1.main_purse()) < U512::from(ACCOUNT_1_FUNDING_AMOUNT)
+    );
+
+    let transfer_result_key = account_1.named_keys()["transfer_result"].normalize();
+    let transfer_result = CLValue::try_from(
+        builder
+            .query(None, transfer_result_key, &[])
+            .expect("should have transfer result"),
+    )
+    .expect("should be a CLValue")
+    .into_t::<String>()
+    .expect("should be String");
+    assert_eq!(
+        transfer_result,
+        format!("{:?}", TransferResult::Ok(TransferredTo::NewAccount))
+    );
+
+    let account_2 = builder
+        .get_account(ACCOUNT_2_ADDR)
+        .expect("should have account 2");
+    assert_eq!(
+        builder.get_purse_balance(account_2.main_purse()),
+        U512::from(ACCOUNT_2_FUNDING_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_see_the_effects_of_preceding_deploys_when_executed_independently() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    builder.exec(dependent_transfers_request());
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results[0].is_success());
+    assert!(
+        results[1].is_failure(),
+        "account 1 should not exist in the parent state"
+    );
+}
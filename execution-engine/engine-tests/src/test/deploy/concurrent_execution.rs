@@ -0,0 +1,58 @@
+use engine_core::engine_state::deploy_item::DeployItem;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const TRANSFER_COUNT: u8 = 32;
+
+fn transfer_deploy(index: u8) -> DeployItem {
+    utils::transfer_deploy_item(
+        DEFAULT_ACCOUNT_ADDR,
+        PublicKey::ed25519_from([index; 32]),
+        U512::from(TRANSFER_AMOUNT),
+        *DEFAULT_PAYMENT,
+        [index; 32],
+    )
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploys_of_a_request_as_if_executed_one_at_a_time() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let exec_request = (1..=TRANSFER_COUNT)
+        .fold(
+            ExecuteRequestBuilder::new(),
+            |exec_request_builder, index| exec_request_builder.push_deploy(transfer_deploy(index)),
+        )
+        .build();
+    builder.exec(exec_request).expect_success();
+    let batch_results = builder
+        .get_exec_response(0)
+        .expect("should have exec response")
+        .clone();
+    assert_eq!(batch_results.len(), TRANSFER_COUNT as usize);
+    assert!(batch_results.iter().all(|result| result.is_success()));
+
+    // Each request is executed against the genesis post state, as nothing is committed.
+    for (index, batch_result) in (1..=TRANSFER_COUNT).zip(batch_results) {
+        let exec_request = ExecuteRequestBuilder::new()
+            .push_deploy(transfer_deploy(index))
+            .build();
+        builder.exec(exec_request).expect_success();
+        let result = builder
+            .get_exec_response(index as usize)
+            .expect("should have exec response")[0]
+            .clone();
+
+        assert_eq!(result.cost(), batch_result.cost());
+        assert_eq!(result.effect(), batch_result.effect());
+    }
+}
@@ -0,0 +1,171 @@
+use engine_core::engine_state::{
+    deploy_item::DeployItem, estimate::EstimateResult, genesis::GenesisConfig,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::storage_costs::StorageCosts;
+use types::{account::PublicKey, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const GAS_PER_BYTE: u32 = 1_000;
+
+fn genesis_config_with_storage_costs(storage_costs: StorageCosts) -> GenesisConfig {
+    let genesis_config = &*DEFAULT_GENESIS_CONFIG;
+    GenesisConfig::new(
+        genesis_config.name().to_string(),
+        genesis_config.timestamp(),
+        genesis_config.protocol_version(),
+        genesis_config.mint_installer_bytes().to_vec(),
+        genesis_config.proof_of_stake_installer_bytes().to_vec(),
+        genesis_config.standard_payment_installer_bytes().to_vec(),
+        genesis_config.accounts().to_vec(),
+        genesis_config.wasm_costs(),
+        genesis_config.host_function_costs(),
+        storage_costs,
+        genesis_config.wasm_limits(),
+        genesis_config.deploy_hash_expiry_millis(),
+    )
+}
+
+fn transfer_deploy(payment_amount: U512) -> DeployItem {
+    utils::transfer_deploy_item(
+        DEFAULT_ACCOUNT_ADDR,
+        ACCOUNT_1_ADDR,
+        U512::from(TRANSFER_AMOUNT),
+        payment_amount,
+        [1; 32],
+    )
+}
+
+#[ignore]
+#[test]
+fn should_estimate_the_cost_charged_for_a_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let genesis_hash = builder.get_post_state_hash();
+
+    let (payment_result, session_result, effect) =
+        match builder.estimate(transfer_deploy(*DEFAULT_PAYMENT)) {
+            EstimateResult::Success {
+                payment_result,
+                maybe_session_result: Some(session_result),
+                effect,
+            } => (payment_result, session_result, effect),
+            _ => panic!("should estimate payment and session"),
+        };
+    assert!(payment_result.is_success());
+    assert!(session_result.is_success());
+    assert!(!effect.transforms.is_empty());
+
+    // Nothing is committed by an estimate.
+    assert_eq!(builder.get_post_state_hash(), genesis_hash);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(*DEFAULT_PAYMENT))
+        .build();
+    builder.exec(exec_request).expect_success();
+    let execution_result = &builder
+        .get_exec_response(0)
+        .expect("should have exec response")[0];
+
+    assert_eq!(
+        execution_result.cost(),
+        payment_result.cost() + session_result.cost()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_estimate_the_storage_charged_for_a_deploy_and_its_record() {
+    let genesis_config = genesis_config_with_storage_costs(StorageCosts::new(GAS_PER_BYTE));
+    assert_ne!(genesis_config.deploy_hash_expiry_millis(), 0);
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+
+    let (payment_result, session_result) = match builder.estimate(transfer_deploy(*DEFAULT_PAYMENT))
+    {
+        EstimateResult::Success {
+            payment_result,
+            maybe_session_result: Some(session_result),
+            ..
+        } => (payment_result, session_result),
+        _ => panic!("should estimate payment and session"),
+    };
+    assert!(payment_result.is_success());
+    assert!(session_result.is_success());
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(*DEFAULT_PAYMENT))
+        .build();
+    builder.exec(exec_request).expect_success();
+    let execution_result = &builder
+        .get_exec_response(0)
+        .expect("should have exec response")[0];
+
+    assert_eq!(
+        execution_result.cost(),
+        payment_result.cost() + session_result.cost()
+    );
+    assert_eq!(
+        execution_result.storage_bytes(),
+        payment_result.storage_bytes() + session_result.storage_bytes()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_estimate_a_deploy_regardless_of_its_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let underpaid_deploy = transfer_deploy(U512::one());
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(underpaid_deploy.clone())
+        .build();
+    builder.exec(exec_request);
+    assert!(builder.is_error());
+
+    match builder.estimate(underpaid_deploy) {
+        EstimateResult::Success {
+            payment_result,
+            maybe_session_result: Some(session_result),
+            ..
+        } => {
+            assert!(payment_result.is_success());
+            assert!(session_result.is_success());
+        }
+        _ => panic!("should estimate payment and session"),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_report_precondition_failures_of_an_estimate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let unknown_account_deploy = utils::transfer_deploy_item(
+        ACCOUNT_1_ADDR,
+        DEFAULT_ACCOUNT_ADDR,
+        U512::from(TRANSFER_AMOUNT),
+        *DEFAULT_PAYMENT,
+        [1; 32],
+    );
+
+    match builder.estimate(unknown_account_deploy) {
+        EstimateResult::Success {
+            payment_result,
+            maybe_session_result: None,
+            ..
+        } => assert!(payment_result.has_precondition_failure()),
+        _ => panic!("should fail to estimate"),
+    }
+}
@@ -1,3 +1,7 @@
+mod block_execution;
+mod concurrent_execution;
+mod estimate;
 mod non_standard_payment;
 mod preconditions;
+mod replay_protection;
 mod stored_contracts;
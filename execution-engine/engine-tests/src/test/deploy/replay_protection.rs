@@ -0,0 +1,477 @@
+use engine_core::engine_state::{
+    deploy_item::DeployItem, execution_result::ExecutionResult, genesis::GenesisConfig,
+    replay_protection, upgrade::ActivationPoint, Error, MAX_EXPIRED_DEPLOY_RECORDS_PER_COMMIT,
+};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS, DEFAULT_GENESIS_CONFIG,
+        DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION, DEFAULT_STORAGE_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::storage_costs::StorageCosts;
+use types::{account::PublicKey, BlockTime, ProtocolVersion, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const REVERT_WASM: &str = "revert.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000;
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+const BLOCK_TIME: u64 = 1_000;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const NEW_DEPLOY_HASH_EXPIRY_MILLIS: u32 = 10;
+const GAS_PER_BYTE: u32 = 1_000;
+
+fn genesis_config(storage_costs: StorageCosts, deploy_hash_expiry_millis: u64) -> GenesisConfig {
+    let genesis_config = &*DEFAULT_GENESIS_CONFIG;
+    GenesisConfig::new(
+        genesis_config.name().to_string(),
+        genesis_config.timestamp(),
+        genesis_config.protocol_version(),
+        genesis_config.mint_installer_bytes().to_vec(),
+        genesis_config.proof_of_stake_installer_bytes().to_vec(),
+        genesis_config.standard_payment_installer_bytes().to_vec(),
+        genesis_config.accounts().to_vec(),
+        genesis_config.wasm_costs(),
+        genesis_config.host_function_costs(),
+        storage_costs,
+        genesis_config.wasm_limits(),
+        deploy_hash_expiry_millis,
+    )
+}
+
+fn transfer_deploy(deploy_hash: [u8; 32]) -> DeployItem {
+    utils::transfer_deploy_item(
+        DEFAULT_ACCOUNT_ADDR,
+        ACCOUNT_1_ADDR,
+        U512::from(TRANSFER_AMOUNT),
+        *DEFAULT_PAYMENT,
+        deploy_hash,
+    )
+}
+
+/// Executes and commits `deploy` at the given block time and returns its result.
+fn exec_at(
+    builder: &mut InMemoryWasmTestBuilder,
+    deploy: DeployItem,
+    block_time: u64,
+) -> &ExecutionResult {
+    exec_at_version(builder, deploy, block_time, *DEFAULT_PROTOCOL_VERSION)
+}
+
+/// Executes and commits `deploy` at the given block time under the given protocol version and
+/// returns its result.
+fn exec_at_version(
+    builder: &mut InMemoryWasmTestBuilder,
+    deploy: DeployItem,
+    block_time: u64,
+    protocol_version: ProtocolVersion,
+) -> &ExecutionResult {
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_block_time(block_time)
+        .with_protocol_version(protocol_version)
+        .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have response");
+    utils::get_success_result(response)
+}
+
+fn assert_replayed(result: &ExecutionResult) {
+    assert!(
+        result.has_precondition_failure(),
+        "replay should be a precondition failure"
+    );
+    assert_eq!(
+        result.error().expect("should have error").to_string(),
+        Error::DeployReplayed.to_string()
+    );
+}
+
+fn account_1_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    builder.get_purse_balance(account_1.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_reject_replayed_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+    assert_eq!(account_1_balance(&builder), U512::from(TRANSFER_AMOUNT));
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME + 1);
+    assert_replayed(result);
+    assert_eq!(account_1_balance(&builder), U512::from(TRANSFER_AMOUNT));
+
+    let result = exec_at(&mut builder, transfer_deploy([43; 32]), BLOCK_TIME + 1);
+    assert!(result.is_success(), "other deploy hashes should execute");
+    assert_eq!(account_1_balance(&builder), U512::from(2 * TRANSFER_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_reject_replay_within_a_block() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(DEPLOY_HASH))
+        .push_deploy(transfer_deploy(DEPLOY_HASH))
+        .with_block_time(BLOCK_TIME)
+        .build();
+    builder.exec_block(exec_request);
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results[0].is_success(), "expected success");
+    assert_replayed(&results[1]);
+    assert_eq!(account_1_balance(&builder), U512::from(TRANSFER_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_reject_replay_within_an_exec_request() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(DEPLOY_HASH))
+        .push_deploy(transfer_deploy([43; 32]))
+        .push_deploy(transfer_deploy(DEPLOY_HASH))
+        .push_deploy(transfer_deploy(DEPLOY_HASH))
+        .with_block_time(BLOCK_TIME)
+        .build();
+    builder.exec(exec_request);
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results[0].is_success(), "expected success");
+    assert!(
+        results[1].is_success(),
+        "other deploy hashes should execute"
+    );
+    assert_replayed(&results[2]);
+    assert_replayed(&results[3]);
+}
+
+#[ignore]
+#[test]
+fn should_reject_replay_of_failed_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let revert_deploy = || {
+        DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(REVERT_WASM, ())
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(DEPLOY_HASH)
+            .build()
+    };
+
+    let result = exec_at(&mut builder, revert_deploy(), BLOCK_TIME);
+    assert!(result.is_failure(), "expected session to fail");
+    assert!(
+        !result.has_precondition_failure(),
+        "failed session should be charged for"
+    );
+
+    let result = exec_at(&mut builder, revert_deploy(), BLOCK_TIME + 1);
+    assert_replayed(result);
+}
+
+#[ignore]
+#[test]
+fn should_not_record_deploy_failing_precondition() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let unauthorized_deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[ACCOUNT_1_ADDR])
+        .with_deploy_hash(DEPLOY_HASH)
+        .build();
+
+    let result = exec_at(&mut builder, unauthorized_deploy, BLOCK_TIME);
+    assert!(result.has_precondition_failure(), "expected auth failure");
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME + 1);
+    assert!(result.is_success(), "expected success");
+}
+
+#[ignore]
+#[test]
+fn should_execute_deploy_hash_again_once_expired() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+
+    let last_rejected_block_time = BLOCK_TIME + DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS - 1;
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        last_rejected_block_time,
+    );
+    assert_replayed(result);
+
+    let expired_block_time = BLOCK_TIME + DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS;
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        expired_block_time,
+    );
+    assert!(result.is_success(), "expected success once expired");
+    assert_eq!(account_1_balance(&builder), U512::from(2 * TRANSFER_AMOUNT));
+
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        expired_block_time + 1,
+    );
+    assert_replayed(result);
+}
+
+#[ignore]
+#[test]
+fn should_expire_deploy_hash_as_set_by_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+
+    let new_protocol_version = {
+        let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch)
+    };
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_deploy_hash_expiry_millis(NEW_DEPLOY_HASH_EXPIRY_MILLIS)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let expiry_millis = u64::from(NEW_DEPLOY_HASH_EXPIRY_MILLIS);
+    let result = exec_at_version(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        BLOCK_TIME + expiry_millis - 1,
+        new_protocol_version,
+    );
+    assert_replayed(result);
+
+    let result = exec_at_version(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        BLOCK_TIME + expiry_millis,
+        new_protocol_version,
+    );
+    assert!(result.is_success(), "expected success under the new expiry");
+    assert_eq!(account_1_balance(&builder), U512::from(2 * TRANSFER_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_record_of_executed_deploy() {
+    let storage_costs = StorageCosts::new(GAS_PER_BYTE);
+
+    let mut unrecorded_builder = InMemoryWasmTestBuilder::default();
+    unrecorded_builder.run_genesis(&genesis_config(storage_costs, 0));
+    let unrecorded_result = exec_at(
+        &mut unrecorded_builder,
+        transfer_deploy(DEPLOY_HASH),
+        BLOCK_TIME,
+    );
+    assert!(unrecorded_result.is_success(), "expected success");
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(
+        storage_costs,
+        DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
+    ));
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+
+    let record_bytes =
+        replay_protection::executed_deploy_record_size(DEPLOY_HASH, BlockTime::new(BLOCK_TIME));
+    assert_eq!(
+        result.storage_bytes() - unrecorded_result.storage_bytes(),
+        record_bytes
+    );
+    assert_eq!(
+        result.cost() - unrecorded_result.cost(),
+        Gas::new(storage_costs.calculate(record_bytes))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_neither_check_nor_record_deploy_hash_when_expiry_is_zero() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(*DEFAULT_STORAGE_COSTS, 0));
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), BLOCK_TIME + 1);
+    assert!(
+        result.is_success(),
+        "expected success without replay protection"
+    );
+    assert_eq!(account_1_balance(&builder), U512::from(2 * TRANSFER_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_delete_expired_records() {
+    let expiry_millis = u64::from(NEW_DEPLOY_HASH_EXPIRY_MILLIS);
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(*DEFAULT_STORAGE_COSTS, expiry_millis));
+
+    let expired_hash = DEPLOY_HASH;
+    let live_hash = [43; 32];
+    let latest_hash = [44; 32];
+    let record_keys = |deploy_hash, block_time| {
+        [
+            replay_protection::executed_deploy_key(deploy_hash),
+            replay_protection::executed_deploy_index_key(deploy_hash, BlockTime::new(block_time)),
+        ]
+    };
+
+    let result = exec_at(&mut builder, transfer_deploy(expired_hash), BLOCK_TIME);
+    assert!(result.is_success(), "expected success");
+    let result = exec_at(&mut builder, transfer_deploy(live_hash), BLOCK_TIME + 1);
+    assert!(result.is_success(), "expected success");
+    for key in record_keys(expired_hash, BLOCK_TIME)
+        .iter()
+        .chain(record_keys(live_hash, BLOCK_TIME + 1).iter())
+    {
+        assert!(builder.query(None, *key, &[]).is_ok(), "should have record");
+    }
+
+    let latest_block_time = BLOCK_TIME + expiry_millis;
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(latest_hash),
+        latest_block_time,
+    );
+    assert!(result.is_success(), "expected success");
+
+    for key in record_keys(expired_hash, BLOCK_TIME).iter() {
+        assert!(
+            builder.query(None, *key, &[]).is_err(),
+            "should have deleted expired record"
+        );
+    }
+    for key in record_keys(live_hash, BLOCK_TIME + 1)
+        .iter()
+        .chain(record_keys(latest_hash, latest_block_time).iter())
+    {
+        assert!(
+            builder.query(None, *key, &[]).is_ok(),
+            "should keep live record"
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_keep_record_of_later_execution_when_deleting_earlier_index_entry() {
+    let expiry_millis = u64::from(NEW_DEPLOY_HASH_EXPIRY_MILLIS);
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config(*DEFAULT_STORAGE_COSTS, expiry_millis));
+
+    // A backlog of expired records longer than a single commit deletes, so that the index entry
+    // of the first execution below is still queued once the deploy hash executes again.
+    let backlog_request = (0..=MAX_EXPIRED_DEPLOY_RECORDS_PER_COMMIT)
+        .fold(ExecuteRequestBuilder::new(), |exec_request, index| {
+            let mut deploy_hash = [255; 32];
+            deploy_hash[..8].copy_from_slice(&(index as u64).to_be_bytes());
+            let deploy = DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_session_code(DO_NOTHING_WASM, ())
+                .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+                .with_deploy_hash(deploy_hash)
+                .build();
+            exec_request.push_deploy(deploy)
+        })
+        .with_block_time(BLOCK_TIME)
+        .build();
+    builder.exec_block(backlog_request);
+
+    let first_block_time = BLOCK_TIME + 1;
+    let result = exec_at(&mut builder, transfer_deploy(DEPLOY_HASH), first_block_time);
+    assert!(result.is_success(), "expected success");
+
+    let second_block_time = first_block_time + expiry_millis;
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        second_block_time,
+    );
+    assert!(result.is_success(), "expected success once expired");
+    let first_index_key =
+        replay_protection::executed_deploy_index_key(DEPLOY_HASH, BlockTime::new(first_block_time));
+    assert!(
+        builder.query(None, first_index_key, &[]).is_ok(),
+        "should leave index entry of first execution queued"
+    );
+
+    // Drains the rest of the backlog along with the index entry of the first execution.
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy([43; 32]),
+        second_block_time + 1,
+    );
+    assert!(result.is_success(), "expected success");
+    assert!(
+        builder.query(None, first_index_key, &[]).is_err(),
+        "should have deleted index entry of first execution"
+    );
+    assert!(
+        builder
+            .query(
+                None,
+                replay_protection::executed_deploy_key(DEPLOY_HASH),
+                &[]
+            )
+            .is_ok(),
+        "should keep record of second execution"
+    );
+
+    let result = exec_at(
+        &mut builder,
+        transfer_deploy(DEPLOY_HASH),
+        second_block_time + 2,
+    );
+    assert_replayed(result);
+}
@@ -0,0 +1,237 @@
+use engine_core::engine_state::{genesis::GenesisConfig, upgrade::ActivationPoint};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_HOST_FUNCTION_COSTS, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::host_function_costs::{HostFunctionCost, HostFunctionCosts};
+use types::{bytesrepr::ToBytes, AccessRights, CLValue, Key, ProtocolVersion, URef, U512};
+
+const CONTRACT_NAMED_KEYS: &str = "named_keys.wasm";
+const COMMAND_CREATE_UREF1: &str = "create-uref1";
+const COMMAND_TEST_READ_UREF1: &str = "test-read-uref1";
+const UREF1_VALUE: &str = "Hello, world!";
+const NEW_UREF_BASE_COST: u32 = 10_000;
+const NEW_UREF_PER_BYTE_COST: u32 = 100;
+const READ_PER_BYTE_COST: u32 = 10;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+fn genesis_config_with_host_function_costs(
+    host_function_costs: HostFunctionCosts,
+) -> GenesisConfig {
+    let genesis_config = &*DEFAULT_GENESIS_CONFIG;
+    GenesisConfig::new(
+        genesis_config.name().to_string(),
+        genesis_config.timestamp(),
+        genesis_config.protocol_version(),
+        genesis_config.mint_installer_bytes().to_vec(),
+        genesis_config.proof_of_stake_installer_bytes().to_vec(),
+        genesis_config.standard_payment_installer_bytes().to_vec(),
+        genesis_config.accounts().to_vec(),
+        genesis_config.wasm_costs(),
+        host_function_costs,
+        genesis_config.storage_costs(),
+        genesis_config.wasm_limits(),
+        genesis_config.deploy_hash_expiry_millis(),
+    )
+}
+
+fn new_uref_costs(base: u32, per_byte: u32) -> HostFunctionCosts {
+    HostFunctionCosts {
+        new_uref: HostFunctionCost::new(base, per_byte),
+        ..Default::default()
+    }
+}
+
+/// Returns the cost of a deploy which creates a single `URef` holding `UREF1_VALUE`.
+fn create_uref_cost(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+) -> Gas {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        (COMMAND_CREATE_UREF1,),
+    )
+    .with_protocol_version(protocol_version)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have response");
+    utils::get_success_result(response).cost()
+}
+
+/// Returns the cost of a deploy which reads `UREF1_VALUE` back twice.
+fn genesis_read_uref_cost(genesis_config: &GenesisConfig) -> Gas {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(genesis_config);
+    create_uref_cost(&mut builder, genesis_config.protocol_version());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        (COMMAND_TEST_READ_UREF1,),
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have response");
+    utils::get_success_result(response).cost()
+}
+
+fn genesis_create_uref_cost(genesis_config: &GenesisConfig) -> Gas {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(genesis_config);
+    create_uref_cost(&mut builder, genesis_config.protocol_version())
+}
+
+#[ignore]
+#[test]
+fn should_charge_base_cost_of_host_function_calls() {
+    let free_cost = genesis_create_uref_cost(&DEFAULT_GENESIS_CONFIG);
+
+    let genesis_config =
+        genesis_config_with_host_function_costs(new_uref_costs(NEW_UREF_BASE_COST, 0));
+    let cost = genesis_create_uref_cost(&genesis_config);
+
+    assert_eq!(cost - free_cost, Gas::new(U512::from(NEW_UREF_BASE_COST)));
+}
+
+#[ignore]
+#[test]
+fn should_charge_per_byte_cost_of_host_function_calls() {
+    let free_cost = genesis_create_uref_cost(&DEFAULT_GENESIS_CONFIG);
+
+    let genesis_config =
+        genesis_config_with_host_function_costs(new_uref_costs(0, NEW_UREF_PER_BYTE_COST));
+    let cost = genesis_create_uref_cost(&genesis_config);
+
+    let value_size = CLValue::from_t(String::from(UREF1_VALUE))
+        .expect("should create CLValue")
+        .to_bytes()
+        .expect("should serialize CLValue")
+        .len();
+    let expected_cost = U512::from(NEW_UREF_PER_BYTE_COST) * U512::from(value_size);
+
+    assert_eq!(cost - free_cost, Gas::new(expected_cost));
+}
+
+#[ignore]
+#[test]
+fn should_charge_read_per_byte_of_key_and_value() {
+    let free_cost = genesis_read_uref_cost(&DEFAULT_GENESIS_CONFIG);
+
+    let genesis_config = genesis_config_with_host_function_costs(HostFunctionCosts {
+        read: HostFunctionCost::new(0, READ_PER_BYTE_COST),
+        ..Default::default()
+    });
+    let cost = genesis_read_uref_cost(&genesis_config);
+
+    let key_size = Key::URef(URef::new([0; 32], AccessRights::READ_ADD_WRITE))
+        .to_bytes()
+        .expect("should serialize key")
+        .len();
+    let value_size = CLValue::from_t(String::from(UREF1_VALUE))
+        .expect("should create CLValue")
+        .inner_bytes()
+        .len();
+    // The contract reads the value twice.
+    let expected_cost = U512::from(READ_PER_BYTE_COST) * U512::from(2 * (key_size + value_size));
+
+    assert_eq!(cost - free_cost, Gas::new(expected_cost));
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_host_function_costs() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let free_cost = create_uref_cost(&mut builder, *DEFAULT_PROTOCOL_VERSION);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+    let new_host_function_costs = new_uref_costs(NEW_UREF_BASE_COST, 0);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_host_function_costs(new_host_function_costs)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_host_function_costs = builder
+        .get_engine_state()
+        .host_function_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded costs");
+    assert_eq!(upgraded_host_function_costs, new_host_function_costs);
+
+    let upgraded_wasm_costs = builder
+        .get_engine_state()
+        .wasm_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have costs");
+    assert_eq!(
+        upgraded_wasm_costs, *DEFAULT_WASM_COSTS,
+        "upgrading host function costs should not change wasm costs"
+    );
+
+    let cost = create_uref_cost(&mut builder, new_protocol_version);
+
+    assert_eq!(cost - free_cost, Gas::new(U512::from(NEW_UREF_BASE_COST)));
+}
+
+#[ignore]
+#[test]
+fn should_keep_host_function_costs_when_upgrading_only_wasm_costs() {
+    let host_function_costs = new_uref_costs(NEW_UREF_BASE_COST, NEW_UREF_PER_BYTE_COST);
+    let genesis_config = genesis_config_with_host_function_costs(host_function_costs);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_costs(*DEFAULT_WASM_COSTS)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_host_function_costs = builder
+        .get_engine_state()
+        .host_function_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have costs");
+    assert_eq!(upgraded_host_function_costs, host_function_costs);
+    assert_ne!(upgraded_host_function_costs, *DEFAULT_HOST_FUNCTION_COSTS);
+}
@@ -0,0 +1,101 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const ACCOUNT_PREFIX: [u8; 1] = [0];
+const HASH_PREFIX: [u8; 1] = [1];
+const PAGE_SIZE: u32 = 1_000;
+
+#[ignore]
+#[test]
+fn should_list_accounts() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let (keys, maybe_next_page_token) = builder
+        .list_keys(
+            builder.get_post_state_hash(),
+            &ACCOUNT_PREFIX,
+            PAGE_SIZE,
+            None,
+        )
+        .expect("should list keys");
+
+    assert!(maybe_next_page_token.is_none());
+    assert!(keys.contains(&Key::Account(DEFAULT_ACCOUNT_ADDR)));
+    assert!(keys.contains(&Key::Account(ACCOUNT_1_ADDR)));
+    assert!(keys.iter().all(|key| match key {
+        Key::Account(_) => true,
+        _ => false,
+    }));
+}
+
+#[ignore]
+#[test]
+fn should_list_contracts() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let (keys, _) = builder
+        .list_keys(builder.get_post_state_hash(), &HASH_PREFIX, PAGE_SIZE, None)
+        .expect("should list keys");
+
+    assert!(!keys.is_empty());
+    assert!(keys.iter().all(|key| match key {
+        Key::Hash(_) => true,
+        _ => false,
+    }));
+}
+
+#[ignore]
+#[test]
+fn should_list_keys_a_page_at_a_time() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let (all_keys, _) = builder
+        .list_keys(post_state_hash.clone(), &[], PAGE_SIZE, None)
+        .expect("should list keys");
+    assert!(all_keys.len() > 1);
+
+    let mut paged_keys = Vec::new();
+    let mut maybe_page_token = None;
+    loop {
+        let (keys, maybe_next_page_token) = builder
+            .list_keys(post_state_hash.clone(), &[], 1, maybe_page_token)
+            .expect("should list keys");
+        assert_eq!(keys.len(), 1);
+        paged_keys.extend(keys);
+        if maybe_next_page_token.is_none() {
+            break;
+        }
+        maybe_page_token = maybe_next_page_token;
+    }
+
+    assert_eq!(paged_keys, all_keys);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_list_keys_under_unknown_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let result = builder.list_keys(vec![1u8; 32], &ACCOUNT_PREFIX, PAGE_SIZE, None);
+
+    assert!(result.is_err());
+}
@@ -1,6 +1,13 @@
 mod contract_api;
 mod deploy;
 mod examples;
+mod host_function_costs;
+mod list_keys;
+mod query_with_proof;
 mod regression;
+mod state_diff;
+mod storage_costs;
 mod system_contracts;
 mod upgrade;
+mod view;
+mod wasm_limits;
@@ -0,0 +1,65 @@
+use std::convert::TryInto;
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key};
+
+const UNKNOWN_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+
+fn post_state_hash(builder: &InMemoryWasmTestBuilder) -> Blake2bHash {
+    builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should parse post state hash")
+}
+
+#[ignore]
+#[test]
+fn should_prove_inclusion_of_genesis_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let proof = builder
+        .query_with_proof(None, key)
+        .expect("should get proof");
+
+    assert_eq!(proof.key(), &key);
+    match proof
+        .verify(&post_state_hash(&builder))
+        .expect("proof should verify")
+    {
+        Some(StoredValue::Account(account)) => {
+            assert_eq!(account.public_key(), DEFAULT_ACCOUNT_ADDR)
+        }
+        other => panic!("expected an account, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_prove_exclusion_of_unknown_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let proof = builder
+        .query_with_proof(None, Key::Account(UNKNOWN_ACCOUNT_ADDR))
+        .expect("should get proof");
+
+    assert_eq!(proof.verify(&post_state_hash(&builder)), Ok(None));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_prove_against_unknown_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let result = builder.query_with_proof(Some(vec![1u8; 32]), Key::Account(DEFAULT_ACCOUNT_ADDR));
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,67 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+#[ignore]
+#[test]
+fn should_diff_identical_states_to_nothing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let post_state_hash = builder.get_post_state_hash();
+    let trie_diff = builder
+        .diff(post_state_hash.clone(), post_state_hash)
+        .expect("should get diff");
+
+    assert!(trie_diff.added.is_empty());
+    assert!(trie_diff.removed.is_empty());
+    assert!(trie_diff.changed.is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_diff_states_before_and_after_transfer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let pre_state_hash = builder.get_post_state_hash();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    let post_state_hash = builder.get_post_state_hash();
+
+    let trie_diff = builder
+        .diff(pre_state_hash.clone(), post_state_hash.clone())
+        .expect("should get diff");
+    assert!(trie_diff.added.contains(&Key::Account(ACCOUNT_1_ADDR)));
+    assert!(!trie_diff.changed.is_empty());
+    assert!(trie_diff.removed.is_empty());
+
+    let reverse_diff = builder
+        .diff(post_state_hash, pre_state_hash)
+        .expect("should get diff");
+    assert_eq!(reverse_diff.removed, trie_diff.added);
+    assert_eq!(reverse_diff.changed, trie_diff.changed);
+    assert!(reverse_diff.added.is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_diff_against_unknown_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let result = builder.diff(builder.get_post_state_hash(), vec![1u8; 32]);
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,143 @@
+use engine_core::engine_state::{
+    execution_result::ExecutionResult, genesis::GenesisConfig, upgrade::ActivationPoint,
+};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_HOST_FUNCTION_COSTS, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_STORAGE_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::storage_costs::StorageCosts;
+use types::{ProtocolVersion, U512};
+
+const CONTRACT_NAMED_KEYS: &str = "named_keys.wasm";
+const COMMAND_CREATE_UREF1: &str = "create-uref1";
+const GAS_PER_BYTE: u32 = 1_000;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+fn genesis_config_with_storage_costs(storage_costs: StorageCosts) -> GenesisConfig {
+    let genesis_config = &*DEFAULT_GENESIS_CONFIG;
+    GenesisConfig::new(
+        genesis_config.name().to_string(),
+        genesis_config.timestamp(),
+        genesis_config.protocol_version(),
+        genesis_config.mint_installer_bytes().to_vec(),
+        genesis_config.proof_of_stake_installer_bytes().to_vec(),
+        genesis_config.standard_payment_installer_bytes().to_vec(),
+        genesis_config.accounts().to_vec(),
+        genesis_config.wasm_costs(),
+        genesis_config.host_function_costs(),
+        storage_costs,
+        genesis_config.wasm_limits(),
+        genesis_config.deploy_hash_expiry_millis(),
+    )
+}
+
+/// Runs a deploy which creates a single `URef` and returns its result.
+fn create_uref(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+) -> &ExecutionResult {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS,
+        (COMMAND_CREATE_UREF1,),
+    )
+    .with_protocol_version(protocol_version)
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have response");
+    utils::get_success_result(response)
+}
+
+#[ignore]
+#[test]
+fn should_charge_per_byte_added_to_global_state() {
+    let mut free_builder = InMemoryWasmTestBuilder::default();
+    free_builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let free_result = create_uref(&mut free_builder, *DEFAULT_PROTOCOL_VERSION);
+    assert!(free_result.is_success(), "expected success");
+    assert!(
+        free_result.storage_bytes() > 0,
+        "creating a uref should grow global state"
+    );
+
+    let genesis_config = genesis_config_with_storage_costs(StorageCosts::new(GAS_PER_BYTE));
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+    let result = create_uref(&mut builder, *DEFAULT_PROTOCOL_VERSION);
+    assert!(result.is_success(), "expected success");
+    assert_eq!(result.storage_bytes(), free_result.storage_bytes());
+
+    let expected_cost = U512::from(GAS_PER_BYTE) * U512::from(result.storage_bytes());
+    assert_eq!(result.cost() - free_result.cost(), Gas::new(expected_cost));
+}
+
+#[ignore]
+#[test]
+fn should_fail_if_storage_cost_exceeds_gas_limit() {
+    let genesis_config = genesis_config_with_storage_costs(StorageCosts::new(u32::max_value()));
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+
+    let result = create_uref(&mut builder, *DEFAULT_PROTOCOL_VERSION);
+    let error_message = format!("{}", result.error().expect("should have error"));
+    assert!(
+        error_message.contains("GasLimit"),
+        "expected gas limit error"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_storage_costs() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch);
+    let new_storage_costs = StorageCosts::new(GAS_PER_BYTE);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_storage_costs(new_storage_costs)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_storage_costs = builder
+        .get_engine_state()
+        .storage_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded costs");
+    assert_eq!(upgraded_storage_costs, new_storage_costs);
+    assert_ne!(upgraded_storage_costs, *DEFAULT_STORAGE_COSTS);
+
+    let upgraded_host_function_costs = builder
+        .get_engine_state()
+        .host_function_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have costs");
+    assert_eq!(
+        upgraded_host_function_costs, *DEFAULT_HOST_FUNCTION_COSTS,
+        "upgrading storage costs should not change host function costs"
+    );
+
+    let result = create_uref(&mut builder, new_protocol_version);
+    assert!(result.is_success(), "expected success");
+}
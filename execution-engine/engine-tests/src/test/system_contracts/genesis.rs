@@ -4,7 +4,8 @@ use engine_core::engine_state::{
 };
 use engine_shared::{motes::Motes, stored_value::StoredValue};
 use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
+    utils, InMemoryWasmTestBuilder, DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS, DEFAULT_HOST_FUNCTION_COSTS,
+    DEFAULT_STORAGE_COSTS, DEFAULT_WASM_COSTS, DEFAULT_WASM_LIMITS, MINT_INSTALL_CONTRACT,
     POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
 use types::{account::PublicKey, Key, ProtocolVersion, U512};
@@ -54,6 +55,9 @@ fn should_run_genesis() {
     let accounts = vec![account_1, account_2];
     let protocol_version = ProtocolVersion::V1_0_0;
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+    let storage_costs = *DEFAULT_STORAGE_COSTS;
+    let wasm_limits = *DEFAULT_WASM_LIMITS;
 
     let genesis_config = GenesisConfig::new(
         name,
@@ -64,6 +68,10 @@ fn should_run_genesis() {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        host_function_costs,
+        storage_costs,
+        wasm_limits,
+        DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
     );
 
     let mut builder = InMemoryWasmTestBuilder::default();
@@ -140,6 +148,9 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
+        let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+        let storage_costs = *DEFAULT_STORAGE_COSTS;
+        let wasm_limits = *DEFAULT_WASM_LIMITS;
 
         GenesisConfig::new(
             name,
@@ -150,6 +161,10 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
         )
     };
 
@@ -192,6 +207,9 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
+        let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+        let storage_costs = *DEFAULT_STORAGE_COSTS;
+        let wasm_limits = *DEFAULT_WASM_LIMITS;
 
         GenesisConfig::new(
             name,
@@ -202,6 +220,10 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            wasm_limits,
+            DEFAULT_DEPLOY_HASH_EXPIRY_MILLIS,
         )
     };
 
@@ -0,0 +1,105 @@
+use engine_shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform};
+use engine_test_support::internal::InMemoryWasmTestBuilder;
+use proof_of_stake::{Queue, QueueEntry, BONDING_KEY, UNBONDING_KEY};
+use types::{bytesrepr::ToBytes, BlockTime, CLValue, Key, U512};
+
+use super::{setup, ACCOUNT_1_ADDR, ACCOUNT_1_BOND, ACCOUNT_2_ADDR, ACCOUNT_2_BOND};
+
+const PENDING_BOND: u64 = 300;
+const PENDING_BOND_TIMESTAMP: u64 = 100;
+const PENDING_UNBOND: u64 = 50;
+const PENDING_UNBOND_TIMESTAMP: u64 = 200;
+const PENDING_UNBOND_ERA_HEIGHT: u64 = 7;
+
+/// Writes a queue to the local state of the Proof of Stake contract, as its `bond` and `unbond`
+/// entry points would.
+fn write_pos_queue(
+    builder: &InMemoryWasmTestBuilder,
+    effects: &mut AdditiveMap<Key, Transform>,
+    queue_key: u8,
+    entry: QueueEntry,
+) {
+    let pos_addr = builder.get_pos_contract_uref().addr();
+    let local_key = Key::local(pos_addr, &queue_key.to_bytes().unwrap());
+    let queue = CLValue::from_t(Queue(vec![entry])).unwrap();
+    effects.insert(local_key, Transform::Write(StoredValue::CLValue(queue)));
+}
+
+#[ignore]
+#[test]
+fn should_return_bid_state_of_genesis_validators() {
+    let builder = setup();
+
+    let bid_state = builder.get_bid_state();
+
+    assert_eq!(
+        bid_state.bids().get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_BOND))
+    );
+    assert_eq!(
+        bid_state.bids().get(&ACCOUNT_2_ADDR),
+        Some(&U512::from(ACCOUNT_2_BOND))
+    );
+    assert!(bid_state.bonding_queue().is_empty());
+    assert!(bid_state.unbonding_queue().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_return_pending_bonds_and_unbonds() {
+    let mut builder = setup();
+
+    // With no bonding delays, `bond` and `unbond` apply their requests as soon as they are made,
+    // so the pending requests are written to the queues directly.
+    let mut effects = AdditiveMap::new();
+    write_pos_queue(
+        &builder,
+        &mut effects,
+        BONDING_KEY,
+        QueueEntry {
+            validator: ACCOUNT_1_ADDR,
+            amount: U512::from(PENDING_BOND),
+            timestamp: BlockTime::new(PENDING_BOND_TIMESTAMP),
+            era_height: 0,
+        },
+    );
+    write_pos_queue(
+        &builder,
+        &mut effects,
+        UNBONDING_KEY,
+        QueueEntry {
+            validator: ACCOUNT_2_ADDR,
+            amount: U512::from(PENDING_UNBOND),
+            timestamp: BlockTime::new(PENDING_UNBOND_TIMESTAMP),
+            era_height: PENDING_UNBOND_ERA_HEIGHT,
+        },
+    );
+    let prestate_hash = builder.get_post_state_hash();
+    builder.commit_effects(prestate_hash, effects);
+
+    let bid_state = builder.get_bid_state();
+
+    // Pending requests are not yet applied to the stakes.
+    assert_eq!(
+        bid_state.bids().get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_BOND))
+    );
+    assert_eq!(
+        bid_state.bids().get(&ACCOUNT_2_ADDR),
+        Some(&U512::from(ACCOUNT_2_BOND))
+    );
+
+    assert_eq!(bid_state.bonding_queue().len(), 1);
+    let bond = bid_state.bonding_queue()[0];
+    assert_eq!(bond.validator(), ACCOUNT_1_ADDR);
+    assert_eq!(bond.amount(), U512::from(PENDING_BOND));
+    assert_eq!(bond.timestamp(), BlockTime::new(PENDING_BOND_TIMESTAMP));
+    assert_eq!(bond.era_height(), 0);
+
+    assert_eq!(bid_state.unbonding_queue().len(), 1);
+    let unbond = bid_state.unbonding_queue()[0];
+    assert_eq!(unbond.validator(), ACCOUNT_2_ADDR);
+    assert_eq!(unbond.amount(), U512::from(PENDING_UNBOND));
+    assert_eq!(unbond.timestamp(), BlockTime::new(PENDING_UNBOND_TIMESTAMP));
+    assert_eq!(unbond.era_height(), PENDING_UNBOND_ERA_HEIGHT);
+}
@@ -0,0 +1,65 @@
+use engine_core::engine_state::{
+    distribute_rewards::{DistributeRewardsError, DistributeRewardsResult},
+    genesis::POS_BONDING_PURSE,
+};
+use types::{account::PublicKey, U512};
+
+use super::{
+    get_pos_purse_balance, setup, ACCOUNT_1_ADDR, ACCOUNT_1_BOND, ACCOUNT_2_ADDR, ACCOUNT_2_BOND,
+};
+
+const UNKNOWN_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+
+#[ignore]
+#[test]
+fn should_restake_distributed_rewards() {
+    let mut builder = setup();
+
+    let bonding_balance_before = get_pos_purse_balance(&builder, POS_BONDING_PURSE);
+
+    let distribute_rewards_result = builder.commit_distribute_rewards(vec![
+        (ACCOUNT_1_ADDR, U512::from(10)),
+        (ACCOUNT_2_ADDR, U512::from(20)),
+        (ACCOUNT_2_ADDR, U512::from(5)),
+    ]);
+
+    let bonded_validators = match distribute_rewards_result {
+        DistributeRewardsResult::Success {
+            bonded_validators, ..
+        } => bonded_validators,
+        other => panic!("expected success, got {}", other),
+    };
+    assert_eq!(
+        bonded_validators.get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_BOND + 10))
+    );
+    assert_eq!(
+        bonded_validators.get(&ACCOUNT_2_ADDR),
+        Some(&U512::from(ACCOUNT_2_BOND + 25))
+    );
+
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        bonding_balance_before + U512::from(35)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_reward_unknown_validator() {
+    let mut builder = setup();
+    let post_state_hash = builder.get_post_state_hash();
+
+    let distribute_rewards_result = builder.commit_distribute_rewards(vec![
+        (ACCOUNT_1_ADDR, U512::from(10)),
+        (UNKNOWN_ADDR, U512::from(10)),
+    ]);
+
+    match distribute_rewards_result {
+        DistributeRewardsResult::Failure(DistributeRewardsError::UnknownValidator(validator)) => {
+            assert_eq!(validator, UNKNOWN_ADDR)
+        }
+        other => panic!("expected unknown validator error, got {}", other),
+    }
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
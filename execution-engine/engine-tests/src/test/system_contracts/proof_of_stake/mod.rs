@@ -1,5 +1,61 @@
+mod bid_state;
 mod bonding;
 mod commit_validators;
+mod distribute_rewards;
 mod finalize_payment;
 mod get_payment_purse;
 mod refund_purse;
+mod slashing;
+mod unbond_payout;
+
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{utils, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS};
+use types::{account::PublicKey, Key, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_1_BALANCE: u64 = 2000;
+const ACCOUNT_1_BOND: u64 = 1000;
+
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_2_BALANCE: u64 = 2000;
+const ACCOUNT_2_BOND: u64 = 200;
+
+/// Runs genesis with `ACCOUNT_1_ADDR` and `ACCOUNT_2_ADDR` bonded as validators, in addition to
+/// the default accounts.
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account_1 = GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BOND.into()),
+        );
+        let account_2 = GenesisAccount::new(
+            ACCOUNT_2_ADDR,
+            Motes::new(ACCOUNT_2_BALANCE.into()),
+            Motes::new(ACCOUNT_2_BOND.into()),
+        );
+        tmp.push(account_1);
+        tmp.push(account_2);
+        tmp
+    };
+
+    let genesis_config = utils::create_genesis_config(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+    builder
+}
+
+/// Returns the balance of the named purse of the Proof of Stake contract.
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse)
+}
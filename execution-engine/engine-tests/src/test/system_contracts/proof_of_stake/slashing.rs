@@ -0,0 +1,108 @@
+use engine_core::engine_state::{
+    genesis::{POS_BONDING_PURSE, POS_REWARDS_PURSE},
+    slash::{SlashError, SlashResult},
+};
+use types::{account::PublicKey, U512};
+
+use super::{
+    get_pos_purse_balance, setup, ACCOUNT_1_ADDR, ACCOUNT_1_BOND, ACCOUNT_2_ADDR, ACCOUNT_2_BOND,
+};
+
+const UNKNOWN_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+
+#[ignore]
+#[test]
+fn should_slash_validator_into_rewards_purse() {
+    let mut builder = setup();
+
+    let bonding_balance_before = get_pos_purse_balance(&builder, POS_BONDING_PURSE);
+    let rewards_balance_before = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+
+    let slash_amount = U512::from(300);
+    let slash_result = builder.commit_slash(vec![(ACCOUNT_1_ADDR, slash_amount)]);
+
+    let bonded_validators = match slash_result {
+        SlashResult::Success {
+            bonded_validators, ..
+        } => bonded_validators,
+        other => panic!("expected success, got {}", other),
+    };
+    assert_eq!(
+        bonded_validators.get(&ACCOUNT_1_ADDR),
+        Some(&U512::from(ACCOUNT_1_BOND - 300))
+    );
+    assert_eq!(
+        bonded_validators.get(&ACCOUNT_2_ADDR),
+        Some(&U512::from(ACCOUNT_2_BOND))
+    );
+
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        bonding_balance_before - slash_amount
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_balance_before + slash_amount
+    );
+}
+
+#[ignore]
+#[test]
+fn should_remove_fully_slashed_validator() {
+    let mut builder = setup();
+
+    let slash_result = builder.commit_slash(vec![
+        (ACCOUNT_2_ADDR, U512::from(150)),
+        (ACCOUNT_2_ADDR, U512::from(50)),
+    ]);
+
+    match slash_result {
+        SlashResult::Success {
+            bonded_validators, ..
+        } => {
+            assert!(!bonded_validators.contains_key(&ACCOUNT_2_ADDR));
+            assert!(bonded_validators.contains_key(&ACCOUNT_1_ADDR));
+        }
+        other => panic!("expected success, got {}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_unknown_validator() {
+    let mut builder = setup();
+    let post_state_hash = builder.get_post_state_hash();
+
+    let slash_result = builder.commit_slash(vec![(UNKNOWN_ADDR, U512::one())]);
+
+    match slash_result {
+        SlashResult::Failure(SlashError::UnknownValidator(validator)) => {
+            assert_eq!(validator, UNKNOWN_ADDR)
+        }
+        other => panic!("expected unknown validator error, got {}", other),
+    }
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_more_than_stake() {
+    let mut builder = setup();
+    let post_state_hash = builder.get_post_state_hash();
+
+    let slash_result = builder.commit_slash(vec![(ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_BOND + 1))]);
+
+    match slash_result {
+        SlashResult::Failure(SlashError::Overdrawn {
+            validator,
+            stake,
+            amount,
+        }) => {
+            assert_eq!(validator, ACCOUNT_1_ADDR);
+            assert_eq!(stake, U512::from(ACCOUNT_1_BOND));
+            assert_eq!(amount, U512::from(ACCOUNT_1_BOND + 1));
+        }
+        other => panic!("expected overdrawn error, got {}", other),
+    }
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
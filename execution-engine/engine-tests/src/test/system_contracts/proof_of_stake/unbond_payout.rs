@@ -0,0 +1,140 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, POS_BONDING_PURSE},
+    unbond_payout::UnbondPayoutResult,
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+};
+use types::{account::PublicKey, U512};
+
+use super::{get_pos_purse_balance, setup, ACCOUNT_1_ADDR, ACCOUNT_1_BOND};
+
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
+const TEST_UNBOND: &str = "unbond";
+
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const VALIDATOR_1_BALANCE: u64 = 1_000_000_000;
+const VALIDATOR_1_BOND: u64 = 42_000;
+const VALIDATOR_1_UNBOND: u64 = 22_000;
+const VALIDATOR_2_ADDR: PublicKey = PublicKey::ed25519_from([43u8; 32]);
+const VALIDATOR_2_BOND: u64 = 50_000;
+
+fn assert_success(unbond_payout_result: UnbondPayoutResult) {
+    if let UnbondPayoutResult::Success { .. } = unbond_payout_result {
+        return;
+    }
+    panic!("expected success, got {}", unbond_payout_result);
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_empty_unbonding_queue() {
+    let mut builder = setup();
+
+    let unbond_payout_result = builder.commit_unbond_payout(1);
+
+    match unbond_payout_result {
+        UnbondPayoutResult::Success {
+            bonded_validators, ..
+        } => assert_eq!(
+            bonded_validators.get(&ACCOUNT_1_ADDR),
+            Some(&U512::from(ACCOUNT_1_BOND))
+        ),
+        other => panic!("expected success, got {}", other),
+    }
+    assert!(builder.get_bid_state().unbonding_queue().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_unbond_once_matured_by_era() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::new(
+            VALIDATOR_1_ADDR,
+            Motes::new(VALIDATOR_1_BALANCE.into()),
+            Motes::new(VALIDATOR_1_BOND.into()),
+        ));
+        tmp.push(GenesisAccount::new(
+            VALIDATOR_2_ADDR,
+            Motes::new(VALIDATOR_2_BOND.into()),
+            Motes::new(VALIDATOR_2_BOND.into()),
+        ));
+        tmp
+    };
+
+    let genesis_config = utils::create_genesis_config(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&genesis_config);
+
+    // The first payout records the era, after which unbonds are paid out by era.
+    assert_success(builder.commit_unbond_payout(1));
+
+    let unbond_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_1_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_UNBOND),
+            Some(U512::from(VALIDATOR_1_UNBOND)),
+        ),
+    )
+    .build();
+
+    let result = builder.exec(unbond_request);
+    if cfg!(feature = "highway") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let exec_response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let gas_cost = Motes::from_gas(utils::get_exec_costs(exec_response)[0], CONV_RATE)
+        .expect("should convert");
+
+    let validator_1_purse = builder
+        .get_account(VALIDATOR_1_ADDR)
+        .expect("should have validator 1 account")
+        .main_purse();
+    let balance_after_unbond = builder.get_purse_balance(validator_1_purse);
+    let total_bond = U512::from(VALIDATOR_1_BOND + VALIDATOR_2_BOND);
+
+    // The unbond isn't paid out until it matures.
+    assert_eq!(
+        balance_after_unbond,
+        U512::from(VALIDATOR_1_BALANCE) - gas_cost.value()
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        total_bond
+    );
+    let bid_state = builder.get_bid_state();
+    assert_eq!(bid_state.unbonding_queue().len(), 1);
+    assert_eq!(
+        bid_state.unbonding_queue()[0].amount(),
+        U512::from(VALIDATOR_1_UNBOND)
+    );
+
+    // Paying out in the same era leaves the unbond in the queue.
+    assert_success(builder.commit_unbond_payout(1));
+    assert_eq!(
+        builder.get_purse_balance(validator_1_purse),
+        balance_after_unbond
+    );
+    assert_eq!(builder.get_bid_state().unbonding_queue().len(), 1);
+
+    // Once the next era starts, the unbond is paid out from the bonding purse.
+    assert_success(builder.commit_unbond_payout(2));
+    assert_eq!(
+        builder.get_purse_balance(validator_1_purse),
+        balance_after_unbond + VALIDATOR_1_UNBOND
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        total_bond - VALIDATOR_1_UNBOND
+    );
+    assert!(builder.get_bid_state().unbonding_queue().is_empty());
+}
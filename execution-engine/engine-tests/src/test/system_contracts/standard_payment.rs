@@ -669,7 +669,7 @@ fn independent_standard_payments_should_not_write_the_same_keys() {
             .with_session_code(DO_NOTHING_WASM, ())
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
             .with_authorization_keys(&[account_1_public_key])
-            .with_deploy_hash([1; 32])
+            .with_deploy_hash([3; 32])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -0,0 +1,117 @@
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::executable_deploy_item::{
+    ExecutableDeployItem, DEFAULT_ENTRY_POINT_NAME,
+};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_CONFIG},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::CLValue;
+
+const PURSE_HOLDER_STORED_WASM: &str = "purse_holder_stored.wasm";
+const PURSE_HOLDER_STORED_CONTRACT_NAME: &str = "purse_holder_stored";
+const METHOD_ADD: &str = "add";
+const METHOD_VERSION: &str = "version";
+const PURSE_NAME: &str = "purse_1";
+const VERSION: &str = "1.0.0";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, PURSE_HOLDER_STORED_WASM, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+    builder
+}
+
+fn stored_contract_by_name(args: impl ArgsParser) -> ExecutableDeployItem {
+    ExecutableDeployItem::StoredContractByName {
+        name: PURSE_HOLDER_STORED_CONTRACT_NAME.to_string(),
+        entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
+        args: args
+            .parse()
+            .expect("should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("should serialize args"),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_return_the_value_of_a_view_call() {
+    let builder = setup();
+    let post_state_hash = builder.get_post_state_hash();
+
+    let return_value = builder
+        .view(
+            DEFAULT_ACCOUNT_ADDR,
+            stored_contract_by_name((METHOD_VERSION,)),
+        )
+        .expect("should view");
+
+    assert_eq!(return_value, CLValue::from_t(VERSION).unwrap());
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_view_a_stored_contract_by_uref() {
+    let builder = setup();
+    let contract_uref = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(PURSE_HOLDER_STORED_CONTRACT_NAME)
+        .and_then(|key| key.into_uref())
+        .expect("should have contract uref");
+    let contract = ExecutableDeployItem::StoredContractByURef {
+        uref: contract_uref.addr().to_vec(),
+        entry_point: DEFAULT_ENTRY_POINT_NAME.to_string(),
+        args: (METHOD_VERSION,)
+            .parse()
+            .expect("should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("should serialize args"),
+    };
+
+    let return_value = builder
+        .view(DEFAULT_ACCOUNT_ADDR, contract)
+        .expect("should view");
+
+    assert_eq!(return_value, CLValue::from_t(VERSION).unwrap());
+}
+
+#[ignore]
+#[test]
+fn should_reject_writes_in_a_view_call() {
+    let builder = setup();
+
+    let error = builder
+        .view(
+            DEFAULT_ACCOUNT_ADDR,
+            stored_contract_by_name((METHOD_ADD, PURSE_NAME)),
+        )
+        .expect_err("should fail to write in a view");
+
+    assert!(error.contains("ViewWrite"), "{}", error);
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key(PURSE_NAME));
+}
+
+#[ignore]
+#[test]
+fn should_reject_module_bytes_in_a_view_call() {
+    let builder = setup();
+
+    let contract = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Vec::new(),
+        args: Vec::new(),
+    };
+    let error = builder
+        .view(DEFAULT_ACCOUNT_ADDR, contract)
+        .expect_err("should fail to view module bytes");
+
+    assert!(error.contains("ExpectedStoredContract"), "{}", error);
+}
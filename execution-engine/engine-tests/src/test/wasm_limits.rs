@@ -0,0 +1,173 @@
+use engine_core::engine_state::{upgrade::ActivationPoint, Error};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_LIMITS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_limits::{WasmLimits, MAX_WASM_MEMORY_PAGES};
+use types::{ApiError, ProtocolVersion};
+
+const CONTRACT_GROW_MEMORY: &str = "grow_memory.wasm";
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const NEW_MAX_MEMORY_PAGES: u32 = 256;
+
+fn next_protocol_version() -> ProtocolVersion {
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor + 1, sem_ver.patch)
+}
+
+/// Runs a deploy which grows its memory by `pages` and returns its error, if any.
+fn grow_memory(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    pages: u32,
+) -> Option<String> {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GROW_MEMORY, (pages,))
+            .with_protocol_version(protocol_version)
+            .build();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have response");
+    utils::get_success_result(response)
+        .error()
+        .map(ToString::to_string)
+}
+
+#[ignore]
+#[test]
+fn should_not_grow_memory_beyond_max_memory_pages() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    assert_eq!(
+        grow_memory(&mut builder, *DEFAULT_PROTOCOL_VERSION, 1),
+        None
+    );
+
+    let error_message = grow_memory(
+        &mut builder,
+        *DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_WASM_LIMITS.max_memory_pages,
+    )
+    .expect("should fail to grow memory");
+    assert!(
+        error_message.contains(&format!("Revert({})", u32::from(ApiError::User(0)))),
+        "expected grow memory error, got {}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_wasm_limits() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let new_protocol_version = next_protocol_version();
+    let new_wasm_limits = WasmLimits {
+        max_memory_pages: NEW_MAX_MEMORY_PAGES,
+        ..*DEFAULT_WASM_LIMITS
+    };
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_wasm_limits(new_wasm_limits)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let upgraded_wasm_limits = builder
+        .get_engine_state()
+        .wasm_limits(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded limits");
+    assert_eq!(upgraded_wasm_limits, new_wasm_limits);
+
+    let previous_wasm_limits = builder
+        .get_engine_state()
+        .wasm_limits(*DEFAULT_PROTOCOL_VERSION)
+        .expect("should have result")
+        .expect("should have limits");
+    assert_eq!(previous_wasm_limits, *DEFAULT_WASM_LIMITS);
+
+    assert_eq!(
+        grow_memory(
+            &mut builder,
+            new_protocol_version,
+            DEFAULT_WASM_LIMITS.max_memory_pages,
+        ),
+        None
+    );
+}
+
+fn assert_upgrade_fails_with_invalid_wasm_limits(invalid_wasm_limits: WasmLimits) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(next_protocol_version())
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_wasm_limits(invalid_wasm_limits)
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidWasmLimits(invalid_wasm_limits).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_initial_memory_above_max() {
+    assert_upgrade_fails_with_invalid_wasm_limits(WasmLimits {
+        initial_memory_pages: DEFAULT_WASM_LIMITS.max_memory_pages + 1,
+        ..*DEFAULT_WASM_LIMITS
+    });
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_lowering_max_memory() {
+    assert_upgrade_fails_with_invalid_wasm_limits(WasmLimits {
+        max_memory_pages: DEFAULT_WASM_LIMITS.max_memory_pages - 1,
+        ..*DEFAULT_WASM_LIMITS
+    });
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_max_memory_beyond_wasm_address_space() {
+    assert_upgrade_fails_with_invalid_wasm_limits(WasmLimits {
+        max_memory_pages: MAX_WASM_MEMORY_PAGES + 1,
+        ..*DEFAULT_WASM_LIMITS
+    });
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_with_zeroed_wasm_limits() {
+    assert_upgrade_fails_with_invalid_wasm_limits(WasmLimits::new(0, 0, 0));
+}
@@ -0,0 +1,385 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+const HOST_FUNCTION_COST_SERIALIZED_LENGTH: usize = 2 * U32_SERIALIZED_LENGTH;
+const NUM_HOST_FUNCTIONS: usize = 47;
+pub const HOST_FUNCTION_COSTS_SERIALIZED_LENGTH: usize =
+    U32_SERIALIZED_LENGTH + NUM_HOST_FUNCTIONS * HOST_FUNCTION_COST_SERIALIZED_LENGTH;
+
+/// The cost of a single call to a host function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostFunctionCost {
+    /// Cost of each call, regardless of its arguments
+    pub base: u32,
+    /// Cost per byte of the arguments passed in Wasm memory
+    pub per_byte: u32,
+}
+
+impl HostFunctionCost {
+    pub fn new(base: u32, per_byte: u32) -> Self {
+        HostFunctionCost { base, per_byte }
+    }
+
+    /// Returns the cost of a call which passes `byte_count` bytes in Wasm memory.
+    pub fn calculate(&self, byte_count: u32) -> u64 {
+        u64::from(self.base) + u64::from(self.per_byte) * u64::from(byte_count)
+    }
+}
+
+impl ToBytes for HostFunctionCost {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.base.to_bytes()?);
+        ret.append(&mut self.per_byte.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COST_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCost {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (base, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (per_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((HostFunctionCost { base, per_byte }, rem))
+    }
+}
+
+/// The costs of calls to each of the host functions available to contracts.
+///
+/// The `gas` host function is not listed, as it is how the costs of Wasm opcodes are charged.
+///
+/// The costs are serialized as a list in the order of the fields below, so that costs serialized
+/// before a host function was added still deserialize, with that host function free.  New host
+/// functions must therefore be added at the end.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostFunctionCosts {
+    /// Cost of reading a value from global state, per byte of the key and of the value read.
+    pub read: HostFunctionCost,
+    /// Cost of reading a local value, per byte of the key and of the value read.
+    pub read_local: HostFunctionCost,
+    /// Cost of loading the named keys of the current context.
+    pub load_named_keys: HostFunctionCost,
+    /// Cost of writing a value to global state, per byte of the key and value.
+    pub write: HostFunctionCost,
+    /// Cost of writing a local value, per byte of the key and value.
+    pub write_local: HostFunctionCost,
+    /// Cost of adding to a value in global state, per byte of the key and value.
+    pub add: HostFunctionCost,
+    /// Cost of adding to a local value, per byte of the key and value.
+    pub add_local: HostFunctionCost,
+    /// Cost of creating a new `URef`, per byte of its initial value.
+    pub new_uref: HostFunctionCost,
+    /// Cost of getting the size of a runtime argument.
+    pub get_arg_size: HostFunctionCost,
+    /// Cost of getting a runtime argument, per byte of the destination buffer.
+    pub get_arg: HostFunctionCost,
+    /// Cost of returning a value from a contract, per byte of the value.
+    pub ret: HostFunctionCost,
+    /// Cost of calling a stored contract, per byte of the key and arguments.
+    pub call_contract: HostFunctionCost,
+    /// Cost of getting a named key, per byte of the name.
+    pub get_key: HostFunctionCost,
+    /// Cost of checking for a named key, per byte of the name.
+    pub has_key: HostFunctionCost,
+    /// Cost of putting a named key, per byte of the name and key.
+    pub put_key: HostFunctionCost,
+    /// Cost of removing a named key, per byte of the name.
+    pub remove_key: HostFunctionCost,
+    /// Cost of getting the caller of the current context.
+    pub get_caller: HostFunctionCost,
+    /// Cost of getting the block time.
+    pub get_blocktime: HostFunctionCost,
+    /// Cost of storing a function, per byte of its name and named keys.
+    pub store_function: HostFunctionCost,
+    /// Cost of storing a function at a hash, per byte of its name and named keys.
+    pub store_function_at_hash: HostFunctionCost,
+    /// Cost of validating a `URef`, per byte of the `URef`.
+    pub is_valid_uref: HostFunctionCost,
+    /// Cost of reverting execution.
+    pub revert: HostFunctionCost,
+    /// Cost of adding an associated key, per byte of the public key.
+    pub add_associated_key: HostFunctionCost,
+    /// Cost of removing an associated key, per byte of the public key.
+    pub remove_associated_key: HostFunctionCost,
+    /// Cost of updating an associated key, per byte of the public key.
+    pub update_associated_key: HostFunctionCost,
+    /// Cost of setting an action threshold.
+    pub set_action_threshold: HostFunctionCost,
+    /// Cost of creating a purse.
+    pub create_purse: HostFunctionCost,
+    /// Cost of transferring to an account, per byte of the public key and amount.
+    pub transfer_to_account: HostFunctionCost,
+    /// Cost of transferring from a purse to an account, per byte of the purse, public key and
+    /// amount.
+    pub transfer_from_purse_to_account: HostFunctionCost,
+    /// Cost of transferring between purses, per byte of the purses and amount.
+    pub transfer_from_purse_to_purse: HostFunctionCost,
+    /// Cost of getting the balance of a purse, per byte of the purse.
+    pub get_balance: HostFunctionCost,
+    /// Cost of getting the current phase.
+    pub get_phase: HostFunctionCost,
+    /// Cost of upgrading a contract, per byte of the name and key.
+    pub upgrade_contract_at_uref: HostFunctionCost,
+    /// Cost of getting a system contract.
+    pub get_system_contract: HostFunctionCost,
+    /// Cost of getting the main purse of the current account.
+    pub get_main_purse: HostFunctionCost,
+    /// Cost of reading the host buffer, per byte of the destination buffer.
+    pub read_host_buffer: HostFunctionCost,
+    /// Cost of emitting an event, per byte of the name and value.
+    pub emit_event: HostFunctionCost,
+    /// Cost of hashing with blake2b-256, per byte of the input.
+    pub blake2b: HostFunctionCost,
+    /// Cost of verifying an ed25519 signature, per byte of the message.
+    pub verify_signature: HostFunctionCost,
+    /// Cost of calling a contract's entry point, per byte of the key, name and arguments.
+    pub call_entry_point: HostFunctionCost,
+    /// Cost of storing several entry points, per byte of their names and named keys.
+    pub store_entry_points: HostFunctionCost,
+    /// Cost of storing several entry points at a hash, per byte of their names and named keys.
+    pub store_entry_points_at_hash: HostFunctionCost,
+    /// Cost of creating an empty contract package under a new `URef`.
+    pub create_contract_package_at_uref: HostFunctionCost,
+    /// Cost of adding a version to a contract package, per byte of the package and contract keys.
+    pub add_contract_version: HostFunctionCost,
+    /// Cost of enabling or disabling a version of a contract package, per byte of the package key.
+    pub set_contract_version_enabled: HostFunctionCost,
+    /// Cost of calling a given version of a contract package, per byte of the key, entry point
+    /// name and arguments.
+    pub call_versioned_contract: HostFunctionCost,
+    /// Cost of drawing random bytes, per byte drawn.
+    pub random_bytes: HostFunctionCost,
+}
+
+impl HostFunctionCosts {
+    /// Returns the costs in the order in which they are serialized.
+    fn to_vec(&self) -> Vec<HostFunctionCost> {
+        vec![
+            self.read,
+            self.read_local,
+            self.load_named_keys,
+            self.write,
+            self.write_local,
+            self.add,
+            self.add_local,
+            self.new_uref,
+            self.get_arg_size,
+            self.get_arg,
+            self.ret,
+            self.call_contract,
+            self.get_key,
+            self.has_key,
+            self.put_key,
+            self.remove_key,
+            self.get_caller,
+            self.get_blocktime,
+            self.store_function,
+            self.store_function_at_hash,
+            self.is_valid_uref,
+            self.revert,
+            self.add_associated_key,
+            self.remove_associated_key,
+            self.update_associated_key,
+            self.set_action_threshold,
+            self.create_purse,
+            self.transfer_to_account,
+            self.transfer_from_purse_to_account,
+            self.transfer_from_purse_to_purse,
+            self.get_balance,
+            self.get_phase,
+            self.upgrade_contract_at_uref,
+            self.get_system_contract,
+            self.get_main_purse,
+            self.read_host_buffer,
+            self.emit_event,
+            self.blake2b,
+            self.verify_signature,
+            self.call_entry_point,
+            self.store_entry_points,
+            self.store_entry_points_at_hash,
+            self.create_contract_package_at_uref,
+            self.add_contract_version,
+            self.set_contract_version_enabled,
+            self.call_versioned_contract,
+            self.random_bytes,
+        ]
+    }
+
+    /// Creates the costs from a list in the order in which they are serialized.  Host functions
+    /// missing from the end of the list are free.
+    fn from_slice(costs: &[HostFunctionCost]) -> Self {
+        let cost = |index: usize| costs.get(index).copied().unwrap_or_default();
+        HostFunctionCosts {
+            read: cost(0),
+            read_local: cost(1),
+            load_named_keys: cost(2),
+            write: cost(3),
+            write_local: cost(4),
+            add: cost(5),
+            add_local: cost(6),
+            new_uref: cost(7),
+            get_arg_size: cost(8),
+            get_arg: cost(9),
+            ret: cost(10),
+            call_contract: cost(11),
+            get_key: cost(12),
+            has_key: cost(13),
+            put_key: cost(14),
+            remove_key: cost(15),
+            get_caller: cost(16),
+            get_blocktime: cost(17),
+            store_function: cost(18),
+            store_function_at_hash: cost(19),
+            is_valid_uref: cost(20),
+            revert: cost(21),
+            add_associated_key: cost(22),
+            remove_associated_key: cost(23),
+            update_associated_key: cost(24),
+            set_action_threshold: cost(25),
+            create_purse: cost(26),
+            transfer_to_account: cost(27),
+            transfer_from_purse_to_account: cost(28),
+            transfer_from_purse_to_purse: cost(29),
+            get_balance: cost(30),
+            get_phase: cost(31),
+            upgrade_contract_at_uref: cost(32),
+            get_system_contract: cost(33),
+            get_main_purse: cost(34),
+            read_host_buffer: cost(35),
+            emit_event: cost(36),
+            blake2b: cost(37),
+            verify_signature: cost(38),
+            call_entry_point: cost(39),
+            store_entry_points: cost(40),
+            store_entry_points_at_hash: cost(41),
+            create_contract_package_at_uref: cost(42),
+            add_contract_version: cost(43),
+            set_contract_version_enabled: cost(44),
+            call_versioned_contract: cost(45),
+            random_bytes: cost(46),
+        }
+    }
+}
+
+impl ToBytes for HostFunctionCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.to_vec().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (costs, rem): (Vec<HostFunctionCost>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if costs.len() > NUM_HOST_FUNCTIONS {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        Ok((HostFunctionCosts::from_slice(&costs), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection, num, prop_compose};
+
+    use crate::host_function_costs::{HostFunctionCost, HostFunctionCosts, NUM_HOST_FUNCTIONS};
+
+    prop_compose! {
+        pub fn host_function_cost_arb()(
+            base in num::u32::ANY,
+            per_byte in num::u32::ANY,
+        ) -> HostFunctionCost {
+            HostFunctionCost { base, per_byte }
+        }
+    }
+
+    prop_compose! {
+        pub fn host_function_costs_arb()(
+            costs in collection::vec(host_function_cost_arb(), NUM_HOST_FUNCTIONS),
+        ) -> HostFunctionCosts {
+            HostFunctionCosts::from_slice(&costs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr::{self, FromBytes, ToBytes};
+
+    use super::gens;
+    use crate::host_function_costs::{HostFunctionCost, HostFunctionCosts, NUM_HOST_FUNCTIONS};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let free = HostFunctionCosts::default();
+        let mock = HostFunctionCosts {
+            write: HostFunctionCost::new(1_000, 10),
+            new_uref: HostFunctionCost::new(1_000, 10),
+            call_contract: HostFunctionCost::new(5_000, 1),
+            ..Default::default()
+        };
+        bytesrepr::test_serialization_roundtrip(&free);
+        bytesrepr::test_serialization_roundtrip(&mock);
+    }
+
+    #[test]
+    fn should_deserialize_costs_serialized_before_host_functions_were_added() {
+        let read = HostFunctionCost::new(1_000, 10);
+        let write = HostFunctionCost::new(2_000, 20);
+        let bytes = vec![
+            read,
+            HostFunctionCost::default(),
+            HostFunctionCost::default(),
+            write,
+        ]
+        .to_bytes()
+        .expect("should serialize");
+        let host_function_costs: HostFunctionCosts =
+            bytesrepr::deserialize(bytes).expect("should deserialize");
+        assert_eq!(
+            host_function_costs,
+            HostFunctionCosts {
+                read,
+                write,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_deserialize_costs_of_unknown_host_functions() {
+        let bytes = vec![HostFunctionCost::default(); NUM_HOST_FUNCTIONS + 1]
+            .to_bytes()
+            .expect("should serialize");
+        assert_eq!(
+            HostFunctionCosts::from_bytes(&bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+
+    #[test]
+    fn should_calculate_cost_without_overflow() {
+        let cost = HostFunctionCost::new(u32::max_value(), u32::max_value());
+        assert_eq!(cost.calculate(0), u64::from(u32::max_value()));
+        assert_eq!(
+            cost.calculate(u32::max_value()),
+            u64::from(u32::max_value()) * u64::from(u32::max_value()) + u64::from(u32::max_value())
+        );
+        assert_eq!(HostFunctionCost::new(10, 2).calculate(5), 20);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            host_function_costs in gens::host_function_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&host_function_costs);
+        }
+    }
+}
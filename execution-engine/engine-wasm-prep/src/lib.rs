@@ -1,20 +1,22 @@
+pub mod host_function_costs;
+pub mod storage_costs;
+pub mod validation;
 pub mod wasm_costs;
+pub mod wasm_limits;
 
 use std::fmt::{self, Display, Formatter};
 
 use parity_wasm::elements::{self, Module};
 use pwasm_utils::{self, stack_height};
 
-use crate::wasm_costs::WasmCosts;
-
-//NOTE: size of Wasm memory page is 64 KiB
-pub const MEM_PAGES: u32 = 64;
+use crate::{validation::ValidationReport, wasm_costs::WasmCosts, wasm_limits::WasmLimits};
 
 #[derive(Debug)]
 pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    ModuleTooLarge { size: usize, max_size: usize },
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,33 +31,60 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::ModuleTooLarge { size, max_size } => write!(
+                f,
+                "Module is {} bytes, exceeding the maximum of {} bytes",
+                size, max_size
+            ),
         }
     }
 }
 
 pub struct Preprocessor {
     wasm_costs: WasmCosts,
-    // Number of memory pages.
-    mem_pages: u32,
+    wasm_limits: WasmLimits,
 }
 
 impl Preprocessor {
-    pub fn new(wasm_costs: WasmCosts) -> Self {
+    pub fn new(wasm_costs: WasmCosts, wasm_limits: WasmLimits) -> Self {
         Self {
             wasm_costs,
-            mem_pages: MEM_PAGES,
+            wasm_limits,
         }
     }
 
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
+        let max_size = self.wasm_limits.max_module_size as usize;
+        if module_bytes.len() > max_size {
+            return Err(PreprocessingError::ModuleTooLarge {
+                size: module_bytes.len(),
+                max_size,
+            });
+        }
         let module = deserialize(module_bytes)?;
-        let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
+        // The initial memory can't exceed the max memory, as that would panic
+        let max_memory_pages = self.wasm_limits.max_memory_pages;
+        let initial_memory_pages = self
+            .wasm_limits
+            .initial_memory_pages()
+            .map(|pages| pages.min(max_memory_pages));
+        let module = pwasm_utils::externalize_mem(module, initial_memory_pages, max_memory_pages);
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
         let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
             .map_err(|_| PreprocessingError::StackLimiter)?;
         Ok(module)
     }
+
+    /// Checks `module_bytes` against all the rules enforced when preprocessing and executing a
+    /// module, returning every rule broken rather than stopping at the first.
+    pub fn validate(&self, module_bytes: &[u8]) -> ValidationReport {
+        validation::validate(
+            module_bytes,
+            &self.wasm_limits,
+            self.wasm_costs.max_stack_height,
+        )
+    }
 }
 
 // Returns a parity Module from bytes without making modifications or limits
@@ -0,0 +1,90 @@
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    U512,
+};
+
+pub const STORAGE_COSTS_SERIALIZED_LENGTH: usize = U32_SERIALIZED_LENGTH;
+
+/// The costs of growing global state.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StorageCosts {
+    /// Cost per byte added to global state
+    pub gas_per_byte: u32,
+}
+
+impl StorageCosts {
+    pub fn new(gas_per_byte: u32) -> Self {
+        StorageCosts { gas_per_byte }
+    }
+
+    /// Returns the cost of adding `byte_count` bytes to global state.
+    pub fn calculate(&self, byte_count: u64) -> U512 {
+        U512::from(self.gas_per_byte) * U512::from(byte_count)
+    }
+}
+
+impl ToBytes for StorageCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.gas_per_byte.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        STORAGE_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for StorageCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (gas_per_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((StorageCosts { gas_per_byte }, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{num, prop_compose};
+
+    use crate::storage_costs::StorageCosts;
+
+    prop_compose! {
+        pub fn storage_costs_arb()(gas_per_byte in num::u32::ANY) -> StorageCosts {
+            StorageCosts { gas_per_byte }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::{bytesrepr, U512};
+
+    use super::gens;
+    use crate::storage_costs::StorageCosts;
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&StorageCosts::default());
+        bytesrepr::test_serialization_roundtrip(&StorageCosts::new(10));
+    }
+
+    #[test]
+    fn should_calculate_cost_without_overflow() {
+        let storage_costs = StorageCosts::new(u32::max_value());
+        assert_eq!(
+            storage_costs.calculate(u64::max_value()),
+            U512::from(u32::max_value()) * U512::from(u64::max_value())
+        );
+        assert_eq!(StorageCosts::new(10).calculate(5), U512::from(50));
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            storage_costs in gens::storage_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&storage_costs);
+        }
+    }
+}
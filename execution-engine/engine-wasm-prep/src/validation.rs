@@ -0,0 +1,443 @@
+use std::fmt::{self, Display, Formatter};
+
+use parity_wasm::elements::{External, ImportCountType, Internal, Module};
+use pwasm_utils::{rules::InstructionType, stack_height};
+
+use crate::wasm_limits::WasmLimits;
+
+/// The names of the functions exported by the host to contracts under the "env" module.
+///
+/// This must list exactly the functions resolved by the engine's module import resolver.
+pub const HOST_FUNCTION_NAMES: &[&str] = &[
+    "read_value",
+    "read_value_local",
+    "load_named_keys",
+    "write",
+    "write_local",
+    "add",
+    "add_local",
+    "new_uref",
+    "get_arg_size",
+    "get_arg",
+    "ret",
+    "call_contract",
+    "get_key",
+    "has_key",
+    "put_key",
+    "gas",
+    "store_function",
+    "store_function_at_hash",
+    "is_valid_uref",
+    "revert",
+    "add_associated_key",
+    "remove_associated_key",
+    "update_associated_key",
+    "set_action_threshold",
+    "remove_key",
+    "get_caller",
+    "get_blocktime",
+    "create_purse",
+    "transfer_to_account",
+    "transfer_from_purse_to_account",
+    "transfer_from_purse_to_purse",
+    "get_balance",
+    "get_phase",
+    "upgrade_contract_at_uref",
+    "get_system_contract",
+    "get_main_purse",
+    "read_host_buffer",
+    "emit_event",
+    "blake2b",
+    "verify_signature",
+    "call_entry_point",
+    "store_entry_points",
+    "store_entry_points_at_hash",
+    "create_contract_package_at_uref",
+    "add_contract_version",
+    "set_contract_version_enabled",
+    "call_versioned_contract",
+    "random_bytes",
+];
+
+/// The module under which the host exports its functions and memory.
+pub const HOST_MODULE_NAME: &str = "env";
+
+/// The name of the function exported by session code and stored contracts called by default.
+pub const CALL_EXPORT_NAME: &str = "call";
+
+/// A single way in which a module breaks the engine's rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The module bytes exceed the max module size.
+    ModuleTooLarge { size: usize, max_size: usize },
+    /// The module bytes aren't a valid Wasm module.
+    Deserialize(String),
+    /// A function is imported from a module other than "env", or isn't a host function.
+    UnknownFunctionImport { module: String, field: String },
+    /// A memory, table or global is imported; only functions may be imported.
+    UnsupportedImport { module: String, field: String },
+    /// The module doesn't declare a memory.
+    MissingMemory,
+    /// The module declares more than one memory.
+    MultipleMemories(usize),
+    /// The initial or max number of pages of the module's memory exceeds the engine's limit.
+    MemoryTooLarge { pages: u32, max_pages: u32 },
+    /// The function at the given index of the module's function space uses a float instruction.
+    FloatInstruction { function_index: u32 },
+    /// The module doesn't export a function named "call".
+    MissingCallExport,
+    /// The module has a start function, which the engine doesn't run.
+    StartFunction,
+    /// The stack height limiter couldn't be injected into the module.
+    StackLimiter,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ValidationError::ModuleTooLarge { size, max_size } => write!(
+                f,
+                "Module is {} bytes, exceeding the maximum of {} bytes",
+                size, max_size
+            ),
+            ValidationError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
+            ValidationError::UnknownFunctionImport { module, field } => {
+                write!(f, "Unknown function import {}::{}", module, field)
+            }
+            ValidationError::UnsupportedImport { module, field } => write!(
+                f,
+                "Unsupported import {}::{}; only functions may be imported",
+                module, field
+            ),
+            ValidationError::MissingMemory => write!(f, "Module has no memory"),
+            ValidationError::MultipleMemories(count) => {
+                write!(f, "Module has {} memories; at most one is allowed", count)
+            }
+            ValidationError::MemoryTooLarge { pages, max_pages } => write!(
+                f,
+                "Memory of {} pages exceeds the maximum of {} pages",
+                pages, max_pages
+            ),
+            ValidationError::FloatInstruction { function_index } => write!(
+                f,
+                "Function {} uses a floating point instruction",
+                function_index
+            ),
+            ValidationError::MissingCallExport => write!(
+                f,
+                "Module doesn't export a \"{}\" function",
+                CALL_EXPORT_NAME
+            ),
+            ValidationError::StartFunction => write!(f, "Module has a start function"),
+            ValidationError::StackLimiter => write!(f, "Stack limiter error"),
+        }
+    }
+}
+
+/// The result of validating a module, listing every rule it breaks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if the module breaks none of the engine's rules.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    pub fn take_errors(self) -> Vec<ValidationError> {
+        self.errors
+    }
+}
+
+pub(crate) fn validate(
+    module_bytes: &[u8],
+    wasm_limits: &WasmLimits,
+    max_stack_height: u32,
+) -> ValidationReport {
+    let mut errors = vec![];
+
+    let max_size = wasm_limits.max_module_size as usize;
+    if module_bytes.len() > max_size {
+        errors.push(ValidationError::ModuleTooLarge {
+            size: module_bytes.len(),
+            max_size,
+        });
+    }
+
+    let module = match parity_wasm::deserialize_buffer::<Module>(module_bytes) {
+        Ok(module) => module,
+        Err(error) => {
+            errors.push(ValidationError::Deserialize(error.to_string()));
+            return ValidationReport { errors };
+        }
+    };
+
+    check_imports(&module, &mut errors);
+    check_memory(&module, wasm_limits, &mut errors);
+    check_floats(&module, &mut errors);
+    check_exports(&module, &mut errors);
+
+    if module.start_section().is_some() {
+        errors.push(ValidationError::StartFunction);
+    }
+
+    if stack_height::inject_limiter(module, max_stack_height).is_err() {
+        errors.push(ValidationError::StackLimiter);
+    }
+
+    ValidationReport { errors }
+}
+
+fn check_imports(module: &Module, errors: &mut Vec<ValidationError>) {
+    let entries = match module.import_section() {
+        Some(import_section) => import_section.entries(),
+        None => return,
+    };
+
+    for entry in entries {
+        let is_host_module = entry.module() == HOST_MODULE_NAME;
+        let module = entry.module().to_string();
+        let field = entry.field().to_string();
+        match entry.external() {
+            External::Function(_) => {
+                if !is_host_module || !HOST_FUNCTION_NAMES.contains(&entry.field()) {
+                    errors.push(ValidationError::UnknownFunctionImport { module, field });
+                }
+            }
+            // The preprocessor imports the module's own memory from the host, so it mustn't
+            // already import one.
+            External::Memory(_) | External::Table(_) | External::Global(_) => {
+                errors.push(ValidationError::UnsupportedImport { module, field });
+            }
+        }
+    }
+}
+
+fn check_memory(module: &Module, wasm_limits: &WasmLimits, errors: &mut Vec<ValidationError>) {
+    let memories = module
+        .memory_section()
+        .map(|memory_section| memory_section.entries())
+        .unwrap_or_default();
+
+    match memories.len() {
+        0 => errors.push(ValidationError::MissingMemory),
+        1 => {}
+        count => errors.push(ValidationError::MultipleMemories(count)),
+    }
+
+    // The preprocessor replaces the memory's limits entirely if the initial memory is overridden,
+    // and otherwise only sets its maximum if it has none.
+    if wasm_limits.initial_memory_pages().is_some() {
+        return;
+    }
+    let max_pages = wasm_limits.max_memory_pages;
+    for memory_type in memories {
+        let limits = memory_type.limits();
+        let pages = limits.maximum().unwrap_or(0).max(limits.initial());
+        if pages > max_pages {
+            errors.push(ValidationError::MemoryTooLarge { pages, max_pages });
+        }
+    }
+}
+
+fn check_floats(module: &Module, errors: &mut Vec<ValidationError>) {
+    let bodies = match module.code_section() {
+        Some(code_section) => code_section.bodies(),
+        None => return,
+    };
+
+    // Function indices count imported functions before those defined in the module.
+    let imported_function_count = module.import_count(ImportCountType::Function);
+    for (index, body) in bodies.iter().enumerate() {
+        let uses_floats =
+            body.code().elements().iter().any(|instruction| {
+                match InstructionType::op(instruction) {
+                    InstructionType::FloatConst
+                    | InstructionType::FloatComparison
+                    | InstructionType::Float
+                    | InstructionType::FloatConversion => true,
+                    _ => false,
+                }
+            });
+        if uses_floats {
+            errors.push(ValidationError::FloatInstruction {
+                function_index: (imported_function_count + index) as u32,
+            });
+        }
+    }
+}
+
+fn check_exports(module: &Module, errors: &mut Vec<ValidationError>) {
+    let exports_call = module
+        .export_section()
+        .map(|export_section| export_section.entries())
+        .unwrap_or_default()
+        .iter()
+        .any(|entry| match entry.internal() {
+            Internal::Function(_) => entry.field() == CALL_EXPORT_NAME,
+            _ => false,
+        });
+    if !exports_call {
+        errors.push(ValidationError::MissingCallExport);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder::{self, ModuleBuilder},
+        elements::{Instruction, Instructions},
+    };
+
+    use super::{ValidationError, CALL_EXPORT_NAME, HOST_MODULE_NAME};
+    use crate::wasm_limits::{WasmLimits, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_MODULE_SIZE};
+
+    const MEM_PAGES: u32 = DEFAULT_MAX_MEMORY_PAGES;
+    const MAX_MODULE_SIZE: usize = DEFAULT_MAX_MODULE_SIZE as usize;
+
+    const MAX_STACK_HEIGHT: u32 = 64 * 1024;
+
+    fn module_builder(instructions: Vec<Instruction>) -> ModuleBuilder {
+        builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .memory()
+            .with_min(1)
+            .build()
+    }
+
+    fn with_call_export(module_builder: ModuleBuilder) -> ModuleBuilder {
+        module_builder
+            .export()
+            .field(CALL_EXPORT_NAME)
+            .internal()
+            .func(0)
+            .build()
+    }
+
+    fn validate(module_builder: ModuleBuilder) -> Vec<ValidationError> {
+        let module_bytes = parity_wasm::serialize(module_builder.build()).unwrap();
+        super::validate(&module_bytes, &WasmLimits::default(), MAX_STACK_HEIGHT).take_errors()
+    }
+
+    #[test]
+    fn should_accept_valid_module() {
+        let errors = validate(with_call_export(module_builder(vec![Instruction::End])));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn should_report_invalid_bytes() {
+        let report = super::validate(&[0, 1, 2, 3], &WasmLimits::default(), MAX_STACK_HEIGHT);
+        match report.errors() {
+            [ValidationError::Deserialize(_)] => {}
+            other => panic!("unexpected errors {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_report_oversized_module() {
+        let module_bytes = vec![0; MAX_MODULE_SIZE + 1];
+        let report = super::validate(&module_bytes, &WasmLimits::default(), MAX_STACK_HEIGHT);
+        assert_eq!(
+            report.errors()[0],
+            ValidationError::ModuleTooLarge {
+                size: MAX_MODULE_SIZE + 1,
+                max_size: MAX_MODULE_SIZE,
+            }
+        );
+    }
+
+    #[test]
+    fn should_report_every_broken_rule() {
+        let instructions = vec![
+            Instruction::F32Const(0),
+            Instruction::Drop,
+            Instruction::End,
+        ];
+        let module_builder = module_builder(instructions)
+            .import()
+            .path(HOST_MODULE_NAME, "not_a_host_function")
+            .external()
+            .func(0)
+            .build();
+
+        let errors = validate(module_builder);
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::UnknownFunctionImport {
+                    module: HOST_MODULE_NAME.to_string(),
+                    field: "not_a_host_function".to_string()
+                },
+                // The imported function precedes the defined one in the function index space
+                ValidationError::FloatInstruction { function_index: 1 },
+                ValidationError::MissingCallExport,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_excess_memory() {
+        let module_builder = with_call_export(module_builder(vec![Instruction::End]))
+            .memory()
+            .with_min(MEM_PAGES + 1)
+            .build();
+
+        let errors = validate(module_builder);
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::MultipleMemories(2),
+                ValidationError::MemoryTooLarge {
+                    pages: MEM_PAGES + 1,
+                    max_pages: MEM_PAGES,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_check_memory_against_configured_limits() {
+        let module = with_call_export(
+            builder::module()
+                .function()
+                .signature()
+                .build()
+                .body()
+                .with_instructions(Instructions::new(vec![Instruction::End]))
+                .build()
+                .build()
+                .memory()
+                .with_min(1)
+                .with_max(Some(2 * MEM_PAGES))
+                .build(),
+        )
+        .build();
+        let module_bytes = parity_wasm::serialize(module).unwrap();
+
+        let report = super::validate(&module_bytes, &WasmLimits::default(), MAX_STACK_HEIGHT);
+        assert_eq!(
+            report.errors(),
+            &[ValidationError::MemoryTooLarge {
+                pages: 2 * MEM_PAGES,
+                max_pages: MEM_PAGES,
+            }]
+        );
+
+        let wasm_limits = WasmLimits::new(2 * MEM_PAGES, 0, DEFAULT_MAX_MODULE_SIZE);
+        let report = super::validate(&module_bytes, &wasm_limits, MAX_STACK_HEIGHT);
+        assert!(report.is_valid(), "{:?}", report);
+    }
+}
@@ -0,0 +1,177 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+const NUM_FIELDS: usize = 3;
+pub const WASM_LIMITS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+
+/// The default max number of 64 KiB pages of linear memory, i.e. 4 MiB.
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 64;
+/// The default number of pages a module's memory starts with; zero keeps each module's own.
+pub const DEFAULT_INITIAL_MEMORY_PAGES: u32 = 0;
+/// The default max size in bytes of a module.
+pub const DEFAULT_MAX_MODULE_SIZE: u32 = 2 * 1024 * 1024;
+/// The max number of 64 KiB pages addressable by a 32-bit linear memory, i.e. 4 GiB.
+pub const MAX_WASM_MEMORY_PAGES: u32 = 65_536;
+
+/// Limits on the resources used by Wasm modules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Max number of 64 KiB pages of linear memory a module may use
+    pub max_memory_pages: u32,
+    /// Number of pages every module's memory starts with, or zero to use the number declared by
+    /// the module
+    pub initial_memory_pages: u32,
+    /// Max size in bytes of a module before preprocessing
+    pub max_module_size: u32,
+}
+
+impl WasmLimits {
+    pub fn new(max_memory_pages: u32, initial_memory_pages: u32, max_module_size: u32) -> Self {
+        WasmLimits {
+            max_memory_pages,
+            initial_memory_pages,
+            max_module_size,
+        }
+    }
+
+    /// Returns `true` if the initial memory doesn't exceed the max memory, the max memory is
+    /// addressable by Wasm and modules of some size are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.initial_memory_pages <= self.max_memory_pages
+            && self.max_memory_pages <= MAX_WASM_MEMORY_PAGES
+            && self.max_module_size > 0
+    }
+
+    /// Returns `true` if these limits are valid and don't lower the max memory of `current`, which
+    /// would make contracts stored under `current` unusable.
+    pub fn is_valid_upgrade_of(&self, current: &WasmLimits) -> bool {
+        self.is_valid() && self.max_memory_pages >= current.max_memory_pages
+    }
+
+    /// Returns the number of pages every module's memory starts with, if it's overridden.
+    pub fn initial_memory_pages(&self) -> Option<u32> {
+        if self.initial_memory_pages == 0 {
+            None
+        } else {
+            Some(self.initial_memory_pages)
+        }
+    }
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        WasmLimits {
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            initial_memory_pages: DEFAULT_INITIAL_MEMORY_PAGES,
+            max_module_size: DEFAULT_MAX_MODULE_SIZE,
+        }
+    }
+}
+
+impl ToBytes for WasmLimits {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.max_memory_pages.to_bytes()?);
+        ret.append(&mut self.initial_memory_pages.to_bytes()?);
+        ret.append(&mut self.max_module_size.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_LIMITS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for WasmLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_memory_pages, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (initial_memory_pages, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_module_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_limits = WasmLimits {
+            max_memory_pages,
+            initial_memory_pages,
+            max_module_size,
+        };
+        Ok((wasm_limits, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{num, prop_compose};
+
+    use crate::wasm_limits::WasmLimits;
+
+    prop_compose! {
+        pub fn wasm_limits_arb()(
+            max_memory_pages in num::u32::ANY,
+            initial_memory_pages in num::u32::ANY,
+            max_module_size in num::u32::ANY,
+        ) -> WasmLimits {
+            WasmLimits {
+                max_memory_pages,
+                initial_memory_pages,
+                max_module_size,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::gens;
+    use crate::wasm_limits::{WasmLimits, MAX_WASM_MEMORY_PAGES};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&WasmLimits::default());
+        bytesrepr::test_serialization_roundtrip(&WasmLimits::new(256, 32, 1024));
+    }
+
+    #[test]
+    fn should_only_override_nonzero_initial_memory() {
+        assert_eq!(WasmLimits::default().initial_memory_pages(), None);
+        assert_eq!(
+            WasmLimits::new(64, 16, 1024).initial_memory_pages(),
+            Some(16)
+        );
+    }
+
+    #[test]
+    fn should_reject_initial_memory_above_max() {
+        assert!(WasmLimits::new(64, 64, 1024).is_valid());
+        assert!(!WasmLimits::new(64, 65, 1024).is_valid());
+    }
+
+    #[test]
+    fn should_reject_max_memory_beyond_wasm_address_space() {
+        assert!(WasmLimits::new(MAX_WASM_MEMORY_PAGES, 0, 1024).is_valid());
+        assert!(!WasmLimits::new(MAX_WASM_MEMORY_PAGES + 1, 0, 1024).is_valid());
+    }
+
+    #[test]
+    fn should_reject_zero_max_module_size() {
+        assert!(!WasmLimits::new(64, 0, 0).is_valid());
+        assert!(!WasmLimits::new(0, 0, 0).is_valid());
+    }
+
+    #[test]
+    fn should_reject_upgrade_lowering_max_memory() {
+        let current = WasmLimits::new(64, 0, 1024);
+        assert!(WasmLimits::new(64, 0, 512).is_valid_upgrade_of(&current));
+        assert!(WasmLimits::new(128, 0, 1024).is_valid_upgrade_of(&current));
+        assert!(!WasmLimits::new(63, 0, 1024).is_valid_upgrade_of(&current));
+        assert!(!WasmLimits::new(64, 0, 0).is_valid_upgrade_of(&current));
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            wasm_limits in gens::wasm_limits_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&wasm_limits);
+        }
+    }
+}
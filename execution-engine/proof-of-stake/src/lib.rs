@@ -9,6 +9,7 @@ mod runtime_provider;
 mod stakes;
 mod stakes_provider;
 
+use alloc::collections::BTreeMap;
 use core::marker::Sized;
 
 use types::{
@@ -18,10 +19,21 @@ use types::{
 };
 
 pub use crate::{
-    mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
-    runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
+    mint_provider::MintProvider,
+    queue::{Queue, QueueEntry},
+    queue_provider::QueueProvider,
+    runtime_provider::RuntimeProvider,
+    stakes::Stakes,
+    stakes_provider::StakesProvider,
 };
 
+/// The local state key of the bonding queue.
+pub const BONDING_KEY: u8 = 1;
+/// The local state key of the unbonding queue.
+pub const UNBONDING_KEY: u8 = 2;
+/// The local state key of the era height of the latest unbonding payout.
+pub const ERA_HEIGHT_KEY: u8 = 3;
+
 pub trait ProofOfStake:
     MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
 {
@@ -53,6 +65,7 @@ pub trait ProofOfStake:
         internal::unbond(self, maybe_amount, validator, timestamp)?;
 
         // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
+        // Once unbonds are paid out by era, `step` leaves them to `unbond_payout`.
         let unbonds = internal::step(self, timestamp)?;
         for entry in unbonds {
             self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
@@ -82,10 +95,46 @@ pub trait ProofOfStake:
     fn finalize_payment(&mut self, amount_spent: U512, account: PublicKey) -> Result<()> {
         internal::finalize_payment(self, amount_spent, account)
     }
+
+    fn slash(&mut self, slashes: BTreeMap<PublicKey, U512>) -> Result<()> {
+        let bonding_purse = internal::get_bonding_purse(self)?;
+        let rewards_purse = internal::get_rewards_purse(self)?;
+        let total_slashed = internal::slash(self, &slashes)?;
+        if total_slashed.is_zero() {
+            return Ok(());
+        }
+        // The slashed motes are forfeited to the rewards purse.
+        self.transfer_purse_to_purse(bonding_purse, rewards_purse, total_slashed)
+            .map_err(|_| Error::SlashTransferFailed)
+    }
+
+    fn distribute_rewards(&mut self, rewards: BTreeMap<PublicKey, U512>) -> Result<()> {
+        let bonding_purse = internal::get_bonding_purse(self)?;
+        let total_rewards = internal::distribute_rewards(self, &rewards)?;
+        if total_rewards.is_zero() {
+            return Ok(());
+        }
+        // The rewards are restaked, so the newly minted motes are moved to the bonding purse.
+        let source = self
+            .mint(total_rewards)
+            .map_err(|_| Error::RewardsMintFailed)?;
+        self.transfer_purse_to_purse(source, bonding_purse, total_rewards)
+            .map_err(|_| Error::RewardsTransferFailed)
+    }
+
+    fn unbond_payout(&mut self, era_height: u64) -> Result<()> {
+        let bonding_purse = internal::get_bonding_purse(self)?;
+        let unbonds = internal::pop_matured_unbonds(self, era_height)?;
+        for entry in unbonds {
+            self.transfer_purse_to_account(bonding_purse, entry.validator, entry.amount)
+                .map_err(|_| Error::UnbondTransferFailed)?;
+        }
+        Ok(())
+    }
 }
 
 mod internal {
-    use alloc::vec::Vec;
+    use alloc::{collections::BTreeMap, vec::Vec};
 
     use types::{
         account::PublicKey,
@@ -121,6 +170,10 @@ mod internal {
     /// The time from an unbonding request until the stakes are paid out.
     const UNBOND_DELAY: u64 = 0;
 
+    /// The number of eras from an unbonding request until the stakes are paid out by
+    /// `unbond_payout`.
+    const UNBOND_DELAY_ERAS: u64 = 1;
+
     /// The maximum number of pending bonding requests.
     const MAX_BOND_LEN: usize = 100;
 
@@ -147,7 +200,8 @@ mod internal {
         }
         stakes.validate_bonding(&validator, amount)?;
 
-        queue.push(validator, amount, timestamp)?;
+        let era_height = provider.read_era_height().unwrap_or_default();
+        queue.push(validator, amount, timestamp, era_height)?;
         provider.write_bonding(queue);
         Ok(())
     }
@@ -172,12 +226,16 @@ mod internal {
         // TODO: Make sure the destination is valid and the amount can be paid. The actual payment
         // will be made later, after the unbonding delay. contract_api::transfer_dry_run(POS_PURSE,
         // dest, amount)?;
-        queue.push(validator, payout, timestamp)?;
+        let era_height = provider.read_era_height().unwrap_or_default();
+        queue.push(validator, payout, timestamp, era_height)?;
         provider.write_unbonding(queue);
         Ok(())
     }
 
     /// Removes all due requests from the queues and applies them.
+    ///
+    /// Once an unbonding payout has recorded an era height, unbonding requests mature by era
+    /// instead, so they are left in the queue for `pop_matured_unbonds`.
     pub fn step<P: QueueProvider + StakesProvider>(
         provider: &mut P,
         timestamp: BlockTime,
@@ -186,8 +244,11 @@ mod internal {
         let mut unbonding_queue = provider.read_unbonding();
 
         let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
-        let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
+        let unbonds = if provider.read_era_height().is_some() {
+            Vec::new()
+        } else {
+            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)))
+        };
 
         if !unbonds.is_empty() {
             provider.write_unbonding(unbonding_queue);
@@ -205,6 +266,73 @@ mod internal {
         Ok(unbonds)
     }
 
+    /// Records the given era height, then removes all unbonding requests which have matured at it
+    /// from the unbonding queue, and returns them. Can only be called by the system account.
+    ///
+    /// Requests are stamped with the era height recorded when they are made, and mature
+    /// `UNBOND_DELAY_ERAS` eras later.
+    pub fn pop_matured_unbonds<P: QueueProvider + RuntimeProvider>(
+        provider: &mut P,
+        era_height: u64,
+    ) -> Result<Vec<QueueEntry>> {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        provider.write_era_height(era_height);
+        let mut unbonding_queue = provider.read_unbonding();
+        let unbonds = unbonding_queue.pop_matured(era_height.saturating_sub(UNBOND_DELAY_ERAS));
+        if !unbonds.is_empty() {
+            provider.write_unbonding(unbonding_queue);
+        }
+        Ok(unbonds)
+    }
+
+    /// Reduces the stakes of the given validators by the given amounts. Can only be called by the
+    /// system account. Returns the total amount slashed.
+    pub fn slash<P: RuntimeProvider + StakesProvider>(
+        provider: &mut P,
+        slashes: &BTreeMap<PublicKey, U512>,
+    ) -> Result<U512> {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let mut stakes = provider.read()?;
+        let mut total = U512::zero();
+        for (validator, amount) in slashes {
+            total += stakes.slash(validator, *amount)?;
+        }
+        provider.write(&stakes);
+        Ok(total)
+    }
+
+    /// Adds the given rewards to the stakes of the given validators. Can only be called by the
+    /// system account. Returns the total amount rewarded.
+    pub fn distribute_rewards<P: RuntimeProvider + StakesProvider>(
+        provider: &mut P,
+        rewards: &BTreeMap<PublicKey, U512>,
+    ) -> Result<U512> {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let mut stakes = provider.read()?;
+        let mut total = U512::zero();
+        for (validator, amount) in rewards {
+            if !stakes.0.contains_key(validator) {
+                return Err(Error::NotBonded);
+            }
+            stakes.bond(validator, *amount);
+            total += *amount;
+        }
+        provider.write(&stakes);
+        Ok(total)
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -340,6 +468,7 @@ mod internal {
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
             static UNBONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
+            static ERA_HEIGHT: RefCell<Option<u64>> = RefCell::new(None);
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((PublicKey::ed25519_from(KEY1), U512::from(1_000))).collect())
             );
@@ -363,6 +492,14 @@ mod internal {
             fn write_unbonding(&mut self, queue: Queue) {
                 UNBONDING.with(|ub| ub.replace(queue));
             }
+
+            fn read_era_height(&mut self) -> Option<u64> {
+                ERA_HEIGHT.with(|e| *e.borrow())
+            }
+
+            fn write_era_height(&mut self, era_height: u64) {
+                ERA_HEIGHT.with(|e| e.replace(Some(era_height)));
+            }
         }
 
         impl StakesProvider for Provider {
@@ -416,5 +553,28 @@ mod internal {
             step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_step_leaves_unbonds_to_payout_by_era() {
+            let mut provider = Provider;
+            provider.write_era_height(7);
+
+            unbond::<Provider>(
+                &mut provider,
+                Some(U512::from(400)),
+                PublicKey::ed25519_from(KEY1),
+                BlockTime::new(1),
+            )
+            .expect("partly unbond validator 1");
+            let unbonds =
+                step::<Provider>(&mut provider, BlockTime::new(1 + UNBOND_DELAY)).expect("step");
+
+            assert!(unbonds.is_empty());
+            assert_stakes(&[(KEY1, 600)]);
+            let unbonding_queue = provider.read_unbonding();
+            assert_eq!(unbonding_queue.0.len(), 1);
+            assert_eq!(unbonding_queue.0[0].amount, U512::from(400));
+            assert_eq!(unbonding_queue.0[0].era_height, 7);
+        }
     }
 }
@@ -16,4 +16,6 @@ pub trait MintProvider {
     ) -> Result<(), ()>;
 
     fn balance(&mut self, purse: URef) -> Option<U512>;
+
+    fn mint(&mut self, amount: U512) -> Result<URef, ()>;
 }
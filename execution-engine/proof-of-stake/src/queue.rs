@@ -3,11 +3,17 @@ use core::result;
 
 use types::{
     account::PublicKey,
-    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     system_contract_errors::pos::{Error, Result},
     BlockTime, CLType, CLTyped, U512,
 };
 
+/// Marks a versioned [`Queue`] layout.  It takes the place of the entry count which prefixes the
+/// unversioned layout stored by earlier versions of the contract, and can never be a real count.
+const QUEUE_VERSION_MARKER: u64 = u64::MAX;
+/// The version of the layout a [`Queue`] is serialized in, which follows the marker.
+const QUEUE_VERSION: u8 = 1;
+
 /// A pending entry in the bonding or unbonding queue.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct QueueEntry {
@@ -17,15 +23,23 @@ pub struct QueueEntry {
     pub amount: U512,
     /// The timestamp when the request was made.
     pub timestamp: BlockTime,
+    /// The era height when the request was made.
+    pub era_height: u64,
 }
 
 impl QueueEntry {
-    /// Creates a new `QueueEntry` with the current block's timestamp.
-    fn new(validator: PublicKey, amount: U512, timestamp: BlockTime) -> QueueEntry {
+    /// Creates a new `QueueEntry` with the current block's timestamp and era height.
+    fn new(
+        validator: PublicKey,
+        amount: U512,
+        timestamp: BlockTime,
+        era_height: u64,
+    ) -> QueueEntry {
         QueueEntry {
             validator,
             amount,
             timestamp,
+            era_height,
         }
     }
 }
@@ -36,6 +50,7 @@ impl ToBytes for QueueEntry {
         bytes.append(&mut self.validator.to_bytes()?);
         bytes.append(&mut self.amount.to_bytes()?);
         bytes.append(&mut self.timestamp.to_bytes()?);
+        bytes.append(&mut self.era_height.to_bytes()?);
         Ok(bytes)
     }
 
@@ -43,6 +58,7 @@ impl ToBytes for QueueEntry {
         self.validator.serialized_length()
             + self.amount.serialized_length()
             + self.timestamp.serialized_length()
+            + self.era_height.serialized_length()
     }
 }
 
@@ -51,10 +67,29 @@ impl FromBytes for QueueEntry {
         let (validator, bytes) = PublicKey::from_bytes(bytes)?;
         let (amount, bytes) = U512::from_bytes(bytes)?;
         let (timestamp, bytes) = BlockTime::from_bytes(bytes)?;
+        let (era_height, bytes) = u64::from_bytes(bytes)?;
         let entry = QueueEntry {
             validator,
             amount,
             timestamp,
+            era_height,
+        };
+        Ok((entry, bytes))
+    }
+}
+
+impl QueueEntry {
+    /// Deserializes an entry stored in the unversioned layout, which has no era height.  Such an
+    /// entry is treated as made at era height zero.
+    fn from_legacy_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator, bytes) = PublicKey::from_bytes(bytes)?;
+        let (amount, bytes) = U512::from_bytes(bytes)?;
+        let (timestamp, bytes) = BlockTime::from_bytes(bytes)?;
+        let entry = QueueEntry {
+            validator,
+            amount,
+            timestamp,
+            era_height: 0,
         };
         Ok((entry, bytes))
     }
@@ -74,7 +109,13 @@ impl Queue {
     /// Pushes a new entry to the end of the queue.
     ///
     /// Returns an error if the validator already has a request in the queue.
-    pub fn push(&mut self, validator: PublicKey, amount: U512, timestamp: BlockTime) -> Result<()> {
+    pub fn push(
+        &mut self,
+        validator: PublicKey,
+        amount: U512,
+        timestamp: BlockTime,
+        era_height: u64,
+    ) -> Result<()> {
         if self.0.iter().any(|entry| entry.validator == validator) {
             return Err(Error::MultipleRequests);
         }
@@ -83,7 +124,8 @@ impl Queue {
                 return Err(Error::TimeWentBackwards);
             }
         }
-        self.0.push(QueueEntry::new(validator, amount, timestamp));
+        self.0
+            .push(QueueEntry::new(validator, amount, timestamp, era_height));
         Ok(())
     }
 
@@ -96,11 +138,23 @@ impl Queue {
         self.0 = rest;
         older_than
     }
+
+    /// Returns all queue entries made at or before the specified era height.
+    pub fn pop_matured(&mut self, era_height: u64) -> Vec<QueueEntry> {
+        let (matured, rest) = self
+            .0
+            .iter()
+            .partition(|entry| entry.era_height <= era_height);
+        self.0 = rest;
+        matured
+    }
 }
 
 impl ToBytes for Queue {
     fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
         let mut bytes = bytesrepr::allocate_buffer(self)?;
+        bytes.append(&mut QUEUE_VERSION_MARKER.to_bytes()?);
+        bytes.append(&mut QUEUE_VERSION.to_bytes()?);
         bytes.append(&mut (self.0.len() as u64).to_bytes()?);
         for entry in &self.0 {
             bytes.append(&mut entry.to_bytes()?);
@@ -109,16 +163,38 @@ impl ToBytes for Queue {
     }
 
     fn serialized_length(&self) -> usize {
-        U64_SERIALIZED_LENGTH + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
+        U64_SERIALIZED_LENGTH
+            + U8_SERIALIZED_LENGTH
+            + U64_SERIALIZED_LENGTH
+            + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
     }
 }
 
 impl FromBytes for Queue {
+    /// Deserializes a queue in either the current layout or the unversioned layout stored by
+    /// earlier versions of the contract, whose entries take an era height of zero.
     fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (marker, rem) = u64::from_bytes(bytes)?;
+        if marker != QUEUE_VERSION_MARKER {
+            return Self::entries_from_bytes(bytes, QueueEntry::from_legacy_bytes);
+        }
+        let (version, rem) = u8::from_bytes(rem)?;
+        if version != QUEUE_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        Self::entries_from_bytes(rem, QueueEntry::from_bytes)
+    }
+}
+
+impl Queue {
+    fn entries_from_bytes(
+        bytes: &[u8],
+        entry_from_bytes: fn(&[u8]) -> result::Result<(QueueEntry, &[u8]), bytesrepr::Error>,
+    ) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
         let (len, mut bytes) = u64::from_bytes(bytes)?;
         let mut queue = Vec::new();
         for _ in 0..len {
-            let (entry, rest) = QueueEntry::from_bytes(bytes)?;
+            let (entry, rest) = entry_from_bytes(bytes)?;
             bytes = rest;
             queue.push(entry);
         }
@@ -134,10 +210,13 @@ impl CLTyped for Queue {
 
 #[cfg(test)]
 mod tests {
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
 
     use types::{
-        account::PublicKey, bytesrepr, system_contract_errors::pos::Error, BlockTime, U512,
+        account::PublicKey,
+        bytesrepr::{self, ToBytes},
+        system_contract_errors::pos::Error,
+        BlockTime, U512,
     };
 
     use super::{Queue, QueueEntry};
@@ -152,15 +231,21 @@ mod tests {
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(5), BlockTime::new(101)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(5), BlockTime::new(100), 0)
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::from(5), BlockTime::new(101), 0)
+        );
         assert_eq!(
             Err(Error::MultipleRequests),
-            queue.push(val1, U512::from(5), BlockTime::new(102))
+            queue.push(val1, U512::from(5), BlockTime::new(102), 0)
         );
         assert_eq!(
             Err(Error::TimeWentBackwards),
-            queue.push(val3, U512::from(5), BlockTime::new(100))
+            queue.push(val3, U512::from(5), BlockTime::new(100), 0)
         );
     }
 
@@ -170,31 +255,104 @@ mod tests {
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
-        assert_eq!(Ok(()), queue.push(val3, U512::from(7), BlockTime::new(102)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(5), BlockTime::new(100), 0)
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::from(6), BlockTime::new(101), 0)
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val3, U512::from(7), BlockTime::new(102), 0)
+        );
         assert_eq!(
             vec![
-                QueueEntry::new(val1, U512::from(5), BlockTime::new(100)),
-                QueueEntry::new(val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(val1, U512::from(5), BlockTime::new(100), 0),
+                QueueEntry::new(val2, U512::from(6), BlockTime::new(101), 0),
             ],
             queue.pop_due(BlockTime::new(101))
         );
         assert_eq!(
-            vec![QueueEntry::new(val3, U512::from(7), BlockTime::new(102)),],
+            vec![QueueEntry::new(val3, U512::from(7), BlockTime::new(102), 0),],
             queue.pop_due(BlockTime::new(105))
         );
     }
 
+    #[test]
+    fn test_pop_matured() {
+        let val1 = PublicKey::ed25519_from(KEY1);
+        let val2 = PublicKey::ed25519_from(KEY2);
+        let val3 = PublicKey::ed25519_from(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(5), BlockTime::new(100), 1)
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::from(6), BlockTime::new(101), 2)
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val3, U512::from(7), BlockTime::new(102), 2)
+        );
+        assert_eq!(Vec::<QueueEntry>::new(), queue.pop_matured(0));
+        assert_eq!(
+            vec![QueueEntry::new(val1, U512::from(5), BlockTime::new(100), 1)],
+            queue.pop_matured(1)
+        );
+        assert_eq!(
+            vec![
+                QueueEntry::new(val2, U512::from(6), BlockTime::new(101), 2),
+                QueueEntry::new(val3, U512::from(7), BlockTime::new(102), 2),
+            ],
+            queue.pop_matured(5)
+        );
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let val1 = PublicKey::ed25519_from(KEY1);
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        queue.push(val1, U512::from(5), BlockTime::new(0)).unwrap();
-        queue.push(val2, U512::from(6), BlockTime::new(1)).unwrap();
-        queue.push(val3, U512::from(7), BlockTime::new(2)).unwrap();
+        queue
+            .push(val1, U512::from(5), BlockTime::new(0), 0)
+            .unwrap();
+        queue
+            .push(val2, U512::from(6), BlockTime::new(1), 1)
+            .unwrap();
+        queue
+            .push(val3, U512::from(7), BlockTime::new(2), 0)
+            .unwrap();
         bytesrepr::test_serialization_roundtrip(&queue);
     }
+
+    #[test]
+    fn should_deserialize_legacy_queue() {
+        let val1 = PublicKey::ed25519_from(KEY1);
+        let val2 = PublicKey::ed25519_from(KEY2);
+        let legacy_bytes = {
+            let mut bytes = 2u64.to_bytes().unwrap();
+            for (validator, amount, timestamp) in &[
+                (val1, U512::from(5), BlockTime::new(0)),
+                (val2, U512::from(6), BlockTime::new(1)),
+            ] {
+                bytes.append(&mut validator.to_bytes().unwrap());
+                bytes.append(&mut amount.to_bytes().unwrap());
+                bytes.append(&mut timestamp.to_bytes().unwrap());
+            }
+            bytes
+        };
+        let queue: Queue = bytesrepr::deserialize(legacy_bytes).expect("should deserialize");
+        assert_eq!(
+            vec![
+                QueueEntry::new(val1, U512::from(5), BlockTime::new(0), 0),
+                QueueEntry::new(val2, U512::from(6), BlockTime::new(1), 0),
+            ],
+            queue.0
+        );
+    }
 }
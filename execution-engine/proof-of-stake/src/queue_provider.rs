@@ -12,4 +12,10 @@ pub trait QueueProvider {
 
     /// Writes unbonding queue.
     fn write_unbonding(&mut self, queue: Queue);
+
+    /// Reads the era height of the latest unbonding payout, if there was any.
+    fn read_era_height(&mut self) -> Option<u64>;
+
+    /// Writes the era height of the latest unbonding payout.
+    fn write_era_height(&mut self, era_height: u64);
 }
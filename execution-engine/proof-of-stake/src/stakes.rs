@@ -109,6 +109,30 @@ impl Stakes {
         Ok(stake)
     }
 
+    /// Subtracts `amount` from the validator's stakes as a penalty. Unlike unbonding, slashing is
+    /// not subject to the spread and relative decrease limits. If the whole stake is slashed, the
+    /// validator is removed.
+    ///
+    /// Returns the amount that was subtracted from the stakes, or an error if
+    /// * the amount is larger than the validator's stake,
+    /// * slashing would remove the last validator,
+    /// * validator was not bonded.
+    pub fn slash(&mut self, validator: &PublicKey, amount: U512) -> Result<U512> {
+        let stake = self.0.get_mut(validator).ok_or(Error::NotBonded)?;
+        if amount > *stake {
+            return Err(Error::SlashTooLarge);
+        }
+        if amount < *stake {
+            *stake -= amount;
+            return Ok(amount);
+        }
+        if self.0.len() == 1 {
+            return Err(Error::CannotUnbondLastValidator);
+        }
+        self.0.remove(validator);
+        Ok(amount)
+    }
+
     /// Adds `amount` to the validator's stakes.
     pub fn bond(&mut self, validator: &PublicKey, amount: U512) {
         self.0
@@ -259,6 +283,39 @@ mod tests {
         assert_eq!(new_stakes(&[(KEY1, 46)]), stakes);
     }
 
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 999), (KEY2, 1)]);
+        assert_eq!(
+            Ok(U512::from(990)),
+            stakes.slash(&PublicKey::ed25519_from(KEY1), U512::from(990))
+        );
+        assert_eq!(new_stakes(&[(KEY1, 9), (KEY2, 1)]), stakes);
+        assert_eq!(
+            Ok(U512::from(9)),
+            stakes.slash(&PublicKey::ed25519_from(KEY1), U512::from(9))
+        );
+        assert_eq!(new_stakes(&[(KEY2, 1)]), stakes);
+    }
+
+    #[test]
+    fn test_slash_errors() {
+        let mut stakes = new_stakes(&[(KEY1, 5)]);
+        assert_eq!(
+            Err(Error::NotBonded),
+            stakes.slash(&PublicKey::ed25519_from(KEY2), U512::from(1))
+        );
+        assert_eq!(
+            Err(Error::SlashTooLarge),
+            stakes.slash(&PublicKey::ed25519_from(KEY1), U512::from(6))
+        );
+        assert_eq!(
+            Err(Error::CannotUnbondLastValidator),
+            stakes.slash(&PublicKey::ed25519_from(KEY1), U512::from(5))
+        );
+        assert_eq!(new_stakes(&[(KEY1, 5)]), stakes);
+    }
+
     #[test]
     fn test_unbond_too_much_rel() {
         let mut stakes = new_stakes(&[(KEY1, 999), (KEY2, 1)]);
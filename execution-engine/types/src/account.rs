@@ -152,6 +152,9 @@ pub const ED25519_SERIALIZED_LENGTH: usize = ED25519_LENGTH;
 /// The upper bound of bytes in a serialized [`PublicKey`].
 pub const PUBLIC_KEY_SERIALIZED_MAX_LENGTH: usize = ED25519_SERIALIZED_LENGTH;
 
+/// The length in bytes of an Ed25519 signature.
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+
 /// A type alias for the raw bytes of an Ed25519 public key.
 pub type Ed25519Bytes = [u8; ED25519_LENGTH];
 
@@ -154,6 +154,12 @@ const MINT_ERROR_OFFSET: u32 = (POS_ERROR_OFFSET - 1) - u8::MAX as u32; // 65024
 /// # show_and_check!(
 /// 34 => HostBufferFull
 /// # );
+/// # show_and_check!(
+/// 35 => AllocLayout
+/// # );
+/// # show_and_check!(
+/// 36 => InvalidContractVersion
+/// # );
 ///
 /// // Mint errors:
 /// use casperlabs_types::system_contract_errors::mint::Error as MintError;
@@ -265,6 +271,18 @@ const MINT_ERROR_OFFSET: u32 = (POS_ERROR_OFFSET - 1) - u8::MAX as u32; // 65024
 /// # show_and_check!(
 /// 65_306 => PosError::SetRefundPurseCalledOutsidePayment
 /// # );
+/// # show_and_check!(
+/// 65_307 => PosError::SlashTooLarge
+/// # );
+/// # show_and_check!(
+/// 65_308 => PosError::SlashTransferFailed
+/// # );
+/// # show_and_check!(
+/// 65_309 => PosError::RewardsMintFailed
+/// # );
+/// # show_and_check!(
+/// 65_310 => PosError::RewardsTransferFailed
+/// # );
 ///
 /// // User-defined errors:
 /// # show_and_check!(
@@ -382,6 +400,8 @@ pub enum ApiError {
     HostBufferFull,
     /// Could not lay out an array in memory
     AllocLayout,
+    /// The contract package has no such version.
+    InvalidContractVersion,
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -516,6 +536,7 @@ impl From<ApiError> for u32 {
             ApiError::HostBufferEmpty => 33,
             ApiError::HostBufferFull => 34,
             ApiError::AllocLayout => 35,
+            ApiError::InvalidContractVersion => 36,
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             ApiError::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -563,6 +584,7 @@ impl Debug for ApiError {
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
             ApiError::AllocLayout => write!(f, "ApiError::AllocLayout")?,
+            ApiError::InvalidContractVersion => write!(f, "ApiError::InvalidContractVersion")?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
             ApiError::User(value) => write!(f, "ApiError::User({})", value)?,
@@ -621,6 +643,7 @@ pub fn result_from(value: i32) -> Result<(), ApiError> {
         33 => Err(ApiError::HostBufferEmpty),
         34 => Err(ApiError::HostBufferFull),
         35 => Err(ApiError::AllocLayout),
+        36 => Err(ApiError::InvalidContractVersion),
         _ => {
             if value > RESERVED_ERROR_MAX as i32 && value <= (2 * RESERVED_ERROR_MAX + 1) as i32 {
                 Err(ApiError::User(value as u16))
@@ -725,6 +748,7 @@ mod tests {
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
         round_trip(Err(ApiError::AllocLayout));
+        round_trip(Err(ApiError::InvalidContractVersion));
         round_trip(Err(ApiError::Mint(0)));
         round_trip(Err(ApiError::Mint(u8::MAX)));
         round_trip(Err(ApiError::ProofOfStake(0)));
@@ -59,3 +59,6 @@ pub use semver::SemVer;
 pub use system_contract_type::SystemContractType;
 pub use transfer_result::{TransferResult, TransferredTo};
 pub use uref::{URef, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
+
+/// The number of bytes returned by each call to the `random_bytes` host function.
+pub const RANDOM_BYTES_COUNT: usize = 32;
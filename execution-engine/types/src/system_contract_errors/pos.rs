@@ -77,6 +77,16 @@ pub enum Error {
     /// PoS contract's "set_refund_purse" method can only be called by the payment code of a
     /// deploy, but was called by the session code.
     SetRefundPurseCalledOutsidePayment,
+    /// Attempted to slash an amount which was larger than the validator's stake.
+    SlashTooLarge,
+    /// While slashing, the transfer from the Proof of Stake internal purse to the rewards purse
+    /// failed.
+    SlashTransferFailed,
+    /// While distributing rewards, minting the rewarded motes failed.
+    RewardsMintFailed,
+    /// While distributing rewards, the transfer of the minted motes to the Proof of Stake internal
+    /// purse failed.
+    RewardsTransferFailed,
 }
 
 impl CLTyped for Error {